@@ -49,7 +49,7 @@ fn bench_n_readers_no_writer(c: &mut Criterion) {
                                     let mut total = 0usize;
                                     for key in keys.iter() {
                                         let val: Cow<'_, [u8]> =
-                                            txn.get(db.dbi(), key.as_slice()).unwrap().unwrap();
+                                            txn.get(db, key.as_slice()).unwrap().unwrap();
                                         total += val.len();
                                     }
                                     black_box(total)
@@ -101,7 +101,7 @@ fn bench_n_readers_one_writer(c: &mut Criterion) {
                                     let mut total = 0usize;
                                     for key in keys.iter() {
                                         let val: Cow<'_, [u8]> =
-                                            txn.get(db.dbi(), key.as_slice()).unwrap().unwrap();
+                                            txn.get(db, key.as_slice()).unwrap().unwrap();
                                         total += val.len();
                                     }
                                     black_box(total)
@@ -152,7 +152,7 @@ fn bench_single_thread_sync_vs_unsync(c: &mut Criterion) {
             let db = txn.open_db(None).unwrap();
             let mut total = 0usize;
             for key in keys.iter() {
-                total += *txn.get::<ObjectLength>(db.dbi(), key.as_slice()).unwrap().unwrap();
+                total += *txn.get::<ObjectLength>(db, key.as_slice()).unwrap().unwrap();
             }
             black_box(total)
         })
@@ -164,7 +164,7 @@ fn bench_single_thread_sync_vs_unsync(c: &mut Criterion) {
             let db = txn.open_db(None).unwrap();
             let mut total = 0usize;
             for key in keys.iter() {
-                total += *txn.get::<ObjectLength>(db.dbi(), key.as_slice()).unwrap().unwrap();
+                total += *txn.get::<ObjectLength>(db, key.as_slice()).unwrap().unwrap();
             }
             black_box(total)
         })
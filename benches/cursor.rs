@@ -2,7 +2,7 @@
 mod utils;
 
 use criterion::{Criterion, criterion_group, criterion_main};
-use signet_libmdbx::{Cursor, ObjectLength, ReadResult, TransactionKind, ffi::*};
+use signet_libmdbx::{Cursor, ObjectLength, ReadResult, TransactionKind, UnboundCursor, ffi::*};
 use std::{hint::black_box, ptr};
 use utils::*;
 
@@ -196,6 +196,45 @@ fn bench_get_seq_for_loop_single_thread(c: &mut Criterion) {
     });
 }
 
+/// Benchmark of opening a cursor in each of many short read transactions.
+fn bench_cursor_open_per_txn(c: &mut Criterion) {
+    let n = 100;
+    let (_dir, env) = setup_bench_env(n);
+    let db = {
+        let txn = create_ro_sync(&env);
+        txn.open_db(None).unwrap()
+    };
+    let key = bench_key(n / 2);
+    c.bench_function("cursor::churn::open", |b| {
+        b.iter(|| {
+            let txn = create_ro_sync(&env);
+            let mut cursor = txn.cursor(db).unwrap();
+            black_box(cursor.set::<ObjectLength>(&key).unwrap());
+        })
+    });
+}
+
+/// Benchmark of rebinding one cursor in each of many short read
+/// transactions.
+fn bench_cursor_rebind_per_txn(c: &mut Criterion) {
+    let n = 100;
+    let (_dir, env) = setup_bench_env(n);
+    let db = {
+        let txn = create_ro_sync(&env);
+        txn.open_db(None).unwrap()
+    };
+    let key = bench_key(n / 2);
+    let mut unbound = Some(UnboundCursor::new().unwrap());
+    c.bench_function("cursor::churn::rebind", |b| {
+        b.iter(|| {
+            let txn = create_ro_sync(&env);
+            let mut cursor = txn.bind_cursor(unbound.take().unwrap(), db).unwrap();
+            black_box(cursor.set::<ObjectLength>(&key).unwrap());
+            unbound = Some(cursor.unbind().unwrap());
+        })
+    });
+}
+
 /// Benchmark of raw MDBX sequential read performance (control).
 fn bench_get_seq_raw(c: &mut Criterion) {
     let n = 1000;
@@ -247,6 +286,7 @@ criterion_group! {
     name = benches;
     config = quick_config();
     targets = bench_get_seq_iter, bench_get_seq_cursor, bench_get_seq_for_loop, bench_get_seq_raw,
-              bench_get_seq_iter_single_thread, bench_get_seq_cursor_single_thread, bench_get_seq_for_loop_single_thread
+              bench_get_seq_iter_single_thread, bench_get_seq_cursor_single_thread, bench_get_seq_for_loop_single_thread,
+              bench_cursor_open_per_txn, bench_cursor_rebind_per_txn
 }
 criterion_main!(benches);
@@ -132,6 +132,51 @@ fn bench_open_db_no_cache_named(c: &mut Criterion) {
     });
 }
 
+/// Benchmark DB opens in a fresh transaction, served by the environment
+/// cache.
+fn bench_open_db_env_cached_named(c: &mut Criterion) {
+    let (_dir, env) = setup_bench_db(10);
+    // Prime the environment cache
+    let _ = env.begin_ro_unsync().unwrap().open_db(Some(NAMED_DB)).unwrap();
+
+    c.bench_function("db_cache::env::named::hit", |b| {
+        b.iter(|| {
+            let txn = env.begin_ro_unsync().unwrap();
+            black_box(txn.open_db(Some(NAMED_DB)).unwrap())
+        })
+    });
+}
+
+/// Benchmark DB opens in a fresh transaction, always through FFI.
+fn bench_open_db_env_no_cache_named(c: &mut Criterion) {
+    let (_dir, env) = setup_bench_db(10);
+
+    c.bench_function("db_cache::env::named::disabled", |b| {
+        b.iter(|| {
+            let txn = env.begin_ro_unsync().unwrap();
+            black_box(txn.open_db_no_cache(Some(NAMED_DB)).unwrap())
+        })
+    });
+}
+
+/// Benchmark DB opens in a fresh write transaction, served by the
+/// environment cache after the handle is published on commit.
+fn bench_open_db_env_cached_rw(c: &mut Criterion) {
+    let (_dir, env) = setup_bench_db(10);
+    // Prime the environment cache
+    let txn = env.begin_rw_unsync().unwrap();
+    let _ = txn.open_db(Some(NAMED_DB)).unwrap();
+    txn.commit().unwrap();
+
+    c.bench_function("db_cache::env::rw::hit", |b| {
+        b.iter(|| {
+            let txn = env.begin_rw_unsync().unwrap();
+            black_box(txn.open_db(Some(NAMED_DB)).unwrap());
+            txn.abort().unwrap();
+        })
+    });
+}
+
 criterion_group! {
     name = db_open;
     config = quick_config();
@@ -142,6 +187,9 @@ criterion_group! {
         bench_open_db_cached,
         bench_open_db_no_cache,
         bench_open_db_cached_named,
-        bench_open_db_no_cache_named
+        bench_open_db_no_cache_named,
+        bench_open_db_env_cached_named,
+        bench_open_db_env_no_cache_named,
+        bench_open_db_env_cached_rw
 }
 criterion_main!(db_open);
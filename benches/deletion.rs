@@ -8,6 +8,7 @@ use utils::quick_config;
 
 const VALUE_SIZE: usize = 100;
 const DB_NAME: &str = "deletion_bench";
+const DUPS_PER_KEY: u32 = 10;
 
 /// Setup a DUPSORT database with the specified number of 100-byte values under a single key.
 fn setup_deletion_db(num_values: u32) -> (TempDir, Environment) {
@@ -27,6 +28,27 @@ fn setup_deletion_db(num_values: u32) -> (TempDir, Environment) {
     (dir, env)
 }
 
+/// Setup a DUPSORT database with the specified number of big-endian `u32` keys, each
+/// holding `DUPS_PER_KEY` 100-byte values.
+fn setup_multi_key_db(num_keys: u32) -> (TempDir, Environment) {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(1).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_unsync().unwrap();
+    let db = txn.create_db(Some(DB_NAME), DatabaseFlags::DUP_SORT).unwrap();
+
+    for key in 0..num_keys {
+        for i in 0..DUPS_PER_KEY {
+            let mut value = [0u8; VALUE_SIZE];
+            value[..4].copy_from_slice(&i.to_le_bytes());
+            txn.put(db, key.to_be_bytes(), value, WriteFlags::empty()).unwrap();
+        }
+    }
+    txn.commit().unwrap();
+
+    (dir, env)
+}
+
 /// Benchmark: del_all_dups (single call bulk deletion).
 fn bench_del_all_dups(c: &mut Criterion) {
     for num_values in [100, 2000, 10000] {
@@ -73,10 +95,78 @@ fn bench_del_loop(c: &mut Criterion) {
     }
 }
 
+/// Benchmark: delete_range over the first half of the keys.
+fn bench_delete_range(c: &mut Criterion) {
+    for num_keys in [100, 1000, 5000] {
+        c.bench_function(&format!("del::delete_range::{num_keys}"), |b| {
+            b.iter_batched(
+                || setup_multi_key_db(num_keys),
+                |(_dir, env)| {
+                    let txn = env.begin_rw_unsync().unwrap();
+                    let db = txn.open_db(Some(DB_NAME)).unwrap();
+                    let end = (num_keys / 2).to_be_bytes();
+                    let deleted = txn.delete_range(db, ..&end[..]).unwrap();
+                    assert_eq!(deleted, (num_keys / 2 * DUPS_PER_KEY) as usize);
+                    txn.commit().unwrap();
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+/// Benchmark: delete_while over the first half of the keys.
+fn bench_delete_while(c: &mut Criterion) {
+    for num_keys in [100, 1000, 5000] {
+        c.bench_function(&format!("del::delete_while::{num_keys}"), |b| {
+            b.iter_batched(
+                || setup_multi_key_db(num_keys),
+                |(_dir, env)| {
+                    let txn = env.begin_rw_unsync().unwrap();
+                    let db = txn.open_db(Some(DB_NAME)).unwrap();
+                    let end = (num_keys / 2).to_be_bytes();
+                    {
+                        let mut cursor = txn.cursor(db).unwrap();
+                        cursor.first::<(), ()>().unwrap();
+                        let deleted = cursor.delete_while(|key, _| key < &end[..]).unwrap();
+                        assert_eq!(deleted, (num_keys / 2 * DUPS_PER_KEY) as usize);
+                    }
+                    txn.commit().unwrap();
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+/// Benchmark: del_all_dups of each key in the first half, one key at a time.
+fn bench_del_all_dups_loop(c: &mut Criterion) {
+    for num_keys in [100, 1000, 5000] {
+        c.bench_function(&format!("del::del_all_dups_loop::{num_keys}"), |b| {
+            b.iter_batched(
+                || setup_multi_key_db(num_keys),
+                |(_dir, env)| {
+                    let txn = env.begin_rw_unsync().unwrap();
+                    let db = txn.open_db(Some(DB_NAME)).unwrap();
+                    {
+                        let mut cursor = txn.cursor(db).unwrap();
+                        for key in 0..num_keys / 2 {
+                            cursor.del_all_dups_of(&key.to_be_bytes()).unwrap();
+                        }
+                    }
+                    txn.commit().unwrap();
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
 criterion_group! {
     name = benches;
     config = quick_config();
-    targets = bench_del_all_dups, bench_del_loop,
+    targets = bench_del_all_dups, bench_del_loop, bench_delete_range, bench_delete_while,
+        bench_del_all_dups_loop,
 }
 
 criterion_main!(benches);
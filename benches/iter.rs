@@ -12,6 +12,8 @@ const NUM_VALUES: u32 = 2000;
 
 const DB_NAME: &str = "dupfixed_bench";
 
+const NUM_ROWS: u32 = 100_000;
+
 /// Setup a DUPFIXED database with NUM_VALUES 100-byte values under a single key.
 fn setup_dupfixed_db() -> (TempDir, Environment) {
     let dir = tempdir().unwrap();
@@ -118,11 +120,72 @@ fn bench_iter_simple_sync(c: &mut Criterion) {
     });
 }
 
+/// Setup a plain database with NUM_ROWS 4-byte keys and 100-byte values.
+fn setup_plain_db() -> (TempDir, Environment) {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_unsync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    for i in 0..NUM_ROWS {
+        let mut value = [0u8; VALUE_SIZE];
+        value[..4].copy_from_slice(&i.to_le_bytes());
+        txn.append(db, i.to_be_bytes(), value).unwrap();
+    }
+    txn.commit().unwrap();
+
+    (dir, env)
+}
+
+/// Benchmark: simple next() iteration over a plain table.
+fn bench_iter_plain(c: &mut Criterion) {
+    let (_dir, env) = setup_plain_db();
+    let txn = create_ro_unsync(&env);
+    let db = txn.open_db(None).unwrap();
+
+    c.bench_function("unsync::iter::plain::simple_next", |b| {
+        b.iter(|| {
+            let mut cursor = txn.cursor(db).unwrap();
+            let mut count = 0u32;
+            for result in cursor.iter_start::<[u8; 4], [u8; VALUE_SIZE]>().unwrap() {
+                let (_key, value) = result.unwrap();
+                black_box(value);
+                count += 1;
+            }
+            assert_eq!(count, NUM_ROWS);
+        })
+    });
+}
+
+/// Benchmark: iter_batched over a plain table, at several batch sizes.
+fn bench_iter_plain_batched(c: &mut Criterion) {
+    let (_dir, env) = setup_plain_db();
+    let txn = create_ro_unsync(&env);
+    let db = txn.open_db(None).unwrap();
+
+    for batch_size in [16, 256, 4096] {
+        c.bench_function(&format!("unsync::iter::plain::batched::{batch_size}"), |b| {
+            b.iter(|| {
+                let mut cursor = txn.cursor(db).unwrap();
+                let mut count = 0u32;
+                for result in cursor.iter_batched::<[u8; 4], [u8; VALUE_SIZE]>(batch_size).unwrap()
+                {
+                    let (_key, value) = result.unwrap();
+                    black_box(value);
+                    count += 1;
+                }
+                assert_eq!(count, NUM_ROWS);
+            })
+        });
+    }
+}
+
 criterion_group! {
     name = benches;
     config = quick_config();
     targets = bench_iter_dupfixed, bench_iter_simple,
               bench_iter_dupfixed_sync, bench_iter_simple_sync,
+              bench_iter_plain, bench_iter_plain_batched,
 }
 
 criterion_main!(benches);
@@ -79,7 +79,7 @@ fn bench_nested_write_and_read(c: &mut Criterion) {
 
                 // Value should be visible to parent after child commit.
                 let db = parent.open_db(None).unwrap();
-                let val: Option<Vec<u8>> = parent.get(db.dbi(), b"nested_key").unwrap();
+                let val: Option<Vec<u8>> = parent.get(db, b"nested_key").unwrap();
                 assert_eq!(val.as_deref(), Some(b"nested_val".as_slice()));
 
                 parent.commit().unwrap();
@@ -50,8 +50,7 @@ fn bench_sequential_get(c: &mut Criterion) {
                     let txn = env.begin_ro_unsync().unwrap();
                     let mut total = 0usize;
                     for key in &keys {
-                        let val: Cow<'_, [u8]> =
-                            txn.get(db.dbi(), key.as_slice()).unwrap().unwrap();
+                        let val: Cow<'_, [u8]> = txn.get(db, key.as_slice()).unwrap().unwrap();
                         total += val.len();
                     }
                     total
@@ -81,8 +80,7 @@ fn bench_random_get(c: &mut Criterion) {
                     let txn = env.begin_ro_unsync().unwrap();
                     let mut total = 0usize;
                     for key in &keys {
-                        let val: Cow<'_, [u8]> =
-                            txn.get(db.dbi(), key.as_slice()).unwrap().unwrap();
+                        let val: Cow<'_, [u8]> = txn.get(db, key.as_slice()).unwrap().unwrap();
                         total += val.len();
                     }
                     total
@@ -191,7 +189,7 @@ fn bench_cold_random_get(c: &mut Criterion) {
             for &i in &indices {
                 let key = bench_key(i);
                 let txn = env.begin_ro_unsync().unwrap();
-                let val: Option<Cow<'_, [u8]>> = txn.get(db.dbi(), key.as_slice()).unwrap();
+                let val: Option<Cow<'_, [u8]>> = txn.get(db, key.as_slice()).unwrap();
                 assert!(val.is_some());
             }
         });
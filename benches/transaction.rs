@@ -60,7 +60,7 @@ fn bench_get_rand_sync(c: &mut Criterion) {
         b.iter(|| {
             let mut i = 0usize;
             for key in &keys {
-                i += *txn.get::<ObjectLength>(db.dbi(), key.as_bytes()).unwrap().unwrap();
+                i += *txn.get::<ObjectLength>(db, key.as_bytes()).unwrap().unwrap();
             }
             black_box(i);
         })
@@ -80,7 +80,7 @@ fn bench_get_rand_unsync(c: &mut Criterion) {
         b.iter(|| {
             let mut i = 0usize;
             for key in &keys {
-                i += *txn.get::<ObjectLength>(db.dbi(), key.as_bytes()).unwrap().unwrap();
+                i += *txn.get::<ObjectLength>(db, key.as_bytes()).unwrap().unwrap();
             }
             black_box(i);
         })
@@ -30,10 +30,10 @@ fuzz_target!(|data: &[u8]| {
 
     // Attempt decoding as fixed-size arrays. Length mismatches must produce an
     // error, never a panic.
-    let r4: Result<Option<[u8; 4]>, ReadError> = ro_txn.get(ro_db.dbi(), key);
-    let r8: Result<Option<[u8; 8]>, ReadError> = ro_txn.get(ro_db.dbi(), key);
-    let r16: Result<Option<[u8; 16]>, ReadError> = ro_txn.get(ro_db.dbi(), key);
-    let r32: Result<Option<[u8; 32]>, ReadError> = ro_txn.get(ro_db.dbi(), key);
+    let r4: Result<Option<[u8; 4]>, ReadError> = ro_txn.get(ro_db, key);
+    let r8: Result<Option<[u8; 8]>, ReadError> = ro_txn.get(ro_db, key);
+    let r16: Result<Option<[u8; 16]>, ReadError> = ro_txn.get(ro_db, key);
+    let r32: Result<Option<[u8; 32]>, ReadError> = ro_txn.get(ro_db, key);
 
     // Validate: correct length → Ok, wrong length → DecodeErrorLenDiff.
     for (result, expected_len) in [
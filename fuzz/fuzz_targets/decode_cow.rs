@@ -29,7 +29,7 @@ fuzz_target!(|data: &[u8]| {
 
     // Read while transaction is still open: data is on a dirty page, so
     // Cow::decode_borrow should return Cow::Owned.
-    let readback: Option<Cow<'_, [u8]>> = txn.get(db.dbi(), key).unwrap();
+    let readback: Option<Cow<'_, [u8]>> = txn.get(db, key).unwrap();
     let readback = readback.unwrap();
     assert_eq!(readback.as_ref(), value);
 
@@ -38,7 +38,7 @@ fuzz_target!(|data: &[u8]| {
     // Read via RO transaction: data is on a clean page, so Cow should borrow.
     let ro_txn = env.begin_ro_unsync().unwrap();
     let ro_db = ro_txn.open_db(None).unwrap();
-    let clean: Option<Cow<'_, [u8]>> = ro_txn.get(ro_db.dbi(), key).unwrap();
+    let clean: Option<Cow<'_, [u8]>> = ro_txn.get(ro_db, key).unwrap();
     let clean = clean.unwrap();
     assert_eq!(clean.as_ref(), value);
 });
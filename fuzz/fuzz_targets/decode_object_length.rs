@@ -29,7 +29,7 @@ fuzz_target!(|data: &[u8]| {
     let ro_db = ro_txn.open_db(None).unwrap();
 
     // ObjectLength must return the exact byte length of the stored value.
-    let len: Option<ObjectLength> = ro_txn.get(ro_db.dbi(), key).unwrap();
+    let len: Option<ObjectLength> = ro_txn.get(ro_db, key).unwrap();
     let len = len.unwrap();
     assert_eq!(*len, value.len());
 });
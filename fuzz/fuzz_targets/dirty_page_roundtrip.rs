@@ -47,7 +47,7 @@ fuzz_target!(|data: &[u8]| {
         txn.put(db, &key, &value, WriteFlags::empty()).unwrap();
 
         // Read while dirty; Vec<u8> always copies, so no lifetime tie to txn.
-        let dirty: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+        let dirty: Option<Vec<u8>> = txn.get(db, &key).unwrap();
         let dirty = dirty.unwrap();
         assert_eq!(dirty.as_slice(), value.as_slice());
 
@@ -58,7 +58,7 @@ fuzz_target!(|data: &[u8]| {
     // Read via RO transaction: data now on a clean page.
     let ro_txn = env.begin_ro_unsync().unwrap();
     let ro_db = ro_txn.open_db(None).unwrap();
-    let clean: Option<Vec<u8>> = ro_txn.get(ro_db.dbi(), &key).unwrap();
+    let clean: Option<Vec<u8>> = ro_txn.get(ro_db, &key).unwrap();
     let clean = clean.unwrap();
 
     // Both reads must agree on value content.
@@ -0,0 +1,98 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use signet_libmdbx::{Cursor, DatabaseFlags, Environment, FixedValue, TransactionKind, WriteFlags};
+use std::borrow::Cow;
+use tempfile::tempdir;
+
+/// Checks that decoding `data` as packed `T` values matches decoding each
+/// value separately.
+fn check_decode<T: FixedValue + PartialEq + std::fmt::Debug>(
+    data: &[u8],
+    from_bytes: fn(&[u8]) -> T,
+) {
+    let size = size_of::<T>();
+    let decoded = T::decode_slice(data);
+    if data.len() % size != 0 {
+        assert!(decoded.is_err(), "length {} is not a multiple of {size}", data.len());
+        return;
+    }
+
+    let decoded = decoded.unwrap();
+    if let Cow::Borrowed(values) = &decoded {
+        assert_eq!(values.as_ptr().cast(), data.as_ptr(), "borrowed slice must alias the input");
+    }
+    let expected: Vec<T> = data.chunks_exact(size).map(from_bytes).collect();
+    assert_eq!(&*decoded, expected.as_slice());
+    assert_eq!(T::copy_slice(data).unwrap(), expected);
+}
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 3 {
+        return;
+    }
+
+    // First byte: offset into the payload, to exercise misaligned input.
+    let offset = data[0] as usize % 8;
+    // Second byte: number of keys, clamped to 1..=4.
+    let n_keys = (data[1] as usize % 4) + 1;
+    // Third byte: number of values per key, clamped to 1..=600 so that some
+    // keys span several pages.
+    let n_values = (data[2] as usize * 3 % 600) + 1;
+    let payload = &data[3..];
+
+    // Decode phase: arbitrary bytes at an arbitrary alignment.
+    let bytes = payload.get(offset..).unwrap_or_default();
+    check_decode::<u8>(bytes, |b| b[0]);
+    check_decode::<u32>(bytes, |b| u32::from_ne_bytes(b.try_into().unwrap()));
+    check_decode::<u64>(bytes, |b| u64::from_ne_bytes(b.try_into().unwrap()));
+    check_decode::<[u8; 32]>(bytes, |b| b.try_into().unwrap());
+
+    // Table phase: u64 values, read from dirty pages and then clean pages.
+    let seed = payload.iter().fold(0u64, |acc, &b| acc.rotate_left(8) ^ u64::from(b));
+    let mut expected: Vec<(Vec<u8>, Vec<u64>)> = (0..n_keys)
+        .map(|k| {
+            let mut values: Vec<u64> = (0..n_values as u64)
+                .map(|i| (seed ^ i.wrapping_mul(0x9e37_79b9_7f4a_7c15)).wrapping_add(k as u64))
+                .collect();
+            values.sort_unstable();
+            values.dedup();
+            (vec![k as u8], values)
+        })
+        .collect();
+    expected.sort();
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let flags = DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED | DatabaseFlags::INTEGER_DUP;
+
+    fn read<K: TransactionKind>(cursor: &mut Cursor<'_, K>) -> Vec<(Vec<u8>, Vec<u64>)> {
+        let mut actual: Vec<(Vec<u8>, Vec<u64>)> = Vec::new();
+        for page in cursor.dupfixed_pages::<Vec<u8>, u64>().unwrap() {
+            let (key, page) = page.unwrap();
+            assert!(!page.is_empty(), "pages are never empty");
+            match actual.last_mut() {
+                Some((last, values)) if *last == key => values.extend_from_slice(&page),
+                _ => actual.push((key, page.into_owned())),
+            }
+        }
+        actual
+    }
+
+    let txn = env.begin_rw_unsync().unwrap();
+    let db = txn.create_db(None, flags).unwrap();
+    for (key, values) in &expected {
+        for value in values {
+            txn.put(db, key, value.to_ne_bytes(), WriteFlags::empty()).unwrap();
+        }
+    }
+    let mut cursor = txn.cursor(db).unwrap();
+    assert_eq!(read(&mut cursor), expected, "dirty pages");
+    assert!(cursor.dupfixed_pages::<(), u32>().is_err(), "value size must match");
+    drop(cursor);
+    txn.commit().unwrap();
+
+    let txn = env.begin_ro_unsync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+    assert_eq!(read(&mut cursor), expected, "clean pages");
+});
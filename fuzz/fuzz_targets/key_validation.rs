@@ -30,9 +30,9 @@ fuzz_target!(|data: &[u8]| {
     // We still fuzz the *content* of those keys.
     if data.len() == 4 || data.len() == 8 {
         let _ = txn.put(int_db, data, b"value", WriteFlags::empty());
-        let _: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(int_db.dbi(), data);
+        let _: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(int_db, data);
     }
 
     // Attempt get with fuzz bytes as key on the default database.
-    let _: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(default_db.dbi(), data);
+    let _: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(default_db, data);
 });
@@ -1,4 +1,9 @@
-use std::{env, path::PathBuf};
+use std::{env, fs, path::PathBuf};
+
+/// Local patches to the vendored libmdbx, and a line each adds. See the
+/// "Local patches" section of the README.
+const PATCHES: &[(&str, &str)] =
+    &[("0001-txn-try-is-not-a-state-flag.patch", "txn->flags = flags & ~MDBX_TXN_TRY;")];
 
 fn main() {
     let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
@@ -6,6 +11,14 @@ fn main() {
 
     println!("cargo:rerun-if-changed={}", mdbx.display());
 
+    let source = fs::read_to_string(mdbx.join("mdbx.c")).unwrap();
+    for (patch, line) in PATCHES {
+        assert!(
+            source.contains(line),
+            "libmdbx/mdbx.c is missing patches/{patch}, re-apply it with `git apply`"
+        );
+    }
+
     let mut cc = cc::Build::new();
     cc.flag_if_supported("-Wno-unused-parameter").flag_if_supported("-Wuninitialized");
 
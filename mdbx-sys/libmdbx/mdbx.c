@@ -36515,7 +36515,9 @@ int txn_renew(MDBX_txn *txn, unsigned flags) {
 
     tASSERT(txn, txn->dbs[FREE_DBI].flags == MDBX_INTEGERKEY);
     tASSERT(txn, check_table_flags(txn->dbs[MAIN_DBI].flags));
-    txn->flags = flags;
+    /* MDBX_TXN_TRY only affects acquiring the lock above, it is not a state
+     * flag of the transaction. */
+    txn->flags = flags & ~MDBX_TXN_TRY;
     txn->nested = nullptr;
     txn->tw.loose_pages = nullptr;
     txn->tw.loose_count = 0;
@@ -2,7 +2,7 @@
 
 use crate::{MdbxError, TransactionKind, error::ReadResult, tx::ops};
 use ffi::MDBX_txn;
-use std::{borrow::Cow, slice};
+use std::{borrow::Cow, ptr, slice};
 
 /// A marker trait for types that can be deserialized from a database value
 /// without borrowing from the transaction.
@@ -29,6 +29,148 @@ pub trait TableObjectOwned: for<'de> TableObject<'de> {
 
 impl<T> TableObjectOwned for T where T: for<'de> TableObject<'de> {}
 
+/// A fixed-size value that can be read in place from the packed pages of a
+/// [`DatabaseFlags::DUP_FIXED`] table.
+///
+/// DUPFIXED pages store values back to back, so a page of `N`-byte values can
+/// be viewed as a slice of `N`-byte types without decoding each value.
+/// [`FixedValue::decode_slice`] checks the size of the values, and borrows
+/// the page in place when it is suitably aligned, or copies it otherwise.
+///
+/// Implemented for the primitive integers, which are read in native byte
+/// order as [`DatabaseFlags::INTEGER_DUP`] stores them, and for arrays of
+/// fixed values, such as `[u8; 32]` hashes.
+///
+/// # Safety
+///
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value of
+/// `Self`, and `Self` must not contain padding.
+///
+/// [`DatabaseFlags::DUP_FIXED`]: crate::DatabaseFlags::DUP_FIXED
+/// [`DatabaseFlags::INTEGER_DUP`]: crate::DatabaseFlags::INTEGER_DUP
+pub unsafe trait FixedValue: Copy + 'static {
+    /// Views packed values in place.
+    ///
+    /// Fails with [`MdbxError::DecodeErrorLenDiff`] if the length of `data`
+    /// is not a multiple of the size of `Self`. Returns `Ok(None)` if `data`
+    /// is not aligned for `Self`.
+    fn cast_slice(data: &[u8]) -> ReadResult<Option<&[Self]>> {
+        let len = checked_len::<Self>(data)?;
+        if data.as_ptr().align_offset(align_of::<Self>()) != 0 {
+            return Ok(None);
+        }
+        // SAFETY: data is aligned, and holds len values of Self, which are
+        // valid for any bit pattern.
+        Ok(Some(unsafe { slice::from_raw_parts(data.as_ptr().cast(), len) }))
+    }
+
+    /// Copies packed values, whatever their alignment.
+    ///
+    /// Fails with [`MdbxError::DecodeErrorLenDiff`] if the length of `data`
+    /// is not a multiple of the size of `Self`.
+    fn copy_slice(data: &[u8]) -> ReadResult<Vec<Self>> {
+        let len = checked_len::<Self>(data)?;
+        let mut values = Vec::<Self>::with_capacity(len);
+        // SAFETY: The vector has room for len values, which are valid for any
+        // bit pattern, and the source and destination do not overlap.
+        unsafe {
+            ptr::copy_nonoverlapping(data.as_ptr(), values.as_mut_ptr().cast(), data.len());
+            values.set_len(len);
+        }
+        Ok(values)
+    }
+
+    /// Views packed values in place if `data` is aligned for `Self`, or
+    /// copies them otherwise.
+    fn decode_slice(data: &[u8]) -> ReadResult<Cow<'_, [Self]>> {
+        match Self::cast_slice(data)? {
+            Some(values) => Ok(Cow::Borrowed(values)),
+            None => Self::copy_slice(data).map(Cow::Owned),
+        }
+    }
+}
+
+/// Returns the number of `T` values packed in `data`.
+fn checked_len<T>(data: &[u8]) -> ReadResult<usize> {
+    match data.len().checked_rem(size_of::<T>()) {
+        Some(0) => Ok(data.len() / size_of::<T>()),
+        _ => Err(MdbxError::DecodeErrorLenDiff.into()),
+    }
+}
+
+macro_rules! impl_fixed_value {
+    ($($ty:ty),*) => {
+        $(
+            // SAFETY: Primitive integers are valid for any bit pattern, and
+            // have no padding.
+            unsafe impl FixedValue for $ty {}
+        )*
+    };
+}
+
+impl_fixed_value!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+// SAFETY: Arrays have no padding between elements, and are valid for any bit
+// pattern if their elements are. Zero-sized values never pass checked_len.
+unsafe impl<T: FixedValue, const N: usize> FixedValue for [T; N] {}
+
+/// Decodes values borrowed only briefly, such as for the duration of a
+/// [`Cursor::for_each`] callback.
+///
+/// Unlike [`TableObject`], the decoded value cannot outlive the bytes it was
+/// decoded from, so borrowing them never requires a dirty-page check or a
+/// copy. The decoded type is the generic associated type [`Self::Ref`],
+/// which may borrow from the bytes.
+///
+/// # Built-in Implementations
+///
+/// - `[u8]` - Decodes to the borrowed `&[u8]`
+/// - Every [`TableObjectOwned`] type - Decodes to itself
+///
+/// ```
+/// use signet_libmdbx::{MdbxError, ReadResult, TableObjectRef};
+///
+/// // A view of a length-prefixed string.
+/// struct Prefixed<'a>(&'a [u8]);
+///
+/// impl TableObjectRef for Prefixed<'_> {
+///     type Ref<'a> = Prefixed<'a>;
+///
+///     fn decode_ref(data: &[u8]) -> ReadResult<Prefixed<'_>> {
+///         let (&len, rest) = data.split_first().ok_or(MdbxError::DecodeErrorLenDiff)?;
+///         rest.get(..len as usize).map(Prefixed).ok_or(MdbxError::DecodeErrorLenDiff.into())
+///     }
+/// }
+/// ```
+///
+/// [`Cursor::for_each`]: crate::Cursor::for_each
+pub trait TableObjectRef {
+    /// The decoded object, borrowing bytes that live for `'a`.
+    type Ref<'a>;
+
+    /// Decodes the object from bytes that are only valid for `'a`.
+    fn decode_ref(data: &[u8]) -> ReadResult<Self::Ref<'_>>;
+}
+
+impl TableObjectRef for [u8] {
+    type Ref<'a> = &'a [u8];
+
+    fn decode_ref(data: &[u8]) -> ReadResult<&[u8]> {
+        Ok(data)
+    }
+}
+
+impl<T> TableObjectRef for T
+where
+    T: TableObjectOwned,
+{
+    type Ref<'a> = T;
+
+    fn decode_ref(data: &[u8]) -> ReadResult<T> {
+        T::decode(data)
+    }
+}
+
 /// Decodes values read from the database into Rust types.
 ///
 /// Implement this trait to enable reading custom types directly from MDBX.
@@ -1,6 +1,11 @@
 //! Error types and result handling for MDBX operations.
 
-use std::{convert::Infallible, ffi::c_int, result};
+use std::{
+    convert::Infallible,
+    ffi::{CStr, c_char, c_int},
+    panic::Location,
+    result,
+};
 
 /// An MDBX result.
 pub type MdbxResult<T, E = MdbxError> = result::Result<T, E>;
@@ -37,6 +42,103 @@ impl ReadError {
     {
         Self::Decoding(Box::new(err))
     }
+
+    /// Classifies the error by how it can be handled. Decoding errors are
+    /// [`ErrorClass::Decoding`].
+    pub const fn class(&self) -> ErrorClass {
+        match self {
+            Self::Mdbx(err) => err.class(),
+            Self::Decoding(_) => ErrorClass::Decoding,
+        }
+    }
+
+    /// Returns true if the error is [`ErrorClass::Transient`].
+    pub const fn is_transient(&self) -> bool {
+        matches!(self.class(), ErrorClass::Transient)
+    }
+
+    /// Returns true if the error is [`ErrorClass::CapacityExceeded`].
+    pub const fn is_capacity_exceeded(&self) -> bool {
+        matches!(self.class(), ErrorClass::CapacityExceeded)
+    }
+
+    /// Returns true if the error is [`ErrorClass::NeedsReopen`].
+    pub const fn needs_reopen(&self) -> bool {
+        matches!(self.class(), ErrorClass::NeedsReopen)
+    }
+
+    /// Returns true if the error is [`ErrorClass::Corruption`].
+    pub const fn is_corruption(&self) -> bool {
+        matches!(self.class(), ErrorClass::Corruption)
+    }
+
+    /// Returns true if the error is [`ErrorClass::Misuse`].
+    pub const fn is_misuse(&self) -> bool {
+        matches!(self.class(), ErrorClass::Misuse)
+    }
+
+    /// Returns true if the error is [`ErrorClass::Decoding`].
+    pub const fn is_decoding(&self) -> bool {
+        matches!(self.class(), ErrorClass::Decoding)
+    }
+}
+
+/// An error that may be caused by an [`MdbxError`].
+///
+/// [`Environment::view`] and [`Environment::update`] use the cause of an
+/// error returned by their closure to decide whether to grow the map or run
+/// the transaction again. Implement this for application errors that wrap
+/// [`MdbxError`] or [`ReadError`] to have them recovered from as well.
+///
+/// [`Environment::view`]: crate::Environment::view
+/// [`Environment::update`]: crate::Environment::update
+pub trait Retryable {
+    /// Returns the MDBX error that caused this error, if any.
+    fn mdbx_error(&self) -> Option<MdbxError>;
+}
+
+impl Retryable for MdbxError {
+    fn mdbx_error(&self) -> Option<MdbxError> {
+        Some(*self)
+    }
+}
+
+impl Retryable for ReadError {
+    fn mdbx_error(&self) -> Option<MdbxError> {
+        match self {
+            Self::Mdbx(err) => Some(*err),
+            Self::Decoding(_) => None,
+        }
+    }
+}
+
+/// How an error can be handled, as returned by [`MdbxError::class`] and
+/// [`ReadError::class`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    /// The operation did not apply to the data, e.g. the key was not found
+    /// or already exists. The database and transaction remain usable.
+    Outcome,
+    /// The operation may succeed if the transaction is run again, e.g.
+    /// another write transaction is running.
+    Transient,
+    /// A size limit was reached, e.g. the map or transaction is full. The
+    /// operation may succeed after raising the limit, or in a smaller
+    /// transaction.
+    CapacityExceeded,
+    /// The environment is unusable and must be reopened, e.g. after a fatal
+    /// error or when the mapping cannot follow the size of the database.
+    NeedsReopen,
+    /// The database files are damaged or not MDBX files. Stop using the
+    /// database.
+    Corruption,
+    /// The API was used incorrectly, e.g. with invalid arguments, flags, or
+    /// handles. Retrying will fail the same way.
+    Misuse,
+    /// A value read from the database could not be decoded.
+    Decoding,
+    /// The error code is not recognised.
+    Unknown,
 }
 
 /// An MDBX error kind.
@@ -44,6 +146,7 @@ impl ReadError {
 /// This represents various error conditions that can occur when interacting
 /// with the MDBX database.
 #[derive(Debug, thiserror::Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum MdbxError {
     /// Key/data pair already exists.
     #[error("key/data pair already exists")]
@@ -169,15 +272,15 @@ pub enum MdbxError {
     ReadTransactionTimeout,
     /// The transaction commit was aborted due to previous errors.
     ///
-    /// This can happen in exceptionally rare cases and it signals the problem
-    /// coming from inside of mdbx.
+    /// Like [`MdbxError::TxnBroken`], this reports the state of the
+    /// transaction rather than of the database.
     #[error("botched transaction")]
     BotchedTransaction,
     /// Permission defined
     #[error("permission denied to setup database")]
     Permission,
-    /// Unknown error code.
-    #[error("unknown error code: {0}")]
+    /// Error code without a dedicated variant, with the libmdbx error string.
+    #[error("error code {0}: {msg}", msg = strerror(*.0))]
     Other(i32),
     /// Operation requires DUP_SORT flag on database.
     #[error("operation requires DUP_SORT flag on database")]
@@ -191,6 +294,35 @@ pub enum MdbxError {
     /// read transactions within the retry limit.
     #[error("failed to acquire consistent MVCC snapshot across multiple read transactions")]
     SnapshotDivergence,
+    /// The database handle cannot be closed or dropped while cursors are
+    /// open on it.
+    #[error("database handle is in use by an open cursor")]
+    DbiInUse,
+    /// The transaction was broken by a [`CancelHandle`] and can only be
+    /// aborted.
+    ///
+    /// [`CancelHandle`]: crate::tx::CancelHandle
+    #[error("transaction was broken by a cancel handle")]
+    TxnBroken,
+    /// The current thread already holds a write transaction on this
+    /// environment, and beginning another would never acquire the lock.
+    ///
+    /// Nested writes should use `begin_nested_txn` instead.
+    #[error(
+        "write transaction already held by this thread{}",
+        .opened_at.map(|loc| format!(" (opened at {loc})")).unwrap_or_default()
+    )]
+    WriteTxnAlreadyHeldByThread {
+        /// Call site where the held transaction was opened. Only recorded in
+        /// debug builds.
+        opened_at: Option<&'static Location<'static>>,
+    },
+    /// A closure submitted to a [`WriteBatcher`] panicked. Its writes were
+    /// rolled back.
+    ///
+    /// [`WriteBatcher`]: crate::WriteBatcher
+    #[error("write batcher submission panicked")]
+    SubmissionPanicked,
 }
 
 impl MdbxError {
@@ -270,12 +402,129 @@ impl MdbxError {
             Self::RequiresDupSort => -96002,
             Self::RequiresDupFixed => -96003,
             Self::SnapshotDivergence => -96004,
+            Self::DbiInUse => -96005,
+            Self::TxnBroken => -96006,
+            Self::WriteTxnAlreadyHeldByThread { .. } => -96007,
+            Self::SubmissionPanicked => -96008,
             Self::Permission => ffi::MDBX_EPERM,
             Self::Other(err_code) => *err_code,
         }
     }
 }
 
+impl MdbxError {
+    /// Classifies the error by how it can be handled.
+    ///
+    /// [`MdbxError::Other`] codes are classified by their libmdbx or system
+    /// error, and are [`ErrorClass::Unknown`] when neither is recognised.
+    pub const fn class(&self) -> ErrorClass {
+        match self {
+            Self::KeyExist | Self::NotFound | Self::NoData | Self::KeyMismatch | Self::Multival => {
+                ErrorClass::Outcome
+            }
+            Self::Busy
+            | Self::BadRslot
+            | Self::ReadTransactionTimeout
+            | Self::SnapshotDivergence => ErrorClass::Transient,
+            Self::MapFull
+            | Self::DbsFull
+            | Self::ReadersFull
+            | Self::TxnFull
+            | Self::CursorFull
+            | Self::PageFull
+            | Self::TooLarge => ErrorClass::CapacityExceeded,
+            Self::Panic | Self::UnableExtendMapSize | Self::WannaRecovery => {
+                ErrorClass::NeedsReopen
+            }
+            Self::Corrupted
+            | Self::PageNotFound
+            | Self::Invalid
+            | Self::VersionMismatch
+            | Self::Problem => ErrorClass::Corruption,
+            Self::Incompatible
+            | Self::BadTxn
+            | Self::BadValSize
+            | Self::BadDbi
+            | Self::BadSignature
+            | Self::Access
+            | Self::Permission
+            | Self::NestedTransactionsUnsupportedWithWriteMap
+            | Self::WriteTransactionUnsupportedInReadOnlyMode
+            | Self::RequiresDupSort
+            | Self::RequiresDupFixed
+            | Self::DbiInUse
+            | Self::TxnBroken
+            | Self::BotchedTransaction
+            | Self::WriteTxnAlreadyHeldByThread { .. }
+            | Self::SubmissionPanicked => ErrorClass::Misuse,
+            Self::DecodeError | Self::DecodeErrorLenDiff => ErrorClass::Decoding,
+            Self::Other(code) => classify_code(*code),
+        }
+    }
+
+    /// Returns true if the error is [`ErrorClass::Transient`].
+    pub const fn is_transient(&self) -> bool {
+        matches!(self.class(), ErrorClass::Transient)
+    }
+
+    /// Returns true if the error is [`ErrorClass::CapacityExceeded`].
+    pub const fn is_capacity_exceeded(&self) -> bool {
+        matches!(self.class(), ErrorClass::CapacityExceeded)
+    }
+
+    /// Returns true if the error is [`ErrorClass::NeedsReopen`].
+    pub const fn needs_reopen(&self) -> bool {
+        matches!(self.class(), ErrorClass::NeedsReopen)
+    }
+
+    /// Returns true if the error is [`ErrorClass::Corruption`].
+    pub const fn is_corruption(&self) -> bool {
+        matches!(self.class(), ErrorClass::Corruption)
+    }
+
+    /// Returns true if the error is [`ErrorClass::Misuse`].
+    pub const fn is_misuse(&self) -> bool {
+        matches!(self.class(), ErrorClass::Misuse)
+    }
+
+    /// Returns true if the error is [`ErrorClass::Decoding`].
+    pub const fn is_decoding(&self) -> bool {
+        matches!(self.class(), ErrorClass::Decoding)
+    }
+}
+
+/// Classifies an error code without a dedicated [`MdbxError`] variant.
+const fn classify_code(code: c_int) -> ErrorClass {
+    match code {
+        ffi::MDBX_OUSTED | ffi::MDBX_MVCC_RETARDED => ErrorClass::Transient,
+        ffi::MDBX_BACKLOG_DEPLETED | ffi::MDBX_ENOMEM => ErrorClass::CapacityExceeded,
+        ffi::MDBX_DUPLICATED_CLK | ffi::MDBX_EIO => ErrorClass::NeedsReopen,
+        ffi::MDBX_THREAD_MISMATCH
+        | ffi::MDBX_TXN_OVERLAPPING
+        | ffi::MDBX_DANGLING_DBI
+        | ffi::MDBX_ENOSYS
+        | ffi::MDBX_ENOFILE
+        | ffi::MDBX_EREMOTE
+        | ffi::MDBX_EDEADLK => ErrorClass::Misuse,
+        _ => ErrorClass::Unknown,
+    }
+}
+
+/// Returns the libmdbx error string for an error code, which describes
+/// system errors as well as libmdbx ones.
+fn strerror(code: c_int) -> String {
+    let mut buf = [0 as c_char; 256];
+    // SAFETY: mdbx_strerror_r writes a NUL-terminated string of at most
+    // `buf.len()` bytes to `buf`, or returns a pointer to a static string.
+    unsafe {
+        let ptr = ffi::mdbx_strerror_r(code, buf.as_mut_ptr(), buf.len());
+        if ptr.is_null() {
+            return "unknown error".to_owned();
+        }
+        CStr::from_ptr(ptr).to_string_lossy().into_owned()
+    }
+}
+
 impl From<MdbxError> for i32 {
     fn from(value: MdbxError) -> Self {
         value.to_err_code()
@@ -322,6 +571,27 @@ pub(crate) const fn mdbx_result_unit(err_code: c_int) -> MdbxResult<()> {
     }
 }
 
+/// Converts the result of a read whose values cannot fail to decode, such as
+/// `()` or `Vec<u8>`, into an [`MdbxResult`].
+pub(crate) trait IntoMdbxResult<T> {
+    /// Returns the MDBX error of a failed read.
+    ///
+    /// # Panics
+    ///
+    /// Panics on a decoding error.
+    fn into_mdbx(self) -> MdbxResult<T>;
+}
+
+impl<T> IntoMdbxResult<T> for ReadResult<T> {
+    #[track_caller]
+    fn into_mdbx(self) -> MdbxResult<T> {
+        self.map_err(|err| match err {
+            ReadError::Mdbx(err) => err,
+            ReadError::Decoding(err) => unreachable!("infallible decoding failed: {err}"),
+        })
+    }
+}
+
 /// Unwrap a `Result<Option<T>, MdbxError>`, or return `Ok(None)` if the error
 /// is `NotFound` or `NoData`.
 #[macro_export]
@@ -369,4 +639,33 @@ mod tests {
     fn test_conversion() {
         assert_eq!(MdbxError::from_err_code(ffi::MDBX_KEYEXIST), MdbxError::KeyExist);
     }
+
+    #[test]
+    fn test_class() {
+        assert_eq!(MdbxError::NotFound.class(), ErrorClass::Outcome);
+        assert!(MdbxError::Busy.is_transient());
+        assert!(MdbxError::MapFull.is_capacity_exceeded());
+        assert!(MdbxError::UnableExtendMapSize.needs_reopen());
+        assert!(MdbxError::Corrupted.is_corruption());
+        assert!(MdbxError::BadDbi.is_misuse());
+        assert!(MdbxError::BotchedTransaction.is_misuse());
+        assert!(MdbxError::DecodeErrorLenDiff.is_decoding());
+
+        assert!(MdbxError::from_err_code(ffi::MDBX_OUSTED).is_transient());
+        assert!(MdbxError::from_err_code(ffi::MDBX_THREAD_MISMATCH).is_misuse());
+        assert_eq!(MdbxError::Other(-1).class(), ErrorClass::Unknown);
+
+        assert!(ReadError::from(MdbxError::TxnFull).is_capacity_exceeded());
+        assert!(ReadError::decoding(std::fmt::Error).is_decoding());
+        assert!(ReadError::from(MdbxError::DecodeError).is_decoding());
+    }
+
+    #[test]
+    fn test_other_description() {
+        let err = MdbxError::from_err_code(ffi::MDBX_THREAD_MISMATCH);
+        assert!(matches!(err, MdbxError::Other(_)));
+        let msg = err.to_string();
+        assert!(msg.starts_with(&format!("error code {}: ", ffi::MDBX_THREAD_MISMATCH)), "{msg}");
+        assert!(msg.contains("MDBX_THREAD_MISMATCH"), "{msg}");
+    }
 }
@@ -42,7 +42,7 @@
 //!     // Read data in a read-only transaction
 //!     let txn = env.begin_ro_sync()?;
 //!     let db = txn.open_db(None)?;
-//!     let value: Option<Vec<u8>> = txn.get(db.dbi(), b"hello").expect("read failed");
+//!     let value: Option<Vec<u8>> = txn.get(db, b"hello").expect("read failed");
 //!     assert_eq!(value.as_deref(), Some(b"world".as_slice()));
 //!
 //!     Ok(())
@@ -188,20 +188,30 @@
 pub extern crate signet_mdbx_sys as ffi;
 
 mod codec;
-pub use codec::{ObjectLength, TableObject, TableObjectOwned};
+pub use codec::{FixedValue, ObjectLength, TableObject, TableObjectOwned, TableObjectRef};
 mod error;
-pub use error::{MdbxError, MdbxResult, ReadError, ReadResult};
+pub use error::{ErrorClass, MdbxError, MdbxResult, ReadError, ReadResult, Retryable};
 
 mod flags;
 pub use flags::{DatabaseFlags, EnvironmentFlags, Mode, SyncMode, WriteFlags};
 
 pub mod sys;
-pub use sys::{Environment, EnvironmentBuilder, Geometry, Info, Stat};
+pub use sys::{
+    Environment, EnvironmentBuilder, Geometry, Info, MapFullPolicy, RetryPolicy, Stat, TxInfo,
+};
 
 pub mod tx;
 pub use tx::aliases::{TxSync, TxUnsync};
 pub use tx::iter::DupItem;
-pub use tx::{CommitLatency, Cursor, Database, Ro, RoSync, Rw, RwSync, TransactionKind};
+pub use tx::{
+    BulkLoadError, BulkLoader, CancelHandle, Change, ChangeOp, ChangeSet, CommitInfo,
+    CommitLatency, Cursor, Database, LoadPosition, LoadStats, Ro, RoSync, Rw, RwSync, Savepoint,
+    ScanChunk, ScanControl, ScanStart, ScanTurn, TransactionKind, UnboundCursor, WriteBatch,
+    WriteBatcher,
+};
+
+mod user_data;
+pub use user_data::UserData;
 
 #[cfg(test)]
 mod test {
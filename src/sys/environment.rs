@@ -1,13 +1,24 @@
 use crate::{
-    Database, Mode, SyncMode,
-    error::{MdbxError, MdbxResult, ReadResult, mdbx_result},
+    Database, Mode, SyncMode, UserData,
+    error::{MdbxError, MdbxResult, ReadError, ReadResult, Retryable, mdbx_result},
     flags::EnvironmentFlags,
-    sys::txn_manager::{LifecycleHandle, RwSyncLifecycle},
-    tx::aliases::{RoTxSync, RoTxUnsync, RwTxSync, RwTxUnsync},
+    sys::{
+        owner::RwTxnOwner,
+        registry::DbiRegistry,
+        txn_manager::{LifecycleHandle, RwSyncLifecycle},
+    },
+    tx::{
+        BulkLoader, ScanChunk, WriteBatcher,
+        aliases::{RoTxSync, RoTxUnsync, RwTxSync, RwTxUnsync},
+        cache::EnvDbCache,
+        scan,
+    },
+    user_data::{self, UserDataCell},
 };
 use byteorder::{ByteOrder, NativeEndian};
 use mem::size_of;
 use std::{
+    any::Any,
     ffi::CString,
     fmt::{self, Debug},
     mem,
@@ -15,6 +26,7 @@ use std::{
     path::Path,
     ptr,
     sync::Arc,
+    thread,
     time::Duration,
 };
 
@@ -23,6 +35,39 @@ use std::{
 /// [`Environment::begin_ro_unsync_multi`].
 const MAX_MULTI_RETRIES: usize = 16;
 
+/// Default interval between attempts to acquire the write lock.
+const DEFAULT_RW_TXN_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Maximum number of times the map is grown for one operation under
+/// [`MapFullPolicy::Grow`].
+const MAX_MAP_GROW_ATTEMPTS: usize = 8;
+
+/// The failure of one attempt of [`Environment::view`] or
+/// [`Environment::update`].
+enum TxFailure<E> {
+    /// Starting or committing the transaction failed.
+    Mdbx(MdbxError),
+    /// The closure failed.
+    User(E),
+}
+
+impl<E: From<MdbxError> + Retryable> TxFailure<E> {
+    /// Returns the MDBX error behind the failure, if any.
+    fn mdbx_error(&self) -> Option<MdbxError> {
+        match self {
+            Self::Mdbx(err) => Some(*err),
+            Self::User(err) => err.mdbx_error(),
+        }
+    }
+
+    fn into_error(self) -> E {
+        match self {
+            Self::Mdbx(err) => err.into(),
+            Self::User(err) => err,
+        }
+    }
+}
+
 /// An environment supports multiple databases, all residing in the same shared-memory map.
 ///
 /// Accessing the environment is thread-safe.
@@ -51,6 +96,10 @@ impl Environment {
             log_level: None,
             kind: Default::default(),
             handle_slow_readers: None,
+            rw_txn_backoff: DEFAULT_RW_TXN_BACKOFF,
+            rw_txn_wait_warning: true,
+            map_full: MapFullPolicy::Fail,
+            retry: RetryPolicy::default(),
         }
     }
 
@@ -84,6 +133,49 @@ impl Environment {
         &self.inner.txn_manager
     }
 
+    /// Returns the environment-wide database handle registry.
+    #[inline]
+    pub(crate) fn dbi_registry(&self) -> &DbiRegistry {
+        &self.inner.dbi_registry
+    }
+
+    /// Returns the environment-wide cache of database handles.
+    #[inline]
+    pub(crate) fn db_cache(&self) -> &EnvDbCache {
+        &self.inner.db_cache
+    }
+
+    /// Returns the record of the thread holding the write transaction.
+    #[inline]
+    pub(crate) fn rw_txn_owner(&self) -> &RwTxnOwner {
+        &self.inner.rw_txn_owner
+    }
+
+    /// Returns the interval between attempts to acquire the write lock.
+    #[inline]
+    pub(crate) fn rw_txn_backoff(&self) -> Duration {
+        self.inner.rw_txn_backoff
+    }
+
+    /// Returns true if a warning is logged while waiting for the write lock.
+    #[inline]
+    pub(crate) fn rw_txn_wait_warning(&self) -> bool {
+        self.inner.rw_txn_wait_warning
+    }
+
+    /// Returns the policy applied when the map is full.
+    #[inline]
+    pub fn map_full_policy(&self) -> MapFullPolicy {
+        self.inner.map_full
+    }
+
+    /// Returns the policy for retrying transactions that fail with transient
+    /// errors.
+    #[inline]
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.inner.retry
+    }
+
     /// Create a read-only transaction for use with the environment.
     #[inline]
     pub fn begin_ro_sync(&self) -> MdbxResult<RoTxSync> {
@@ -93,10 +185,41 @@ impl Environment {
     /// Create a read-write transaction for use with the environment. This
     /// method will block while there are any other read-write transactions
     /// open on the environment.
+    ///
+    /// Fails with [`MdbxError::WriteTxnAlreadyHeldByThread`] if the calling
+    /// thread opened the read-write transaction that is currently open. Use
+    /// [`Tx::begin_nested_txn`] for nested writes.
+    ///
+    /// The returned Tx is `Send`. Once it is used from another thread, the
+    /// thread that opened it is no longer recorded, and beginning another
+    /// write transaction blocks until it ends.
+    ///
+    /// [`Tx::begin_nested_txn`]: crate::tx::Tx::begin_nested_txn
+    #[track_caller]
     pub fn begin_rw_sync(&self) -> MdbxResult<RwTxSync> {
         RwTxSync::begin(self.clone())
     }
 
+    /// Try to create a read-write transaction without blocking.
+    ///
+    /// Returns [`MdbxError::Busy`] immediately if another read-write
+    /// transaction is open on the environment, in this or another process.
+    #[track_caller]
+    pub fn try_begin_rw_sync(&self) -> MdbxResult<RwTxSync> {
+        RwTxSync::try_begin(self.clone())
+    }
+
+    /// Create a read-write transaction, waiting at most `timeout` for other
+    /// read-write transactions to finish.
+    ///
+    /// While the write lock is held elsewhere, this retries at the interval
+    /// set by [`EnvironmentBuilder::set_rw_txn_backoff`]. Returns
+    /// [`MdbxError::Busy`] if the lock could not be acquired in time.
+    #[track_caller]
+    pub fn begin_rw_sync_timeout(&self, timeout: Duration) -> MdbxResult<RwTxSync> {
+        RwTxSync::begin_timeout(self.clone(), timeout)
+    }
+
     /// Create a single-threaded read-only transaction for use with the
     /// environment.
     ///
@@ -114,13 +237,254 @@ impl Environment {
     /// environment. This method will block while there are any other read-write
     /// transactions open on the environment.
     ///
+    /// Fails with [`MdbxError::WriteTxnAlreadyHeldByThread`] if the calling
+    /// thread opened the read-write transaction that is currently open.
+    ///
     /// The returned Tx is `!Send` and `!Sync`. As a result, it saves about 30%
     /// overhead on transaction operations compared to the multi-threaded
     /// version, but cannot be sent or shared between threads.
+    #[track_caller]
     pub fn begin_rw_unsync(&self) -> MdbxResult<RwTxUnsync> {
         RwTxUnsync::begin(self.clone())
     }
 
+    /// Try to create a single-threaded read-write transaction without
+    /// blocking.
+    ///
+    /// Returns [`MdbxError::Busy`] immediately if another read-write
+    /// transaction is open on the environment, in this or another process.
+    #[track_caller]
+    pub fn try_begin_rw_unsync(&self) -> MdbxResult<RwTxUnsync> {
+        RwTxUnsync::try_begin(self.clone())
+    }
+
+    /// Create a single-threaded read-write transaction, waiting at most
+    /// `timeout` for other read-write transactions to finish.
+    ///
+    /// While the write lock is held elsewhere, this retries at the interval
+    /// set by [`EnvironmentBuilder::set_rw_txn_backoff`]. Returns
+    /// [`MdbxError::Busy`] if the lock could not be acquired in time.
+    #[track_caller]
+    pub fn begin_rw_unsync_timeout(&self, timeout: Duration) -> MdbxResult<RwTxUnsync> {
+        RwTxUnsync::begin_timeout(self.clone(), timeout)
+    }
+
+    /// Start a [`WriteBatcher`] that applies submissions from many threads
+    /// in shared write transactions, committing once per group.
+    ///
+    /// Fails with [`MdbxError::NestedTransactionsUnsupportedWithWriteMap`]
+    /// for WRITEMAP environments, as failing submissions are isolated with
+    /// nested transactions.
+    pub fn write_batcher(&self) -> MdbxResult<WriteBatcher> {
+        WriteBatcher::spawn(self.clone())
+    }
+
+    /// Create a [`BulkLoader`] that loads sorted data into tables of the
+    /// environment, committing in chunks and growing the map as needed.
+    pub fn bulk_loader(&self) -> BulkLoader {
+        BulkLoader::new(self.clone())
+    }
+
+    /// Runs `f` in a read-only transaction.
+    ///
+    /// Errors returned by `f` and by starting the transaction are retried
+    /// under the environment's [`RetryPolicy`], so `f` must be safe to rerun.
+    /// If starting the transaction fails with
+    /// [`MdbxError::UnableExtendMapSize`], as it can after another process
+    /// grew the map, the map geometry is re-synced and the transaction is
+    /// started once more.
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, ReadError};
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let env = Environment::builder().open(dir.path()).unwrap();
+    /// let value = env.view(|txn| {
+    ///     let db = txn.open_db(None)?;
+    ///     txn.get::<Vec<u8>>(db, b"key")
+    /// })?;
+    /// # Ok::<(), ReadError>(())
+    /// ```
+    pub fn view<T, E, F>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut(&RoTxSync) -> Result<T, E>,
+        E: From<MdbxError> + From<ReadError> + Retryable,
+    {
+        self.run_retrying(false, || {
+            let txn = self.begin_ro_sync().map_err(TxFailure::Mdbx)?;
+            f(&txn).map_err(TxFailure::User)
+        })
+    }
+
+    /// Runs `f` in a single-threaded read-only transaction.
+    ///
+    /// See [`Environment::view`].
+    pub fn view_unsync<T, E, F>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut(&RoTxUnsync) -> Result<T, E>,
+        E: From<MdbxError> + From<ReadError> + Retryable,
+    {
+        self.run_retrying(false, || {
+            let txn = self.begin_ro_unsync().map_err(TxFailure::Mdbx)?;
+            f(&txn).map_err(TxFailure::User)
+        })
+    }
+
+    /// Runs `f` in a read-write transaction, committing it if `f` succeeds.
+    ///
+    /// The transaction is aborted if `f` fails or panics. A failed
+    /// transaction is run again, in a new transaction, so `f` must be safe to
+    /// rerun:
+    /// - under [`MapFullPolicy::Grow`], after growing the map if it failed
+    ///   with [`MdbxError::MapFull`].
+    /// - under the environment's [`RetryPolicy`], if it failed with a
+    ///   transient error.
+    ///
+    /// Errors returned by `f` are recognised through their [`Retryable`]
+    /// implementation.
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, MapFullPolicy, ReadError, WriteFlags};
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let env = Environment::builder()
+    ///     .on_map_full(MapFullPolicy::Grow { step: 64 << 20, max: 1 << 30 })
+    ///     .open(dir.path())
+    ///     .unwrap();
+    /// env.update(|txn| {
+    ///     let db = txn.open_db(None)?;
+    ///     txn.put(db, b"key", b"value", WriteFlags::empty())?;
+    ///     Ok::<_, ReadError>(())
+    /// })?;
+    /// # Ok::<(), ReadError>(())
+    /// ```
+    pub fn update<T, E, F>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut(&RwTxSync) -> Result<T, E>,
+        E: From<MdbxError> + From<ReadError> + Retryable,
+    {
+        self.run_retrying(true, || {
+            let txn = self.begin_rw_sync().map_err(TxFailure::Mdbx)?;
+            let value = f(&txn).map_err(TxFailure::User)?;
+            txn.commit().map_err(TxFailure::Mdbx)?;
+            Ok(value)
+        })
+    }
+
+    /// Runs `f` in a single-threaded read-write transaction, committing it if
+    /// `f` succeeds.
+    ///
+    /// See [`Environment::update`].
+    pub fn update_unsync<T, E, F>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut(&RwTxUnsync) -> Result<T, E>,
+        E: From<MdbxError> + From<ReadError> + Retryable,
+    {
+        self.run_retrying(true, || {
+            let txn = self.begin_rw_unsync().map_err(TxFailure::Mdbx)?;
+            let value = f(&txn).map_err(TxFailure::User)?;
+            txn.commit().map_err(TxFailure::Mdbx)?;
+            Ok(value)
+        })
+    }
+
+    /// Runs `f` until it succeeds, or fails with an error that neither the
+    /// [`MapFullPolicy`], applied if `writes` is set, nor the [`RetryPolicy`]
+    /// recovers from. Readers re-sync the map geometry once on
+    /// [`MdbxError::UnableExtendMapSize`].
+    fn run_retrying<T, E>(
+        &self,
+        writes: bool,
+        mut f: impl FnMut() -> Result<T, TxFailure<E>>,
+    ) -> Result<T, E>
+    where
+        E: From<MdbxError> + Retryable,
+    {
+        let policy = self.inner.retry;
+        let (mut grows, mut retries, mut resynced) = (0, 0, false);
+        loop {
+            let failure = match f() {
+                Ok(value) => return Ok(value),
+                Err(failure) => failure,
+            };
+            match failure.mdbx_error() {
+                Some(err @ MdbxError::MapFull) if writes && self.grow_after(err, &mut grows) => {}
+                Some(err @ MdbxError::UnableExtendMapSize) if !writes && !resynced => {
+                    resynced = true;
+                    if !self.resync_geometry(err) {
+                        return Err(failure.into_error());
+                    }
+                }
+                Some(err) if err.is_transient() && retries < policy.max_retries => {
+                    retries += 1;
+                    tracing::debug!(target: "libmdbx", %err, retry = retries, "retrying transaction");
+                    thread::sleep(policy.backoff);
+                }
+                _ => return Err(failure.into_error()),
+            }
+        }
+    }
+
+    /// Grows the map after `err` under [`MapFullPolicy::Grow`], returning
+    /// `true` if the failed operation should be retried.
+    fn grow_after(&self, err: MdbxError, attempts: &mut usize) -> bool {
+        let MapFullPolicy::Grow { step, max } = self.inner.map_full else { return false };
+        if *attempts >= MAX_MAP_GROW_ATTEMPTS {
+            tracing::warn!(target: "libmdbx", %err, attempts, "giving up growing the map");
+            return false;
+        }
+        *attempts += 1;
+        match self.grow_map(step, max) {
+            Ok(true) => {
+                tracing::debug!(target: "libmdbx", %err, attempt = *attempts, "retrying after growing the map");
+                true
+            }
+            Ok(false) => {
+                tracing::warn!(target: "libmdbx", %err, max, "map is at its maximum size");
+                false
+            }
+            Err(grow_err) => {
+                tracing::warn!(target: "libmdbx", %err, %grow_err, "unable to grow the map");
+                false
+            }
+        }
+    }
+
+    /// Re-syncs the map geometry with the one recorded in the database after
+    /// a reader failed with `err`, returning `true` if the reader should be
+    /// retried.
+    fn resync_geometry(&self, err: MdbxError) -> bool {
+        // SAFETY: The environment pointer is valid for the lifetime of self.
+        match mdbx_result(unsafe {
+            ffi::mdbx_env_set_geometry(self.env_ptr(), -1, -1, -1, -1, -1, -1)
+        }) {
+            Ok(_) => {
+                tracing::debug!(target: "libmdbx", %err, "retrying after re-syncing the map geometry");
+                true
+            }
+            Err(sync_err) => {
+                tracing::warn!(target: "libmdbx", %err, %sync_err, "unable to re-sync the map geometry");
+                false
+            }
+        }
+    }
+
+    /// Attaches application data to the environment, replacing and dropping
+    /// any previously attached data.
+    ///
+    /// The data is released along with the environment, and dropped once no
+    /// [`UserData`] handle to it remains. It is kept separately from the MDBX
+    /// `userctx` pointer, which is left untouched.
+    pub fn set_user_data<T: Any + Send + Sync>(&self, data: T) {
+        user_data::set(&self.inner.user_data, data);
+    }
+
+    /// Returns the data attached with [`Environment::set_user_data`], if it
+    /// is of type `T`.
+    ///
+    /// The returned handle shares the data without locking it.
+    pub fn user_data<T: Any + Send + Sync>(&self) -> Option<UserData<T>> {
+        user_data::get(&self.inner.user_data)
+    }
+
     /// Open `n` read-only synchronized transactions guaranteed to share the
     /// same MVCC snapshot.
     ///
@@ -204,6 +568,79 @@ impl Environment {
         Err(MdbxError::SnapshotDivergence)
     }
 
+    /// Scans a range of a table on `n_threads` threads, at a single
+    /// snapshot.
+    ///
+    /// The range is split into up to `n_threads` chunks of roughly equal
+    /// size, based on MDBX's estimates of the number of items between keys.
+    /// Each chunk is scanned on its own thread, in its own read transaction,
+    /// and every transaction sees the same snapshot (see
+    /// [`begin_ro_sync_multi`]). Every key in the range is visited exactly
+    /// once, by exactly one chunk, whatever the accuracy of the estimates.
+    ///
+    /// `f` is called once per chunk, and its results are returned in key
+    /// order. If any call fails, one of the errors is returned. A panic in
+    /// `f` is propagated once every worker has finished.
+    ///
+    /// Ranges are given over byte slices, as for [`Cursor::range`], e.g.
+    /// `..` or `&b"a"[..]..&b"b"[..]`, and are interpreted in the table's
+    /// key order.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use signet_libmdbx::{Environment, MdbxResult};
+    /// # use std::path::Path;
+    /// # let env = Environment::builder().open(Path::new("db")).unwrap();
+    /// let db = env.begin_ro_sync().unwrap().open_db(None).unwrap();
+    ///
+    /// let counts = env
+    ///     .par_scan(db, .., 4, |mut chunk| chunk.try_fold(0, |n, item| item.map(|_| n + 1)))
+    ///     .unwrap();
+    /// let total: usize = counts.iter().sum();
+    /// ```
+    ///
+    /// [`begin_ro_sync_multi`]: Self::begin_ro_sync_multi
+    /// [`Cursor::range`]: crate::Cursor::range
+    pub fn par_scan<'r, R, F, T>(
+        &self,
+        db: Database,
+        range: R,
+        n_threads: usize,
+        f: F,
+    ) -> ReadResult<Vec<T>>
+    where
+        R: RangeBounds<&'r [u8]>,
+        F: Fn(ScanChunk<'_, '_>) -> ReadResult<T> + Sync,
+        T: Send,
+    {
+        let txns = self.begin_ro_sync_multi(n_threads.max(1))?;
+        let chunks = scan::split_range(
+            &mut txns[0].cursor(db)?,
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+            txns.len(),
+        )?;
+
+        std::thread::scope(|s| {
+            let workers: Vec<_> = txns
+                .iter()
+                .zip(&chunks)
+                .map(|(txn, bounds)| {
+                    let f = &f;
+                    s.spawn(move || {
+                        let mut cursor = txn.cursor(db)?;
+                        f(ScanChunk::new(&mut cursor, bounds)?)
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        })
+    }
+
     /// Returns a raw pointer to the underlying MDBX environment.
     ///
     /// The caller **must** ensure that the pointer is never dereferenced after the environment has
@@ -260,6 +697,33 @@ impl Environment {
         }
     }
 
+    /// Grows the map by `step` bytes, up to `max` bytes.
+    ///
+    /// The current size is raised while it is below the upper bound, which
+    /// is reserved in the address space when the environment is opened. Past
+    /// that, the upper bound is raised. Environments are opened with
+    /// `MDBX_NOTLS`, so MDBX cannot move the mapping to do so: this fails
+    /// with [`MdbxError::UnableExtendMapSize`] unless the address space
+    /// following the mapping is free.
+    ///
+    /// Returns `false` if the map is already at `max`.
+    pub(crate) fn grow_map(&self, step: usize, max: usize) -> MdbxResult<bool> {
+        let geometry = self.info()?.geometry();
+        let (current, upper) = (geometry.current() as usize, geometry.max() as usize);
+        let limit = if current < upper { upper.min(max) } else { max };
+        let size = current.saturating_add(step).min(limit);
+        if size <= current {
+            return Ok(false);
+        }
+        let upper = if size > upper { size as isize } else { -1 };
+        tracing::debug!(target: "libmdbx", from = current, to = size, "growing map");
+        // SAFETY: The environment pointer is valid for the lifetime of self.
+        mdbx_result(unsafe {
+            ffi::mdbx_env_set_geometry(self.env_ptr(), -1, size as isize, upper, -1, -1, -1)
+        })?;
+        Ok(true)
+    }
+
     /// Retrieves the total number of pages on the freelist.
     ///
     /// Along with [`Environment::info()`], this can be used to calculate the exact number
@@ -320,6 +784,22 @@ struct EnvironmentInner {
     env_kind: EnvironmentKind,
     /// Transaction manager
     txn_manager: LifecycleHandle,
+    /// Generations and cursor counts of database handles.
+    dbi_registry: DbiRegistry,
+    /// Database handles shared by every transaction.
+    db_cache: EnvDbCache,
+    /// Interval between attempts to acquire the write lock.
+    rw_txn_backoff: Duration,
+    /// Whether to warn while waiting for the write lock.
+    rw_txn_wait_warning: bool,
+    /// The thread holding the open write transaction.
+    rw_txn_owner: RwTxnOwner,
+    /// Application data attached to the environment.
+    user_data: UserDataCell,
+    /// What to do when the map is full.
+    map_full: MapFullPolicy,
+    /// How transactions failing with transient errors are retried.
+    retry: RetryPolicy,
 }
 
 impl Drop for EnvironmentInner {
@@ -448,6 +928,16 @@ impl GeometryInfo {
     pub const fn min(&self) -> u64 {
         self.0.lower
     }
+
+    /// Maximum geometry setting of the environment.
+    pub const fn max(&self) -> u64 {
+        self.0.upper
+    }
+
+    /// Current size of the environment.
+    pub const fn current(&self) -> u64 {
+        self.0.current
+    }
 }
 
 /// Environment information.
@@ -531,6 +1021,60 @@ impl Info {
     }
 }
 
+/// Transaction information.
+///
+/// Contains the space used and dirtied by a transaction, and how far it lags
+/// behind the latest snapshot.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct TxInfo(pub(crate) ffi::MDBX_txn_info);
+
+impl TxInfo {
+    /// The ID of the transaction.
+    #[inline]
+    pub const fn id(&self) -> u64 {
+        self.0.txn_id
+    }
+
+    /// For read transactions, the number of transactions committed since
+    /// the snapshot was taken. Always zero for write transactions.
+    #[inline]
+    pub const fn reader_lag(&self) -> u64 {
+        self.0.txn_reader_lag
+    }
+
+    /// Bytes of the database used by the transaction's snapshot.
+    #[inline]
+    pub const fn space_used(&self) -> u64 {
+        self.0.txn_space_used
+    }
+
+    /// Bytes available to a write transaction before the map must grow.
+    #[inline]
+    pub const fn space_limit_soft(&self) -> u64 {
+        self.0.txn_space_limit_soft
+    }
+
+    /// Bytes available to a write transaction before it fails with
+    /// [`MdbxError::MapFull`].
+    #[inline]
+    pub const fn space_limit_hard(&self) -> u64 {
+        self.0.txn_space_limit_hard
+    }
+
+    /// Bytes of pages retired by a write transaction.
+    #[inline]
+    pub const fn space_retired(&self) -> u64 {
+        self.0.txn_space_retired
+    }
+
+    /// Bytes of pages dirtied by a write transaction.
+    #[inline]
+    pub const fn space_dirty(&self) -> u64 {
+        self.0.txn_space_dirty
+    }
+}
+
 impl fmt::Debug for Environment {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Environment").field("kind", &self.inner.env_kind).finish_non_exhaustive()
@@ -684,6 +1228,48 @@ pub enum HandleSlowReadersReturnCode {
     ReaderProcessTerminated = 2,
 }
 
+/// What to do when a write fails with [`MdbxError::MapFull`].
+///
+/// Set with [`EnvironmentBuilder::on_map_full`], and applied by
+/// [`Environment::update`] and [`Environment::update_unsync`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MapFullPolicy {
+    /// Return the error. The default.
+    #[default]
+    Fail,
+    /// Grow the map by `step` bytes, up to `max` bytes, and retry.
+    ///
+    /// The map grows within the upper bound of its [`Geometry`] first, as
+    /// that address space is reserved when the environment is opened; set a
+    /// growth step of zero for MDBX to leave growth to this policy. Past the
+    /// upper bound, the bound is raised. Environments are opened with
+    /// `MDBX_NOTLS`, so MDBX cannot move the mapping to do that. Growing
+    /// fails, and the original error is returned, unless the address space
+    /// following the mapping is free.
+    Grow {
+        /// The number of bytes to grow the map by.
+        step: usize,
+        /// The size, in bytes, the map is never grown beyond.
+        max: usize,
+    },
+}
+
+/// How [`Environment::view`] and [`Environment::update`] retry transactions
+/// that fail with [`ErrorClass::Transient`] errors, such as
+/// [`MdbxError::Busy`] and [`MdbxError::BadRslot`].
+///
+/// [`ErrorClass::Transient`]: crate::ErrorClass::Transient
+///
+/// Set with [`EnvironmentBuilder::set_retry_policy`]. The default is not to
+/// retry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The number of times a transaction is run again.
+    pub max_retries: u32,
+    /// The time to wait before each retry.
+    pub backoff: Duration,
+}
+
 /// Options for opening or creating an environment.
 #[derive(Debug, Clone)]
 pub struct EnvironmentBuilder {
@@ -702,6 +1288,10 @@ pub struct EnvironmentBuilder {
     log_level: Option<ffi::MDBX_log_level_t>,
     kind: EnvironmentKind,
     handle_slow_readers: Option<HandleSlowReadersCallback>,
+    rw_txn_backoff: Duration,
+    rw_txn_wait_warning: bool,
+    map_full: MapFullPolicy,
+    retry: RetryPolicy,
 }
 
 impl EnvironmentBuilder {
@@ -721,6 +1311,7 @@ impl EnvironmentBuilder {
         mode: ffi::mdbx_mode_t,
     ) -> MdbxResult<Environment> {
         let mut env: *mut ffi::MDBX_env = ptr::null_mut();
+        let mut max_dbs: u64 = 0;
         unsafe {
             if let Some(log_level) = self.log_level {
                 // Returns the previously debug_flags in the 0-15 bits and log_level in the
@@ -823,6 +1414,8 @@ impl EnvironmentBuilder {
                     }
                 }
 
+                mdbx_result(ffi::mdbx_env_get_option(env, ffi::MDBX_opt_max_db, &mut max_dbs))?;
+
                 Ok(())
             })() {
                 ffi::mdbx_env_close_ex(env, false);
@@ -835,7 +1428,21 @@ impl EnvironmentBuilder {
 
         let txn_manager = RwSyncLifecycle::spawn(env_ptr);
 
-        let env = EnvironmentInner { env, txn_manager, env_kind: self.kind };
+        let dbi_registry = DbiRegistry::new(max_dbs as usize);
+
+        let env = EnvironmentInner {
+            env,
+            txn_manager,
+            env_kind: self.kind,
+            dbi_registry,
+            db_cache: EnvDbCache::default(),
+            rw_txn_backoff: self.rw_txn_backoff,
+            rw_txn_wait_warning: self.rw_txn_wait_warning,
+            rw_txn_owner: RwTxnOwner::default(),
+            user_data: UserDataCell::default(),
+            map_full: self.map_full,
+            retry: self.retry,
+        };
 
         Ok(Environment { inner: Arc::new(env) })
     }
@@ -972,6 +1579,38 @@ impl EnvironmentBuilder {
         self.handle_slow_readers = Some(hsr);
         self
     }
+
+    /// Sets the interval between attempts to acquire the write lock while
+    /// another read-write transaction is open. The default is 250ms.
+    ///
+    /// This applies to [`Environment::begin_rw_sync`] and to the `_timeout`
+    /// variants of the read-write transaction constructors.
+    pub const fn set_rw_txn_backoff(&mut self, backoff: Duration) -> &mut Self {
+        self.rw_txn_backoff = backoff;
+        self
+    }
+
+    /// Sets whether a warning is logged, once per transaction, while waiting
+    /// for the write lock. Enabled by default.
+    pub const fn set_rw_txn_wait_warning(&mut self, warn: bool) -> &mut Self {
+        self.rw_txn_wait_warning = warn;
+        self
+    }
+
+    /// Sets what to do when a write fails with [`MdbxError::MapFull`]. The
+    /// default is [`MapFullPolicy::Fail`].
+    pub const fn on_map_full(&mut self, policy: MapFullPolicy) -> &mut Self {
+        self.map_full = policy;
+        self
+    }
+
+    /// Sets how [`Environment::view`] and [`Environment::update`] retry
+    /// transactions that fail with transient errors. The default is not to
+    /// retry.
+    pub const fn set_retry_policy(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.retry = policy;
+        self
+    }
 }
 
 /// Converts a [`HandleSlowReadersCallback`] to the actual FFI function pointer.
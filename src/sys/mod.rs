@@ -9,13 +9,19 @@
 //! - [`PageSize`] - Database page size configuration
 //! - [`HandleSlowReadersCallback`] - Callback for handling slow readers
 //! - [`HandleSlowReadersReturnCode`] - Return codes for slow reader callbacks
+//! - [`MapFullPolicy`] - Handling of a full map
+//! - [`RetryPolicy`] - Retrying of transactions failing with transient errors
 //!
 
 mod environment;
 pub(crate) use environment::EnvPtr;
 pub use environment::{
     Environment, EnvironmentBuilder, EnvironmentKind, Geometry, HandleSlowReadersCallback,
-    HandleSlowReadersReturnCode, Info, PageSize, Stat,
+    HandleSlowReadersReturnCode, Info, MapFullPolicy, PageSize, RetryPolicy, Stat, TxInfo,
 };
 
+pub(crate) mod owner;
+
+pub(crate) mod registry;
+
 pub(crate) mod txn_manager;
@@ -0,0 +1,63 @@
+//! Tracking of the thread holding an environment's write transaction.
+//!
+//! MDBX allows a single top-level write transaction per environment. A
+//! thread that begins a second one while still holding the first can never
+//! make progress, so [`RwTxnOwner`] records which thread opened the current
+//! write transaction and rejects re-entrant begins from that thread.
+//!
+//! A `Send` transaction may move to another thread, which the opener must
+//! then simply wait for. Its record is released when it is first used from
+//! a thread other than the one that opened it.
+
+use crate::{MdbxError, MdbxResult, sys::txn_manager::RawTxPtr};
+use parking_lot::Mutex;
+use std::{
+    panic::Location,
+    thread::{self, ThreadId},
+};
+
+/// The open top-level write transaction and the thread that opened it.
+#[derive(Debug, Clone, Copy)]
+struct Owner {
+    thread: ThreadId,
+    txn: RawTxPtr,
+    opened_at: &'static Location<'static>,
+}
+
+/// Per-environment record of the open top-level write transaction.
+#[derive(Debug, Default)]
+pub(crate) struct RwTxnOwner {
+    owner: Mutex<Option<Owner>>,
+}
+
+impl RwTxnOwner {
+    /// Fails with [`MdbxError::WriteTxnAlreadyHeldByThread`] if the current
+    /// thread opened the recorded write transaction.
+    pub(crate) fn check(&self) -> MdbxResult<()> {
+        match *self.owner.lock() {
+            Some(owner) if owner.thread == thread::current().id() => {
+                Err(MdbxError::WriteTxnAlreadyHeldByThread {
+                    opened_at: cfg!(debug_assertions).then_some(owner.opened_at),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records `txn` as the open write transaction, opened by the current
+    /// thread at `opened_at`.
+    pub(crate) fn acquire(&self, txn: *mut ffi::MDBX_txn, opened_at: &'static Location<'static>) {
+        *self.owner.lock() =
+            Some(Owner { thread: thread::current().id(), txn: RawTxPtr(txn), opened_at });
+    }
+
+    /// Clears the record if `txn` is the open write transaction.
+    ///
+    /// Nested transactions are never recorded, so releasing them is a no-op.
+    pub(crate) fn release(&self, txn: *mut ffi::MDBX_txn) {
+        let mut owner = self.owner.lock();
+        if owner.is_some_and(|owner| owner.txn.0 == txn) {
+            *owner = None;
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Environment-wide registry of database handles.
+//!
+//! MDBX dbi handles are shared by every transaction in an environment, and
+//! closing or dropping a table releases the handle for all of them. The
+//! [`DbiRegistry`] tracks a generation counter and an open-cursor count per
+//! dbi slot, so that [`Database`] copies can be validated before use and
+//! tables cannot be closed out from under a live [`Cursor`].
+//!
+//! [`Cursor`]: crate::Cursor
+
+use crate::{Database, MdbxError, MdbxResult};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of core databases (freelist and main) preceding named dbis.
+const CORE_DBS: usize = 2;
+
+/// Mask selecting the cursor count from a packed slot state.
+const CURSOR_MASK: u64 = u32::MAX as u64;
+
+/// Cursor count marking a handle that is being closed or dropped.
+const CLOSING: u32 = u32::MAX;
+
+/// Packs a generation and cursor count into a slot state.
+const fn pack(generation: u32, cursors: u32) -> u64 {
+    ((generation as u64) << 32) | cursors as u64
+}
+
+/// Extracts the generation from a packed slot state.
+const fn generation_of(state: u64) -> u32 {
+    (state >> 32) as u32
+}
+
+/// Extracts the open cursor count from a packed slot state.
+const fn cursors_of(state: u64) -> u32 {
+    (state & CURSOR_MASK) as u32
+}
+
+/// Per-environment table of dbi generations and open cursor counts.
+///
+/// Each slot packs the generation into the high 32 bits and the number of
+/// open cursors into the low 32 bits of a single atomic, so that checking
+/// for open cursors and invalidating the handle happen atomically.
+#[derive(Debug)]
+pub(crate) struct DbiRegistry {
+    slots: Box<[AtomicU64]>,
+}
+
+impl DbiRegistry {
+    /// Creates a registry with room for `max_dbs` named databases plus the
+    /// core databases.
+    pub(crate) fn new(max_dbs: usize) -> Self {
+        let slots = (0..max_dbs + CORE_DBS).map(|_| AtomicU64::new(0)).collect();
+        Self { slots }
+    }
+
+    /// Returns the slot for the given dbi, if it is tracked.
+    #[inline]
+    fn slot(&self, dbi: ffi::MDBX_dbi) -> Option<&AtomicU64> {
+        self.slots.get(dbi as usize)
+    }
+
+    /// Returns the current generation of the given dbi.
+    #[inline]
+    pub(crate) fn generation(&self, dbi: ffi::MDBX_dbi) -> u32 {
+        self.slot(dbi).map_or(0, |slot| generation_of(slot.load(Ordering::Acquire)))
+    }
+
+    /// Returns `true` if the handle has not been closed or dropped since it
+    /// was opened, and is not being closed or dropped.
+    #[inline]
+    pub(crate) fn is_current(&self, db: Database) -> bool {
+        self.slot(db.dbi()).is_none_or(|slot| {
+            let state = slot.load(Ordering::Acquire);
+            generation_of(state) == db.generation() && cursors_of(state) != CLOSING
+        })
+    }
+
+    /// Returns [`MdbxError::BadDbi`] if the handle is stale.
+    #[inline]
+    pub(crate) fn check(&self, db: Database) -> MdbxResult<()> {
+        if self.is_current(db) { Ok(()) } else { Err(MdbxError::BadDbi) }
+    }
+
+    /// Records a cursor being opened on the given handle.
+    ///
+    /// Fails with [`MdbxError::BadDbi`] if the handle is stale.
+    pub(crate) fn acquire_cursor(&self, db: Database) -> MdbxResult<()> {
+        let Some(slot) = self.slot(db.dbi()) else { return Ok(()) };
+        let mut state = slot.load(Ordering::Acquire);
+        loop {
+            if generation_of(state) != db.generation() || cursors_of(state) == CLOSING {
+                return Err(MdbxError::BadDbi);
+            }
+            match slot.compare_exchange_weak(state, state + 1, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => return Ok(()),
+                Err(actual) => state = actual,
+            }
+        }
+    }
+
+    /// Records a cursor on the given dbi being closed.
+    pub(crate) fn release_cursor(&self, dbi: ffi::MDBX_dbi) {
+        if let Some(slot) = self.slot(dbi) {
+            let prev = slot.fetch_sub(1, Ordering::AcqRel);
+            debug_assert!(cursors_of(prev) > 0, "cursor count underflow for dbi {dbi}");
+        }
+    }
+
+    /// Invalidates every copy of the given handle.
+    ///
+    /// Fails with [`MdbxError::BadDbi`] if the handle is already stale, or
+    /// with [`MdbxError::DbiInUse`] if any cursor is open on it.
+    pub(crate) fn invalidate(&self, db: Database) -> MdbxResult<()> {
+        self.begin_invalidate(db).map(Invalidation::finish)
+    }
+
+    /// Marks the given handle as being closed or dropped.
+    ///
+    /// While the returned [`Invalidation`] is alive, copies of the handle
+    /// fail validation and no cursor can be opened on it, so the caller may
+    /// release the handle. [`Invalidation::finish`] then bumps the
+    /// generation, while dropping the guard restores the handle, for when
+    /// releasing it failed.
+    ///
+    /// Fails with [`MdbxError::BadDbi`] if the handle is already stale or
+    /// being closed, or with [`MdbxError::DbiInUse`] if any cursor is open
+    /// on it.
+    pub(crate) fn begin_invalidate(&self, db: Database) -> MdbxResult<Invalidation<'_>> {
+        let slot = self.slot(db.dbi());
+        let Some(slot) = slot else { return Ok(Invalidation { slot, db }) };
+        let mut state = slot.load(Ordering::Acquire);
+        loop {
+            if generation_of(state) != db.generation() || cursors_of(state) == CLOSING {
+                return Err(MdbxError::BadDbi);
+            }
+            if cursors_of(state) != 0 {
+                return Err(MdbxError::DbiInUse);
+            }
+            let next = pack(db.generation(), CLOSING);
+            match slot.compare_exchange_weak(state, next, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => return Ok(Invalidation { slot: Some(slot), db }),
+                Err(actual) => state = actual,
+            }
+        }
+    }
+}
+
+/// A handle being closed or dropped. See [`DbiRegistry::begin_invalidate`].
+#[derive(Debug)]
+pub(crate) struct Invalidation<'a> {
+    slot: Option<&'a AtomicU64>,
+    db: Database,
+}
+
+impl Invalidation<'_> {
+    /// Invalidates every copy of the handle, once it has been released.
+    pub(crate) fn finish(mut self) {
+        if let Some(slot) = self.slot.take() {
+            slot.store(pack(self.db.generation().wrapping_add(1), 0), Ordering::Release);
+        }
+    }
+}
+
+impl Drop for Invalidation<'_> {
+    fn drop(&mut self) {
+        if let Some(slot) = self.slot {
+            slot.store(pack(self.db.generation(), 0), Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DatabaseFlags;
+
+    #[test]
+    fn invalidate_bumps_generation() {
+        let registry = DbiRegistry::new(4);
+        let db = Database::new(2, DatabaseFlags::empty(), registry.generation(2));
+        registry.check(db).unwrap();
+
+        registry.acquire_cursor(db).unwrap();
+        assert_eq!(registry.invalidate(db), Err(MdbxError::DbiInUse));
+        registry.release_cursor(db.dbi());
+
+        registry.invalidate(db).unwrap();
+        assert_eq!(registry.check(db), Err(MdbxError::BadDbi));
+        assert_eq!(registry.acquire_cursor(db), Err(MdbxError::BadDbi));
+        assert_eq!(registry.invalidate(db), Err(MdbxError::BadDbi));
+
+        let reopened = Database::new(2, DatabaseFlags::empty(), registry.generation(2));
+        registry.check(reopened).unwrap();
+    }
+
+    #[test]
+    fn failed_invalidation_restores_handle() {
+        let registry = DbiRegistry::new(4);
+        let db = Database::new(2, DatabaseFlags::empty(), registry.generation(2));
+
+        let invalidation = registry.begin_invalidate(db).unwrap();
+        assert_eq!(registry.check(db), Err(MdbxError::BadDbi));
+        assert_eq!(registry.acquire_cursor(db), Err(MdbxError::BadDbi));
+        assert_eq!(registry.begin_invalidate(db).unwrap_err(), MdbxError::BadDbi);
+        drop(invalidation);
+
+        registry.check(db).unwrap();
+        registry.acquire_cursor(db).unwrap();
+        registry.release_cursor(db.dbi());
+        registry.begin_invalidate(db).unwrap().finish();
+        assert_eq!(registry.check(db), Err(MdbxError::BadDbi));
+    }
+}
@@ -1,13 +1,24 @@
 use crate::{
-    Environment,
+    Environment, MdbxError, MdbxResult,
+    error::mdbx_result,
     sys::txn_manager::{Abort, RawTxPtr},
+    tx::{
+        cancel::{CancelHandle, CancelState},
+        changes::TxChanges,
+        hooks::TxHooks,
+    },
+    user_data::TxUserDataCell,
 };
 use core::fmt;
 use parking_lot::{Mutex, MutexGuard};
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
-    mpsc::sync_channel,
+use std::{
+    cell::OnceCell,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicBool, Ordering},
+        mpsc::sync_channel,
+    },
+    thread::{self, ThreadId},
 };
 use tracing::debug_span;
 
@@ -42,9 +53,63 @@ pub trait TxPtrAccess: fmt::Debug + sealed::Sealed {
     where
         F: FnOnce(*mut ffi::MDBX_txn) -> R;
 
+    /// Execute a fallible closure with the transaction pointer.
+    ///
+    /// If the transaction was broken by a [`CancelHandle`], the closure is
+    /// not run, or its error is replaced, and [`MdbxError::TxnBroken`] is
+    /// returned instead.
+    fn try_with_txn_ptr<F, T, E>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(*mut ffi::MDBX_txn) -> Result<T, E>,
+        E: From<MdbxError>,
+    {
+        if self.is_broken() {
+            return Err(MdbxError::TxnBroken.into());
+        }
+        self.with_txn_ptr(f)
+            .map_err(|err| if self.is_broken() { MdbxError::TxnBroken.into() } else { err })
+    }
+
     /// Mark the transaction as committed.
     fn mark_committed(&self);
 
+    /// Abort the transaction, reporting the result.
+    ///
+    /// The transaction is marked as finished, so it is not aborted again on
+    /// drop.
+    fn abort(&self) -> MdbxResult<()>;
+
+    /// Get a [`CancelHandle`] that can break the transaction from another
+    /// thread.
+    fn cancel_handle(&self) -> CancelHandle;
+
+    /// Get the cancellation state of the transaction, creating it if
+    /// needed.
+    fn cancel_state(&self) -> &Arc<CancelState>;
+
+    /// Link the cancellation state of a new nested transaction to its
+    /// parent, so that cancelling the parent also cancels it.
+    ///
+    /// Must be called while holding access to the parent transaction.
+    fn set_cancel_parent(&self, parent: &Arc<CancelState>);
+
+    /// Returns `true` if the transaction was broken by a [`CancelHandle`].
+    fn is_broken(&self) -> bool;
+
+    /// Detach any [`CancelHandle`]s before the transaction finishes.
+    ///
+    /// Returns `true` if the transaction was broken.
+    fn detach_cancel(&self) -> bool;
+
+    /// Get the user data attached to the transaction.
+    fn user_data(&self) -> &TxUserDataCell;
+
+    /// Get the commit and abort callbacks registered on the transaction.
+    fn hooks(&self) -> &TxHooks;
+
+    /// Get the change recorder of the transaction.
+    fn changes(&self) -> &TxChanges;
+
     /// Get the transaction ID by making a call into the MDBX C API.
     fn tx_id(&self) -> Option<usize> {
         let mut id = 0;
@@ -77,12 +142,55 @@ where
     fn mark_committed(&self) {
         self.as_ref().mark_committed();
     }
+
+    fn abort(&self) -> MdbxResult<()> {
+        self.as_ref().abort()
+    }
+
+    fn cancel_handle(&self) -> CancelHandle {
+        self.as_ref().cancel_handle()
+    }
+
+    fn cancel_state(&self) -> &Arc<CancelState> {
+        self.as_ref().cancel_state()
+    }
+
+    fn set_cancel_parent(&self, parent: &Arc<CancelState>) {
+        self.as_ref().set_cancel_parent(parent);
+    }
+
+    fn is_broken(&self) -> bool {
+        self.as_ref().is_broken()
+    }
+
+    fn detach_cancel(&self) -> bool {
+        self.as_ref().detach_cancel()
+    }
+
+    fn user_data(&self) -> &TxUserDataCell {
+        self.as_ref().user_data()
+    }
+
+    fn hooks(&self) -> &TxHooks {
+        self.as_ref().hooks()
+    }
+
+    fn changes(&self) -> &TxChanges {
+        self.as_ref().changes()
+    }
 }
 
 /// Wrapper for raw txn pointer for RW transactions.
 pub struct PtrUnsync {
     committed: AtomicBool,
     ptr: *mut ffi::MDBX_txn,
+    cancel: OnceCell<Arc<CancelState>>,
+    /// The environment, kept for read-write transactions only, to release
+    /// the write transaction owner record when finished.
+    rw_env: Option<Environment>,
+    user_data: TxUserDataCell,
+    hooks: TxHooks,
+    changes: TxChanges,
 }
 
 impl fmt::Debug for PtrUnsync {
@@ -92,17 +200,31 @@ impl fmt::Debug for PtrUnsync {
 }
 
 impl TxPtrAccess for PtrUnsync {
-    fn from_ptr_and_env(ptr: *mut ffi::MDBX_txn, _env: Environment, _is_read_only: bool) -> Self
+    fn from_ptr_and_env(ptr: *mut ffi::MDBX_txn, env: Environment, is_read_only: bool) -> Self
     where
         Self: Sized,
     {
-        Self { committed: AtomicBool::new(false), ptr }
+        let rw_env = (!is_read_only).then_some(env);
+        Self {
+            committed: AtomicBool::new(false),
+            ptr,
+            cancel: OnceCell::new(),
+            rw_env,
+            user_data: TxUserDataCell::default(),
+            hooks: TxHooks::default(),
+            changes: TxChanges::default(),
+        }
     }
 
     fn with_txn_ptr<F, R>(&self, f: F) -> R
     where
         F: FnOnce(*mut ffi::MDBX_txn) -> R,
     {
+        if let Some(state) = self.cancel.get() {
+            // SAFETY: the type is not Sync, so this thread has exclusive
+            // access to the transaction.
+            unsafe { state.apply(self.ptr) };
+        }
         f(self.ptr)
     }
 
@@ -110,6 +232,53 @@ impl TxPtrAccess for PtrUnsync {
         // SAFETY:
         // Type is neither Sync nor Send, so no concurrent access is possible.
         unsafe { *self.committed.as_ptr() = true };
+        if let Some(env) = &self.rw_env {
+            env.rw_txn_owner().release(self.ptr);
+        }
+        if let Some(state) = self.cancel.get() {
+            state.release();
+        }
+    }
+
+    fn abort(&self) -> MdbxResult<()> {
+        self.detach_cancel();
+        // SAFETY: the transaction is marked as finished below, so the
+        // pointer is not used again.
+        let res = mdbx_result(unsafe { ffi::mdbx_txn_abort(self.ptr) }).map(drop);
+        self.mark_committed();
+        res
+    }
+
+    fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle::new(Arc::clone(self.cancel_state()))
+    }
+
+    fn cancel_state(&self) -> &Arc<CancelState> {
+        self.cancel.get_or_init(|| Arc::new(CancelState::new()))
+    }
+
+    fn set_cancel_parent(&self, parent: &Arc<CancelState>) {
+        let _ = self.cancel.set(Arc::new(CancelState::nested(parent)));
+    }
+
+    fn is_broken(&self) -> bool {
+        self.cancel.get().is_some_and(|state| state.is_broken())
+    }
+
+    fn detach_cancel(&self) -> bool {
+        self.cancel.get().is_some_and(|state| state.detach())
+    }
+
+    fn user_data(&self) -> &TxUserDataCell {
+        &self.user_data
+    }
+
+    fn hooks(&self) -> &TxHooks {
+        &self.hooks
+    }
+
+    fn changes(&self) -> &TxChanges {
+        &self.changes
     }
 }
 
@@ -117,10 +286,8 @@ impl Drop for PtrUnsync {
     fn drop(&mut self) {
         // SAFETY:
         // We have exclusive ownership of this pointer.
-        unsafe {
-            if !*self.committed.as_ptr() {
-                ffi::mdbx_txn_abort(self.ptr);
-            }
+        if !unsafe { *self.committed.as_ptr() } {
+            let _ = self.abort();
         }
     }
 }
@@ -136,18 +303,34 @@ pub struct PtrSync {
     /// Raw pointer to the MDBX transaction.
     txn: *mut ffi::MDBX_txn,
 
-    /// Whether the transaction was committed.
+    /// Whether the transaction was committed or aborted.
     committed: AtomicBool,
 
     /// Contains a lock to ensure exclusive access to the transaction.
-    /// The inner boolean indicates the timeout status.
-    lock: Mutex<()>,
+    ///
+    /// The inner value is the thread recorded as the owner of a top-level
+    /// write transaction, until the transaction is first used from another
+    /// thread.
+    lock: Mutex<Option<ThreadId>>,
 
     /// The environment that owns the transaction.
     env: Environment,
 
     /// Whether the transaction is read-only.
     is_read_only: bool,
+
+    /// Cancellation state, created when the first [`CancelHandle`] is
+    /// requested.
+    cancel: OnceLock<Arc<CancelState>>,
+
+    /// Application data attached to the transaction.
+    user_data: TxUserDataCell,
+
+    /// Commit and abort callbacks.
+    hooks: TxHooks,
+
+    /// Recorded changes, if change data capture is enabled.
+    changes: TxChanges,
 }
 
 // SAFETY: Access to the transaction is synchronized by the lock.
@@ -159,7 +342,7 @@ unsafe impl Sync for PtrSync {}
 impl PtrSync {
     /// Acquires the inner transaction lock to guarantee exclusive access to the transaction
     /// pointer.
-    pub(crate) fn lock(&self) -> MutexGuard<'_, ()> {
+    pub(crate) fn lock(&self) -> MutexGuard<'_, Option<ThreadId>> {
         if let Some(lock) = self.lock.try_lock() {
             lock
         } else {
@@ -173,6 +356,14 @@ impl PtrSync {
             self.lock.lock()
         }
     }
+
+    /// Tracks the current thread as the recorded owner of the transaction.
+    ///
+    /// The environment's owner record is released once the transaction is
+    /// used from another thread, as the opener no longer holds it.
+    pub(crate) fn track_owner(&self) {
+        *self.lock() = Some(thread::current().id());
+    }
 }
 
 impl TxPtrAccess for PtrSync {
@@ -182,10 +373,14 @@ impl TxPtrAccess for PtrSync {
     {
         Self {
             committed: AtomicBool::new(false),
-            lock: Mutex::new(()),
+            lock: Mutex::new(None),
             txn: ptr,
             env,
             is_read_only,
+            cancel: OnceLock::new(),
+            user_data: TxUserDataCell::default(),
+            hooks: TxHooks::default(),
+            changes: TxChanges::default(),
         }
     }
 
@@ -193,12 +388,85 @@ impl TxPtrAccess for PtrSync {
     where
         F: FnOnce(*mut ffi::MDBX_txn) -> R,
     {
-        let _lock = self.lock();
+        let mut lock = self.lock();
+        if let Some(owner) = *lock
+            && owner != thread::current().id()
+        {
+            self.env.rw_txn_owner().release(self.txn);
+            *lock = None;
+        }
+        if let Some(state) = self.cancel.get() {
+            // SAFETY: the lock gives exclusive access to the transaction.
+            unsafe { state.apply(self.txn) };
+        }
         f(self.txn)
     }
 
     fn mark_committed(&self) {
         self.committed.store(true, Ordering::SeqCst);
+        if !self.is_read_only {
+            self.env.rw_txn_owner().release(self.txn);
+        }
+        if let Some(state) = self.cancel.get() {
+            state.release();
+        }
+    }
+
+    fn abort(&self) -> MdbxResult<()> {
+        self.detach_cancel();
+        self.with_txn_ptr(|txn| {
+            let res = if self.is_read_only {
+                // RO: direct abort is safe and fast.
+                // SAFETY: the transaction is marked as finished below, so
+                // the pointer is not used again.
+                mdbx_result(unsafe { ffi::mdbx_txn_abort(txn) })
+            } else {
+                // RW: must go through txn manager for thread safety.
+                let (sender, rx) = sync_channel(0);
+                self.env.txn_manager().send(Abort {
+                    tx: RawTxPtr(txn),
+                    sender,
+                    span: debug_span!("txn_manager_abort"),
+                });
+                rx.recv().unwrap()
+            };
+            self.mark_committed();
+            res
+        })?;
+        tracing::debug!(target: "libmdbx", "aborted");
+        Ok(())
+    }
+
+    fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle::new(Arc::clone(self.cancel_state()))
+    }
+
+    fn cancel_state(&self) -> &Arc<CancelState> {
+        self.cancel.get_or_init(|| Arc::new(CancelState::new()))
+    }
+
+    fn set_cancel_parent(&self, parent: &Arc<CancelState>) {
+        let _ = self.cancel.set(Arc::new(CancelState::nested(parent)));
+    }
+
+    fn is_broken(&self) -> bool {
+        self.cancel.get().is_some_and(|state| state.is_broken())
+    }
+
+    fn detach_cancel(&self) -> bool {
+        self.cancel.get().is_some_and(|state| state.detach())
+    }
+
+    fn user_data(&self) -> &TxUserDataCell {
+        &self.user_data
+    }
+
+    fn hooks(&self) -> &TxHooks {
+        &self.hooks
+    }
+
+    fn changes(&self) -> &TxChanges {
+        &self.changes
     }
 }
 
@@ -208,20 +476,11 @@ impl Drop for PtrSync {
             return;
         }
 
-        if self.is_read_only {
-            // RO: direct abort is safe and fast.
-            // SAFETY: We have exclusive ownership of this pointer.
-            unsafe { ffi::mdbx_txn_abort(self.txn) };
-        } else {
-            // RW: must go through txn manager for thread safety.
-            let (sender, rx) = sync_channel(0);
-            self.env.txn_manager().send(Abort {
-                tx: RawTxPtr(self.txn),
-                sender,
-                span: debug_span!("txn_manager_abort"),
-            });
-            rx.recv().unwrap().unwrap();
+        let res = self.abort();
+        // Failing to abort a RW transaction through the txn manager leaves
+        // the write lock held.
+        if !self.is_read_only {
+            res.unwrap();
         }
-        tracing::debug!(target: "libmdbx", "aborted");
     }
 }
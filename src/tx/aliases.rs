@@ -6,7 +6,9 @@ use crate::{
         PtrSync, PtrUnsync,
         cursor::Cursor,
         r#impl::Tx,
-        iter::{Iter, IterDup, IterDupFixed, IterDupFixedOfKey, IterDupOfKey},
+        iter::{
+            Iter, IterDup, IterDupFixed, IterDupFixedOfKey, IterDupOfKey, IterDupRange, IterRange,
+        },
     },
 };
 use std::{borrow::Cow, sync::Arc};
@@ -65,6 +67,27 @@ pub type RwCursorUnsync<'tx> = Cursor<'tx, Rw>;
 pub type IterKeyVals<'tx, 'cur, K, Key = Cow<'tx, [u8]>, Value = Cow<'tx, [u8]>> =
     Iter<'tx, 'cur, K, Key, Value, { ffi::MDBX_NEXT }>;
 
+/// Iterates over KV pairs in an MDBX database, in descending order.
+pub type IterKeyValsRev<'tx, 'cur, K, Key = Cow<'tx, [u8]>, Value = Cow<'tx, [u8]>> =
+    Iter<'tx, 'cur, K, Key, Value, { ffi::MDBX_PREV }>;
+
+/// Iterates over the values of a single DUPSORT key, in descending order.
+pub type IterDupOfKeyRev<'tx, 'cur, K, Value = Cow<'tx, [u8]>> =
+    IterDupOfKey<'tx, 'cur, K, Value, { ffi::MDBX_PREV_DUP }>;
+
+/// Iterates over KV pairs in a range of keys, in descending order.
+pub type IterRangeRev<'tx, 'cur, K, Key = Cow<'tx, [u8]>, Value = Cow<'tx, [u8]>> =
+    IterRange<'tx, 'cur, K, Key, Value, { ffi::MDBX_PREV }>;
+
+/// Iterates over a range of the values of a single DUPSORT key, in
+/// descending order.
+pub type IterDupRangeRev<'tx, 'cur, K, Value = Cow<'tx, [u8]>> =
+    IterDupRange<'tx, 'cur, K, Value, { ffi::MDBX_PREV_DUP }>;
+
+/// Iterates over a DUPFIXED table, in descending order.
+pub type IterDupFixedRev<'tx, 'cur, K, Key = Cow<'tx, [u8]>, Value = Cow<'tx, [u8]>> =
+    IterDupFixed<'tx, 'cur, K, Key, Value, { ffi::MDBX_PREV_MULTIPLE }>;
+
 // --- DUPSORT iterator aliases ---
 
 /// A flat DUPSORT iterator for a synchronized read-only transaction.
@@ -10,6 +10,7 @@
 #![allow(dead_code)]
 
 use crate::DatabaseFlags;
+use std::cmp::Ordering;
 
 /// Debug assertion that validates key size constraints.
 ///
@@ -114,6 +115,7 @@ pub(crate) fn debug_assert_put(pagesize: usize, flags: DatabaseFlags, key: &[u8]
 /// All debug assertions for append operations.
 ///
 /// Combines: key size, value size, integer key/dup checks, and append ordering.
+/// Keys are ordered by `cmp`, the table's key comparator.
 #[inline(always)]
 #[track_caller]
 pub(crate) fn debug_assert_append(
@@ -122,14 +124,16 @@ pub(crate) fn debug_assert_append(
     key: &[u8],
     data: &[u8],
     last_key: Option<&[u8]>,
+    cmp: impl Fn(&[u8], &[u8]) -> Ordering,
 ) {
     debug_assert_put(pagesize, flags, key, data);
-    debug_assert_append_key_order(flags, last_key, key);
+    debug_assert_append_key_order(flags, last_key, key, cmp);
 }
 
 /// All debug assertions for append_dup operations.
 ///
 /// Combines: key size, value size, integer key/dup checks, and append dup ordering.
+/// Values are ordered by `cmp`, the table's duplicate value comparator.
 #[inline(always)]
 #[track_caller]
 pub(crate) fn debug_assert_append_dup(
@@ -138,57 +142,65 @@ pub(crate) fn debug_assert_append_dup(
     key: &[u8],
     data: &[u8],
     last_dup: Option<&[u8]>,
+    cmp: impl Fn(&[u8], &[u8]) -> Ordering,
 ) {
     debug_assert_put(pagesize, flags, key, data);
-    debug_assert_append_dup_order(flags, last_dup, data);
+    debug_assert_append_dup_order(flags, last_dup, data, cmp);
 }
 
-/// Internal: validates append ordering for keys.
+/// Internal: validates append ordering for keys, compared by `cmp`.
 ///
-/// Skips the check for REVERSE_KEY databases since the comparison semantics
-/// differ (keys are compared from end to beginning) and implementing the
-/// reverse comparison would be complex. MDBX will return KeyMismatch if the
-/// order is wrong.
+/// Skips the check for REVERSE_KEY databases, leaving MDBX to return
+/// KeyMismatch if the order is wrong.
 #[inline(always)]
 #[track_caller]
-fn debug_assert_append_key_order(flags: DatabaseFlags, last_key: Option<&[u8]>, new_key: &[u8]) {
+fn debug_assert_append_key_order(
+    flags: DatabaseFlags,
+    last_key: Option<&[u8]>,
+    new_key: &[u8],
+    cmp: impl Fn(&[u8], &[u8]) -> Ordering,
+) {
     #[cfg(debug_assertions)]
     if !flags.contains(DatabaseFlags::REVERSE_KEY)
         && let Some(last) = last_key
     {
         debug_assert!(
-            new_key > last,
+            cmp(new_key, last).is_gt(),
             "Append key must be greater than last key: new={:?} <= last={:?}",
             new_key,
             last
         );
     }
     #[cfg(not(debug_assertions))]
-    let _ = (flags, last_key, new_key);
+    let _ = (flags, last_key, new_key, cmp);
 }
 
-/// Internal: validates append ordering for duplicate values.
+/// Internal: validates append ordering for duplicate values, compared by
+/// `cmp`.
 ///
-/// Skips the check for REVERSE_DUP databases since the comparison semantics
-/// differ (values are compared from end to beginning) and implementing the
-/// reverse comparison would be complex. MDBX will return KeyMismatch if the
-/// order is wrong.
+/// Skips the check for REVERSE_DUP databases, leaving MDBX to return
+/// KeyMismatch if the order is wrong.
 #[inline(always)]
 #[track_caller]
-fn debug_assert_append_dup_order(flags: DatabaseFlags, last_dup: Option<&[u8]>, new_data: &[u8]) {
+fn debug_assert_append_dup_order(
+    flags: DatabaseFlags,
+    last_dup: Option<&[u8]>,
+    new_data: &[u8],
+    cmp: impl Fn(&[u8], &[u8]) -> Ordering,
+) {
     #[cfg(debug_assertions)]
     if !flags.contains(DatabaseFlags::REVERSE_DUP)
         && let Some(last) = last_dup
     {
         debug_assert!(
-            new_data > last,
+            cmp(new_data, last).is_gt(),
             "Append dup must be greater than last dup: new={:?} <= last={:?}",
             new_data,
             last
         );
     }
     #[cfg(not(debug_assertions))]
-    let _ = (flags, last_dup, new_data);
+    let _ = (flags, last_dup, new_data, cmp);
 }
 
 /// Debug assertion that validates DUP_SORT flag is set.
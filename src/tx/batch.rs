@@ -0,0 +1,222 @@
+use crate::{Database, Environment, MdbxError, MdbxResult, WriteFlags, tx::aliases::RwTxUnsync};
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::mpsc::{Receiver, Sender, channel},
+    thread,
+};
+
+/// Maximum number of submissions applied in a single write transaction.
+const MAX_GROUP_SIZE: usize = 1024;
+
+/// A unit of work applied by the [`WriteBatcher`].
+type Job = Box<dyn FnOnce(&RwTxUnsync) -> MdbxResult<()> + Send>;
+
+/// A single operation in a [`WriteBatch`].
+#[derive(Debug, Clone)]
+enum BatchOp {
+    Put { db: Database, key: Vec<u8>, value: Vec<u8>, flags: WriteFlags },
+    Del { db: Database, key: Vec<u8>, value: Option<Vec<u8>> },
+}
+
+/// An owned list of writes, applied atomically by a [`WriteBatcher`].
+///
+/// Operations are applied in insertion order. If any of them fails, none of
+/// the batch's writes are committed.
+#[derive(Debug, Clone, Default)]
+pub struct WriteBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl WriteBatch {
+    /// Creates an empty batch.
+    pub const fn new() -> Self {
+        Self { ops: Vec::new() }
+    }
+
+    /// Adds a put of a key/value pair. See [`Tx::put`].
+    ///
+    /// [`Tx::put`]: crate::tx::Tx::put
+    pub fn put(
+        &mut self,
+        db: Database,
+        key: impl AsRef<[u8]>,
+        value: impl AsRef<[u8]>,
+        flags: WriteFlags,
+    ) -> &mut Self {
+        self.ops.push(BatchOp::Put {
+            db,
+            key: key.as_ref().to_vec(),
+            value: value.as_ref().to_vec(),
+            flags,
+        });
+        self
+    }
+
+    /// Adds a delete of a key, or of a single key/value pair for
+    /// [`DatabaseFlags::DUP_SORT`] tables. See [`Tx::del`].
+    ///
+    /// Deleting a missing key is not an error.
+    ///
+    /// [`DatabaseFlags::DUP_SORT`]: crate::DatabaseFlags::DUP_SORT
+    /// [`Tx::del`]: crate::tx::Tx::del
+    pub fn del(&mut self, db: Database, key: impl AsRef<[u8]>, value: Option<&[u8]>) -> &mut Self {
+        self.ops.push(BatchOp::Del {
+            db,
+            key: key.as_ref().to_vec(),
+            value: value.map(<[u8]>::to_vec),
+        });
+        self
+    }
+
+    /// Returns the number of operations in the batch.
+    pub const fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns `true` if the batch contains no operations.
+    pub const fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Applies the batch to a write transaction.
+    pub fn apply(&self, txn: &RwTxUnsync) -> MdbxResult<()> {
+        for op in &self.ops {
+            match op {
+                BatchOp::Put { db, key, value, flags } => txn.put(*db, key, value, *flags)?,
+                BatchOp::Del { db, key, value } => {
+                    txn.del(*db, key, value.as_deref())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Group commit for many concurrent small writers.
+///
+/// Submissions from any number of threads are queued and applied by a
+/// background thread in a single [`RwTxUnsync`], which is then committed
+/// once, so that concurrent writers share the cost of the commit and its
+/// sync. Each submission runs in its own nested transaction: a failing
+/// submission is rolled back and reported to its submitter, without
+/// affecting the others in the group.
+///
+/// Every submission returns a channel that receives `Ok(())` once its writes
+/// are durably committed, or the error that prevented them from being
+/// committed.
+///
+/// The batcher is cheap to clone. The background thread exits once every
+/// clone has been dropped and the queued submissions have been applied.
+///
+/// Created with [`Environment::write_batcher`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use signet_libmdbx::{Environment, WriteBatch, WriteFlags};
+/// # let env = Environment::builder().open(std::path::Path::new("db")).unwrap();
+/// let db = env.begin_ro_sync().unwrap().open_db(None).unwrap();
+/// let batcher = env.write_batcher().unwrap();
+///
+/// let mut batch = WriteBatch::new();
+/// batch.put(db, b"key", b"value", WriteFlags::empty());
+/// batcher.submit(batch).recv().unwrap().unwrap();
+/// ```
+#[derive(Clone)]
+pub struct WriteBatcher {
+    sender: Sender<(Job, Sender<MdbxResult<()>>)>,
+}
+
+impl fmt::Debug for WriteBatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteBatcher").finish_non_exhaustive()
+    }
+}
+
+impl WriteBatcher {
+    /// Spawns the background thread applying submissions to `env`.
+    ///
+    /// Fails with [`MdbxError::NestedTransactionsUnsupportedWithWriteMap`]
+    /// for WRITEMAP environments, as submissions cannot be isolated there,
+    /// and with the system error if the thread cannot be spawned.
+    pub(crate) fn spawn(env: Environment) -> MdbxResult<Self> {
+        if env.is_write_map() {
+            return Err(MdbxError::NestedTransactionsUnsupportedWithWriteMap);
+        }
+
+        let (sender, rx) = channel();
+        thread::Builder::new()
+            .name("mdbx-rs-write-batcher".to_string())
+            .spawn(move || run(env, rx))
+            .map_err(|err| MdbxError::Other(err.raw_os_error().unwrap_or(ffi::MDBX_ENOMEM)))?;
+        Ok(Self { sender })
+    }
+
+    /// Submits a batch of writes.
+    pub fn submit(&self, batch: WriteBatch) -> Receiver<MdbxResult<()>> {
+        self.submit_fn(move |txn| batch.apply(txn))
+    }
+
+    /// Submits a closure that performs writes on the group transaction.
+    ///
+    /// The closure runs in its own nested transaction, on the batcher's
+    /// thread. If it returns an error, its writes are rolled back and the
+    /// error is sent to the returned channel.
+    ///
+    /// If the closure panics, its writes are rolled back in the same way,
+    /// and [`MdbxError::SubmissionPanicked`] is sent to the returned
+    /// channel. The rest of its group is unaffected.
+    pub fn submit_fn<F>(&self, f: F) -> Receiver<MdbxResult<()>>
+    where
+        F: FnOnce(&RwTxUnsync) -> MdbxResult<()> + Send + 'static,
+    {
+        let (result_tx, result_rx) = channel();
+        // If the batcher stopped, the result sender is dropped and the
+        // receiver reports the disconnect.
+        let _ = self.sender.send((Box::new(f), result_tx));
+        result_rx
+    }
+}
+
+/// Applies queued submissions until every [`WriteBatcher`] is dropped.
+fn run(env: Environment, rx: Receiver<(Job, Sender<MdbxResult<()>>)>) {
+    let mut group = Vec::new();
+    while let Ok(first) = rx.recv() {
+        group.push(first);
+        group.extend(rx.try_iter().take(MAX_GROUP_SIZE - 1));
+        apply_group(&env, &mut group);
+    }
+}
+
+/// Applies a group of submissions in one transaction and reports results.
+fn apply_group(env: &Environment, group: &mut Vec<(Job, Sender<MdbxResult<()>>)>) {
+    let mut txn = match env.begin_rw_unsync() {
+        Ok(txn) => txn,
+        Err(err) => {
+            for (_, result_tx) in group.drain(..) {
+                let _ = result_tx.send(Err(err));
+            }
+            return;
+        }
+    };
+
+    let mut results = Vec::with_capacity(group.len());
+    for (job, result_tx) in group.drain(..) {
+        let res = txn.begin_nested_txn().and_then(|nested| {
+            match panic::catch_unwind(AssertUnwindSafe(|| job(&nested))) {
+                Ok(res) => res.and_then(|()| nested.commit()),
+                Err(_) => {
+                    let _ = nested.abort();
+                    Err(MdbxError::SubmissionPanicked)
+                }
+            }
+        });
+        results.push((res, result_tx));
+    }
+
+    let commit = txn.commit();
+    for (res, result_tx) in results {
+        let _ = result_tx.send(res.and(commit));
+    }
+}
@@ -0,0 +1,475 @@
+use crate::{
+    Cursor, Database, DatabaseFlags, Environment, MdbxError, MdbxResult, Rw, error::IntoMdbxResult,
+};
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Default maximum number of rows written per transaction.
+const DEFAULT_MAX_ROWS: usize = 1_000_000;
+
+/// Default dirty space, in bytes, after which a transaction is committed.
+const DEFAULT_MAX_DIRTY_BYTES: u64 = 256 << 20;
+
+/// Default number of bytes the map is grown by after [`MdbxError::MapFull`].
+const DEFAULT_GROW_STEP: usize = 1 << 30;
+
+/// Rows written between checks of a transaction's dirty space.
+const DIRTY_CHECK_INTERVAL: usize = 1024;
+
+/// An owned key/value pair.
+type Row = (Vec<u8>, Vec<u8>);
+
+/// A callback receiving the progress of a load after each commit.
+type ProgressFn = Box<dyn FnMut(&LoadStats)>;
+
+/// Throughput of a [`BulkLoader::load`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadStats {
+    rows: u64,
+    bytes: u64,
+    commits: u64,
+    map_grows: u64,
+    elapsed: Duration,
+}
+
+impl LoadStats {
+    /// The number of rows committed.
+    #[inline]
+    pub const fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// The number of key and value bytes committed.
+    #[inline]
+    pub const fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The number of transactions committed.
+    #[inline]
+    pub const fn commits(&self) -> u64 {
+        self.commits
+    }
+
+    /// The number of times the map was grown after [`MdbxError::MapFull`].
+    #[inline]
+    pub const fn map_grows(&self) -> u64 {
+        self.map_grows
+    }
+
+    /// The time spent loading.
+    #[inline]
+    pub const fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Committed rows per second.
+    pub fn rows_per_sec(&self) -> f64 {
+        self.rows as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Committed key and value bytes per second.
+    pub fn bytes_per_sec(&self) -> f64 {
+        self.bytes as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// How far a failed [`BulkLoader::load`] got.
+///
+/// Every row before the position was committed, and none after it. To
+/// resume, call [`BulkLoader::load`] again with the rows following
+/// [`LoadPosition::last_key`], or after skipping [`LoadPosition::rows`] rows
+/// of the original source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadPosition {
+    rows: u64,
+    last: Option<Row>,
+}
+
+impl LoadPosition {
+    /// The number of rows of the source that were committed.
+    #[inline]
+    pub const fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// The key of the last committed row, if any.
+    pub fn last_key(&self) -> Option<&[u8]> {
+        self.last.as_ref().map(|(key, _)| key.as_slice())
+    }
+
+    /// The value of the last committed row, if any.
+    pub fn last_value(&self) -> Option<&[u8]> {
+        self.last.as_ref().map(|(_, value)| value.as_slice())
+    }
+}
+
+/// The error of a failed [`BulkLoader::load`], with the position to resume
+/// from.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("bulk load failed after {} rows: {error}", position.rows)]
+pub struct BulkLoadError {
+    error: MdbxError,
+    position: LoadPosition,
+    stats: LoadStats,
+}
+
+impl BulkLoadError {
+    /// The error that stopped the load.
+    #[inline]
+    pub const fn error(&self) -> MdbxError {
+        self.error
+    }
+
+    /// The position to resume the load from.
+    #[inline]
+    pub const fn position(&self) -> &LoadPosition {
+        &self.position
+    }
+
+    /// Throughput of the load until it failed.
+    #[inline]
+    pub const fn stats(&self) -> &LoadStats {
+        &self.stats
+    }
+}
+
+/// How writing a chunk of rows ended.
+enum ChunkEnd {
+    /// The source has no more rows.
+    Exhausted,
+    /// The transaction reached its size limit.
+    Full,
+    /// The row following the chunk is out of order.
+    OutOfOrder,
+}
+
+/// The state of a load across transactions.
+struct LoadState {
+    stats: LoadStats,
+    position: LoadPosition,
+    /// Rows of aborted chunks, written again before reading the source.
+    replay: VecDeque<Row>,
+    /// Rows written per transaction, lowered after [`MdbxError::TxnFull`].
+    max_rows: usize,
+}
+
+/// Loads large amounts of sorted data into tables, committing in chunks.
+///
+/// Rows are written with [`Cursor::append`], [`Cursor::append_dup`] for
+/// [`DatabaseFlags::DUP_SORT`] tables, or [`Cursor::put_multiple`] for
+/// [`DatabaseFlags::DUP_FIXED`] tables, in write transactions that are
+/// committed once they hold [`set_max_rows`] rows or dirty
+/// [`set_max_dirty_bytes`] bytes.
+///
+/// The loader recovers from running out of space:
+/// - On [`MdbxError::MapFull`], the map is grown by [`set_grow_step`] bytes,
+///   up to [`set_max_map_size`], and the uncommitted rows are written again,
+///   if MDBX can extend the mapping in place.
+/// - On [`MdbxError::TxnFull`], the uncommitted rows are written again in
+///   smaller transactions.
+///
+/// Rows must be sorted in the table's order, and sort after any rows already
+/// in the table. Out-of-order rows fail with [`MdbxError::KeyMismatch`],
+/// after committing the rows before them. Any failure is reported with the
+/// [`LoadPosition`] to resume from.
+///
+/// Created with [`Environment::bulk_loader`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use signet_libmdbx::{DatabaseFlags, Environment};
+/// # let env = Environment::builder().open(std::path::Path::new("db")).unwrap();
+/// let txn = env.begin_rw_sync().unwrap();
+/// let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+/// txn.commit().unwrap();
+///
+/// let rows = (0..100_000_000u64).map(|i| (i.to_be_bytes(), [0u8; 32]));
+/// let stats = env.bulk_loader().set_max_rows(500_000).load(db, rows).unwrap();
+/// println!("{:.0} rows/s", stats.rows_per_sec());
+/// ```
+///
+/// [`set_max_rows`]: Self::set_max_rows
+/// [`set_max_dirty_bytes`]: Self::set_max_dirty_bytes
+/// [`set_grow_step`]: Self::set_grow_step
+/// [`set_max_map_size`]: Self::set_max_map_size
+pub struct BulkLoader {
+    env: Environment,
+    max_rows: usize,
+    max_dirty_bytes: u64,
+    grow_step: usize,
+    max_map_size: usize,
+    progress: Option<ProgressFn>,
+}
+
+impl fmt::Debug for BulkLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkLoader")
+            .field("max_rows", &self.max_rows)
+            .field("max_dirty_bytes", &self.max_dirty_bytes)
+            .field("grow_step", &self.grow_step)
+            .field("max_map_size", &self.max_map_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl BulkLoader {
+    /// Creates a loader writing to `env`.
+    pub(crate) const fn new(env: Environment) -> Self {
+        Self {
+            env,
+            max_rows: DEFAULT_MAX_ROWS,
+            max_dirty_bytes: DEFAULT_MAX_DIRTY_BYTES,
+            grow_step: DEFAULT_GROW_STEP,
+            max_map_size: usize::MAX,
+            progress: None,
+        }
+    }
+
+    /// Sets the maximum number of rows written per transaction.
+    pub const fn set_max_rows(&mut self, rows: usize) -> &mut Self {
+        self.max_rows = if rows == 0 { 1 } else { rows };
+        self
+    }
+
+    /// Sets the dirty space, in bytes, after which a transaction is
+    /// committed. See [`TxInfo::space_dirty`].
+    ///
+    /// [`TxInfo::space_dirty`]: crate::TxInfo::space_dirty
+    pub const fn set_max_dirty_bytes(&mut self, bytes: u64) -> &mut Self {
+        self.max_dirty_bytes = bytes;
+        self
+    }
+
+    /// Sets the number of bytes the map is grown by after
+    /// [`MdbxError::MapFull`]. Zero disables growing the map.
+    ///
+    /// The map grows within the upper bound of its [`Geometry`] first, and
+    /// then raises the bound, which MDBX can only do while the address space
+    /// following the mapping is free. Otherwise the load fails with the error
+    /// of the failed attempt, usually [`MdbxError::UnableExtendMapSize`], and
+    /// the upper bound must be raised by reopening the environment.
+    ///
+    /// [`Geometry`]: crate::Geometry
+    pub const fn set_grow_step(&mut self, bytes: usize) -> &mut Self {
+        self.grow_step = bytes;
+        self
+    }
+
+    /// Sets the size, in bytes, the map is never grown beyond.
+    pub const fn set_max_map_size(&mut self, bytes: usize) -> &mut Self {
+        self.max_map_size = bytes;
+        self
+    }
+
+    /// Registers a callback receiving the progress of the load after each
+    /// commit.
+    pub fn on_progress(&mut self, f: impl FnMut(&LoadStats) + 'static) -> &mut Self {
+        self.progress = Some(Box::new(f));
+        self
+    }
+
+    /// Loads sorted rows into a table.
+    ///
+    /// Returns the throughput of the load once every row is committed.
+    pub fn load<I, K, V>(&mut self, db: Database, rows: I) -> Result<LoadStats, BulkLoadError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let start = Instant::now();
+        let mut source = rows.into_iter().map(|(k, v)| (k.as_ref().to_vec(), v.as_ref().to_vec()));
+        let mut state = LoadState {
+            stats: LoadStats::default(),
+            position: LoadPosition::default(),
+            replay: VecDeque::new(),
+            max_rows: self.max_rows,
+        };
+
+        loop {
+            let mut chunk = Vec::new();
+            let res = self.write_chunk(db, &mut state, &mut source, &mut chunk);
+            state.stats.elapsed = start.elapsed();
+
+            let end = match res {
+                Ok(end) => end,
+                Err(MdbxError::MapFull) => match self.grow() {
+                    Ok(true) => {
+                        state.stats.map_grows += 1;
+                        chunk.into_iter().rev().for_each(|row| state.replay.push_front(row));
+                        continue;
+                    }
+                    Ok(false) => return Err(state.fail(MdbxError::MapFull)),
+                    Err(error) => return Err(state.fail(error)),
+                },
+                Err(MdbxError::TxnFull) if chunk.len() > 1 => {
+                    state.max_rows = chunk.len() / 2;
+                    chunk.into_iter().rev().for_each(|row| state.replay.push_front(row));
+                    continue;
+                }
+                Err(error) => return Err(state.fail(error)),
+            };
+
+            if !chunk.is_empty() {
+                state.record_commit(chunk);
+                if let Some(progress) = &mut self.progress {
+                    progress(&state.stats);
+                }
+            }
+
+            match end {
+                ChunkEnd::Exhausted => return Ok(state.stats),
+                ChunkEnd::Full => {}
+                ChunkEnd::OutOfOrder => return Err(state.fail(MdbxError::KeyMismatch)),
+            }
+        }
+    }
+
+    /// Grows the map after [`MdbxError::MapFull`], returning `false` if it
+    /// cannot grow any further.
+    fn grow(&self) -> MdbxResult<bool> {
+        if self.grow_step == 0 {
+            return Ok(false);
+        }
+        self.env.grow_map(self.grow_step, self.max_map_size)
+    }
+
+    /// Writes and commits one transaction's worth of rows.
+    ///
+    /// Every row written is moved into `chunk`, so that the rows can be
+    /// written again if the transaction fails.
+    fn write_chunk(
+        &self,
+        db: Database,
+        state: &mut LoadState,
+        source: &mut impl Iterator<Item = Row>,
+        chunk: &mut Vec<Row>,
+    ) -> MdbxResult<ChunkEnd> {
+        let txn = self.env.begin_rw_unsync()?;
+        let mut cursor = txn.cursor(db)?;
+        let table_last = cursor.last::<Vec<u8>, Vec<u8>>().into_mdbx()?;
+
+        let mut writer = RowWriter::new(db.flags());
+        let mut end = ChunkEnd::Full;
+        while chunk.len() < state.max_rows {
+            let Some(row) = state.replay.pop_front().or_else(|| source.next()) else {
+                end = ChunkEnd::Exhausted;
+                break;
+            };
+            let prev = chunk.last().or(table_last.as_ref());
+            if prev.is_some_and(|prev| !writer.in_order(&cursor, prev, &row)) {
+                end = ChunkEnd::OutOfOrder;
+                break;
+            }
+
+            let same_key = prev.is_some_and(|(key, _)| *key == row.0);
+            chunk.push(row);
+            writer.write(&mut cursor, &chunk[chunk.len() - 1], same_key)?;
+
+            if chunk.len().is_multiple_of(DIRTY_CHECK_INTERVAL)
+                && txn.info()?.space_dirty() >= self.max_dirty_bytes
+            {
+                break;
+            }
+        }
+
+        writer.flush(&mut cursor)?;
+        if chunk.is_empty() {
+            // Nothing to commit; dropping the transaction aborts it.
+            return Ok(end);
+        }
+        drop(cursor);
+        txn.commit()?;
+        Ok(end)
+    }
+}
+
+/// Writes sorted rows to a table with the cheapest operation available.
+struct RowWriter {
+    dup_sort: bool,
+    dup_fixed: bool,
+    /// For DUP_FIXED tables, the key, values and value size of the pending
+    /// `put_multiple`.
+    group: Option<(Vec<u8>, Vec<u8>, usize)>,
+}
+
+impl RowWriter {
+    const fn new(flags: DatabaseFlags) -> Self {
+        Self {
+            dup_sort: flags.contains(DatabaseFlags::DUP_SORT),
+            dup_fixed: flags.contains(DatabaseFlags::DUP_FIXED),
+            group: None,
+        }
+    }
+
+    /// Returns `true` if `row` sorts after `prev` in the cursor's table.
+    fn in_order(&self, cursor: &Cursor<'_, Rw>, prev: &Row, row: &Row) -> bool {
+        match cursor.cmp_keys(&row.0, &prev.0) {
+            Ordering::Greater => true,
+            Ordering::Equal => self.dup_sort && cursor.cmp_values(&row.1, &prev.1).is_gt(),
+            Ordering::Less => false,
+        }
+    }
+
+    /// Writes a row that sorts after every row in the table.
+    fn write(&mut self, cursor: &mut Cursor<'_, Rw>, row: &Row, same_key: bool) -> MdbxResult<()> {
+        if self.dup_fixed && !row.1.is_empty() {
+            if let Some((key, values, size)) = &mut self.group
+                && *key == row.0
+                && *size == row.1.len()
+            {
+                values.extend_from_slice(&row.1);
+                return Ok(());
+            }
+            self.flush(cursor)?;
+            self.group = Some((row.0.clone(), row.1.clone(), row.1.len()));
+            return Ok(());
+        }
+
+        self.flush(cursor)?;
+        if self.dup_sort && same_key {
+            cursor.append_dup(&row.0, &row.1)
+        } else {
+            cursor.append(&row.0, &row.1)
+        }
+    }
+
+    /// Writes the pending `put_multiple`, if any.
+    ///
+    /// MDBX may store fewer values than requested per call, so the rest are
+    /// written by further calls.
+    fn flush(&mut self, cursor: &mut Cursor<'_, Rw>) -> MdbxResult<()> {
+        let Some((key, values, size)) = self.group.take() else { return Ok(()) };
+        let mut rest = values.as_slice();
+        while !rest.is_empty() {
+            let written = cursor.put_multiple(&key, rest, size)?;
+            if written == 0 {
+                return Err(MdbxError::KeyExist);
+            }
+            rest = &rest[written * size..];
+        }
+        Ok(())
+    }
+}
+
+impl LoadState {
+    /// Records the rows of a committed chunk.
+    fn record_commit(&mut self, mut chunk: Vec<Row>) {
+        self.stats.rows += chunk.len() as u64;
+        self.stats.bytes += chunk.iter().map(|(k, v)| (k.len() + v.len()) as u64).sum::<u64>();
+        self.stats.commits += 1;
+        self.position.rows = self.stats.rows;
+        self.position.last = chunk.pop();
+    }
+
+    /// Wraps the error that stopped the load with its position.
+    fn fail(self, error: MdbxError) -> BulkLoadError {
+        BulkLoadError { error, position: self.position, stats: self.stats }
+    }
+}
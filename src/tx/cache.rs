@@ -11,6 +11,10 @@
 //! - [`SharedCache`]: A thread-safe cache using `Arc<RwLock<...>>` for
 //!   synchronized transactions.
 //!
+//! Transactions fall back to the environment-wide [`EnvDbCache`] before
+//! opening a handle through FFI, as MDBX handles are shared by every
+//! transaction in an environment.
+//!
 //! [`TxSync`]: crate::tx::aliases::TxSync
 //! [`TxUnsync`]: crate::tx::aliases::TxUnsync
 
@@ -38,6 +42,9 @@ pub trait Cache: Clone + Default + std::fmt::Debug {
 
     /// Remove a database entry from the cache by dbi.
     fn remove_dbi(&self, dbi: ffi::MDBX_dbi);
+
+    /// Returns a copy of every cached entry.
+    fn entries(&self) -> DbCache;
 }
 
 /// Cached database entry.
@@ -59,6 +66,17 @@ impl CachedDb {
         Self { name_hash, db }
     }
 
+    /// Creates a cached database entry from an already hashed name.
+    pub(crate) const fn from_hash(name_hash: u64, db: Database) -> Self {
+        Self { name_hash, db }
+    }
+
+    /// The cached database.
+    #[inline]
+    pub(crate) const fn db(&self) -> Database {
+        self.db
+    }
+
     #[inline]
     pub(crate) fn hash_name(name: Option<&str>) -> u64 {
         let mut hasher = std::hash::DefaultHasher::new();
@@ -107,6 +125,15 @@ impl DbCache {
     }
 }
 
+impl IntoIterator for DbCache {
+    type Item = CachedDb;
+    type IntoIter = smallvec::IntoIter<[CachedDb; 16]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
 /// Simple cache container for database handles.
 ///
 /// Uses inline storage for the common case (most apps use < 16 databases).
@@ -150,6 +177,11 @@ impl Cache for SharedCache {
         let mut cache = self.write();
         cache.remove_dbi(dbi);
     }
+
+    /// Returns a copy of every cached entry.
+    fn entries(&self) -> DbCache {
+        self.read().clone()
+    }
 }
 
 impl Default for SharedCache {
@@ -176,4 +208,65 @@ impl Cache for RefCell<DbCache> {
         let mut cache = self.borrow_mut();
         cache.remove_dbi(dbi);
     }
+
+    /// Returns a copy of every cached entry.
+    fn entries(&self) -> DbCache {
+        self.borrow().clone()
+    }
+}
+
+/// A database handle published to the [`EnvDbCache`].
+#[derive(Debug, Clone, Copy)]
+struct PublishedDb {
+    db: CachedDb,
+    /// The ID of the write transaction that published the handle. Its table
+    /// exists in the snapshots of this and every later transaction.
+    txn_id: u64,
+}
+
+/// Environment-wide cache of database handles.
+///
+/// Write transactions publish the handles they opened once they commit,
+/// since handles to tables they created are closed if they abort. Each
+/// handle is only handed to transactions whose snapshot includes the
+/// publishing commit, so that a transaction on an older snapshot does not
+/// receive a handle to a table that does not exist in it. Entries may be
+/// stale, and must be validated against the [`DbiRegistry`] before use.
+///
+/// [`DbiRegistry`]: crate::sys::registry::DbiRegistry
+#[derive(Debug, Default)]
+pub(crate) struct EnvDbCache {
+    cache: RwLock<SmallVec<[PublishedDb; 16]>>,
+}
+
+impl EnvDbCache {
+    /// Read a database entry from the cache, if it was published by a
+    /// transaction no later than `txn_id`.
+    pub(crate) fn read_db(&self, name_hash: u64, txn_id: u64) -> Option<Database> {
+        self.cache
+            .read()
+            .iter()
+            .find(|entry| entry.db.name_hash == name_hash && entry.txn_id <= txn_id)
+            .map(|entry| entry.db.db)
+    }
+
+    /// Write database entries published by the committed write transaction
+    /// `txn_id` to the cache, replacing entries with the same name.
+    pub(crate) fn publish(&self, entries: impl IntoIterator<Item = CachedDb>, txn_id: u64) {
+        let mut cache = self.cache.write();
+        for db in entries {
+            let published = PublishedDb { db, txn_id };
+            match cache.iter_mut().find(|entry| entry.db.name_hash == db.name_hash) {
+                // Keep the earliest commit that published the same handle.
+                Some(entry) if entry.db.db == db.db => {}
+                Some(entry) => *entry = published,
+                None => cache.push(published),
+            }
+        }
+    }
+
+    /// Remove a database entry from the cache by dbi.
+    pub(crate) fn remove_dbi(&self, dbi: ffi::MDBX_dbi) {
+        self.cache.write().retain(|entry| entry.db.db.dbi() != dbi);
+    }
 }
@@ -0,0 +1,182 @@
+use crate::MdbxResult;
+use parking_lot::Mutex;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+/// State shared between a transaction and its [`CancelHandle`]s.
+///
+/// A handle only marks the transaction as cancelled. The break itself is
+/// applied by the thread using the transaction, the next time it accesses
+/// the transaction pointer, so the handle never touches libmdbx state from
+/// a foreign thread.
+///
+/// The transaction is detached under the lock before it is committed or
+/// aborted, so a handle never cancels a finished transaction.
+///
+/// Public only because it appears in the sealed [`TxPtrAccess`] trait.
+///
+/// [`TxPtrAccess`]: crate::tx::TxPtrAccess
+#[allow(unreachable_pub)]
+#[derive(Debug)]
+pub struct CancelState {
+    /// Whether the transaction has finished.
+    finished: Mutex<bool>,
+
+    /// Whether the transaction was cancelled by a handle.
+    cancelled: AtomicBool,
+
+    /// Whether `mdbx_txn_break` was applied to the transaction.
+    applied: AtomicBool,
+
+    /// Whether the transaction was released from its parent.
+    released: AtomicBool,
+
+    /// The number of live nested transactions.
+    nested: AtomicUsize,
+
+    /// The state of the parent transaction, for nested transactions.
+    parent: Option<Arc<CancelState>>,
+}
+
+impl CancelState {
+    /// Creates a new cancellation state for a top-level transaction.
+    pub(crate) const fn new() -> Self {
+        Self {
+            finished: Mutex::new(false),
+            cancelled: AtomicBool::new(false),
+            applied: AtomicBool::new(false),
+            released: AtomicBool::new(false),
+            nested: AtomicUsize::new(0),
+            parent: None,
+        }
+    }
+
+    /// Creates a new cancellation state for a nested transaction of the
+    /// transaction owning `parent`.
+    ///
+    /// Must be called while holding access to the parent transaction, so
+    /// that the parent does not apply a break concurrently.
+    pub(crate) fn nested(parent: &Arc<Self>) -> Self {
+        parent.nested.fetch_add(1, Ordering::AcqRel);
+        Self { parent: Some(Arc::clone(parent)), ..Self::new() }
+    }
+
+    /// Returns `true` if the transaction, or one of its parents, was
+    /// cancelled by a handle.
+    #[inline]
+    pub(crate) fn is_broken(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+            || self.parent.as_ref().is_some_and(|parent| parent.is_broken())
+    }
+
+    /// Applies `mdbx_txn_break` to a cancelled transaction, once.
+    ///
+    /// `mdbx_txn_break` also marks the nested transactions in libmdbx, so
+    /// the break is deferred while any are live. Those apply their own
+    /// break when next used, and the parent applies it once they finish.
+    ///
+    /// # Safety
+    ///
+    /// `txn` must be the transaction owning this state, and the caller must
+    /// have exclusive access to it.
+    #[inline]
+    pub(crate) unsafe fn apply(&self, txn: *mut ffi::MDBX_txn) {
+        if self.is_broken()
+            && self.nested.load(Ordering::Acquire) == 0
+            && !self.applied.swap(true, Ordering::AcqRel)
+        {
+            // The transaction is refused with `TxnBroken` regardless, so a
+            // failure here only leaves libmdbx unaware of the break.
+            // SAFETY: guaranteed by the caller.
+            let _ = unsafe { ffi::mdbx_txn_break(txn) };
+        }
+    }
+
+    /// Detaches the transaction, so that later cancellation is a no-op.
+    ///
+    /// Returns `true` if the transaction was broken before it was detached.
+    pub(crate) fn detach(&self) -> bool {
+        *self.finished.lock() = true;
+        self.is_broken()
+    }
+
+    /// Releases a finished nested transaction from its parent, once.
+    ///
+    /// Must be called after the transaction was committed or aborted in
+    /// libmdbx, so that the parent's break does not reach it.
+    pub(crate) fn release(&self) {
+        if let Some(parent) = &self.parent
+            && !self.released.swap(true, Ordering::AcqRel)
+        {
+            parent.nested.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+
+    /// Cancels the transaction, if it has not yet finished.
+    fn cancel(&self) {
+        let finished = self.finished.lock();
+        if !*finished {
+            self.cancelled.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// A cloneable handle that cancels a write transaction from another thread.
+///
+/// Cancelling marks the transaction as broken, which keeps the transaction
+/// and its locks alive but prevents any further operations on it.
+/// Operations that start after cancellation fail with
+/// [`MdbxError::TxnBroken`], and committing the transaction is refused with
+/// the same error. The transaction must then be aborted, either explicitly
+/// with [`Tx::abort`] or by dropping it.
+///
+/// The handle never touches the transaction itself. The thread using the
+/// transaction calls `mdbx_txn_break` the next time it accesses it.
+///
+/// Cancelling a transaction also cancels its nested transactions, which
+/// fail in the same way.
+///
+/// Cancelling a transaction that has already been committed or aborted is
+/// a no-op.
+///
+/// # Example
+///
+/// ```no_run
+/// # use signet_libmdbx::Environment;
+/// # let env = Environment::builder().open(std::path::Path::new("db")).unwrap();
+/// let txn = env.begin_rw_sync().unwrap();
+/// let handle = txn.cancel_handle();
+///
+/// std::thread::spawn(move || handle.cancel().unwrap()).join().unwrap();
+///
+/// assert!(txn.commit().is_err());
+/// ```
+///
+/// [`MdbxError::TxnBroken`]: crate::MdbxError::TxnBroken
+/// [`Tx::abort`]: crate::tx::Tx::abort
+#[derive(Debug, Clone)]
+pub struct CancelHandle {
+    state: Arc<CancelState>,
+}
+
+impl CancelHandle {
+    /// Creates a handle from shared cancellation state.
+    pub(crate) const fn new(state: Arc<CancelState>) -> Self {
+        Self { state }
+    }
+
+    /// Cancels the transaction, preventing any further operations on it.
+    ///
+    /// Does nothing if the transaction has already finished.
+    pub fn cancel(&self) -> MdbxResult<()> {
+        self.state.cancel();
+        Ok(())
+    }
+
+    /// Returns `true` if the transaction was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.is_broken()
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{Database, DatabaseFlags, MdbxResult, tx::ops};
+use parking_lot::Mutex;
+use std::{
+    fmt, mem,
+    sync::{Arc, OnceLock},
+};
+
+/// A callback receiving the changes of a committed transaction.
+type Subscriber = Box<dyn FnOnce(ChangeSet) + Send>;
+
+/// The kind of a recorded [`Change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeOp {
+    /// A value was written at a key.
+    Put,
+    /// A value, or every value, of a key was deleted.
+    Del,
+    /// Every item of the table was deleted.
+    Clear,
+    /// The table was dropped.
+    Drop,
+}
+
+/// A single write recorded in a [`ChangeSet`].
+///
+/// - [`ChangeOp::Put`] changes carry the written value. Values written
+///   through [`Tx::reserve`] are unknown when the write is recorded, and are
+///   `None`.
+/// - [`ChangeOp::Del`] changes carry the deleted value if a single value was
+///   deleted from a [`DatabaseFlags::DUP_SORT`] table, or if one was given
+///   to [`Tx::del`]. Otherwise, every value of the key was deleted.
+/// - [`ChangeOp::Clear`] and [`ChangeOp::Drop`] changes have an empty key and
+///   no value.
+///
+/// If old values are recorded, puts and deletes in tables without
+/// [`DatabaseFlags::DUP_SORT`] also carry the value stored at the key before
+/// the write, if any.
+///
+/// [`Tx::reserve`]: crate::tx::Tx::reserve
+/// [`Tx::del`]: crate::tx::Tx::del
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    db: Database,
+    op: ChangeOp,
+    key: Vec<u8>,
+    value: Option<Vec<u8>>,
+    old_value: Option<Vec<u8>>,
+}
+
+impl Change {
+    /// The handle of the table that was written.
+    ///
+    /// Unlike its raw dbi, which MDBX reuses once a table is closed or
+    /// dropped, the handle identifies the table: handles compare equal only
+    /// if they were opened in the same generation.
+    #[inline]
+    pub const fn db(&self) -> Database {
+        self.db
+    }
+
+    /// The kind of the write.
+    #[inline]
+    pub const fn op(&self) -> ChangeOp {
+        self.op
+    }
+
+    /// The key that was written.
+    #[inline]
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// The value that was written or deleted, if known.
+    #[inline]
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_deref()
+    }
+
+    /// The value stored at the key before the write, if old values are
+    /// recorded and the key was present.
+    #[inline]
+    pub fn old_value(&self) -> Option<&[u8]> {
+        self.old_value.as_deref()
+    }
+}
+
+/// The ordered writes of a committed transaction, passed to the subscribers
+/// registered with [`Tx::record_changes`].
+///
+/// [`Tx::record_changes`]: crate::tx::Tx::record_changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeSet {
+    txn_id: u64,
+    changes: Vec<Change>,
+}
+
+impl ChangeSet {
+    /// The ID of the committed transaction.
+    #[inline]
+    pub const fn txn_id(&self) -> u64 {
+        self.txn_id
+    }
+
+    /// The recorded writes, in the order they were made.
+    #[inline]
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns the number of recorded writes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Returns `true` if no writes were recorded.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl IntoIterator for ChangeSet {
+    type Item = Change;
+    type IntoIter = std::vec::IntoIter<Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.into_iter()
+    }
+}
+
+/// The writes recorded in a transaction, and their subscribers.
+pub(crate) struct Recorder {
+    old_values: bool,
+    changes: Mutex<Vec<Change>>,
+    subscribers: Mutex<Vec<Subscriber>>,
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("old_values", &self.old_values)
+            .field("changes", &self.changes.lock().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Recorder {
+    const fn new(old_values: bool) -> Self {
+        Self { old_values, changes: Mutex::new(Vec::new()), subscribers: Mutex::new(Vec::new()) }
+    }
+
+    /// Reads the value stored at `key`, if old values are recorded and the
+    /// table does not allow duplicates.
+    ///
+    /// # Safety
+    ///
+    /// `txn` must be a valid transaction pointer, and `db` a valid handle.
+    pub(crate) unsafe fn old_value(
+        &self,
+        txn: *mut ffi::MDBX_txn,
+        db: Database,
+        key: &[u8],
+    ) -> MdbxResult<Option<Vec<u8>>> {
+        if !self.old_values || db.flags().contains(DatabaseFlags::DUP_SORT) {
+            return Ok(None);
+        }
+        // SAFETY: caller guarantees txn and db are valid. The value is copied
+        // before the transaction is used again.
+        let val = unsafe { ops::get_raw(txn, db.dbi(), key)? };
+        Ok(val.map(|val| unsafe {
+            std::slice::from_raw_parts(val.iov_base as *const u8, val.iov_len).to_vec()
+        }))
+    }
+
+    /// Records a write.
+    pub(crate) fn push(
+        &self,
+        db: Database,
+        op: ChangeOp,
+        key: &[u8],
+        value: Option<&[u8]>,
+        old_value: Option<Vec<u8>>,
+    ) {
+        self.changes.lock().push(Change {
+            db,
+            op,
+            key: key.to_vec(),
+            value: value.map(<[u8]>::to_vec),
+            old_value,
+        });
+    }
+}
+
+/// The change recorder of a transaction, and that of its parent if it is
+/// nested.
+///
+/// Public only because it appears in the sealed [`TxPtrAccess`] trait.
+///
+/// [`TxPtrAccess`]: crate::tx::TxPtrAccess
+#[allow(unreachable_pub)]
+#[derive(Debug, Default)]
+pub struct TxChanges {
+    recorder: Arc<OnceLock<Recorder>>,
+    parent: OnceLock<Arc<OnceLock<Recorder>>>,
+}
+
+impl TxChanges {
+    /// Returns the recorder, if changes are recorded.
+    #[inline]
+    pub(crate) fn recorder(&self) -> Option<&Recorder> {
+        self.recorder.get()
+    }
+
+    /// Starts recording, if not already recording, and registers a
+    /// subscriber.
+    pub(crate) fn subscribe(&self, old_values: bool, f: impl FnOnce(ChangeSet) + Send + 'static) {
+        let recorder = self.recorder.get_or_init(|| Recorder::new(old_values));
+        recorder.subscribers.lock().push(Box::new(f));
+    }
+
+    /// Returns the number of changes recorded so far, to later discard
+    /// those recorded after it with [`TxChanges::truncate`].
+    pub(crate) fn mark(&self) -> usize {
+        self.recorder().map_or(0, |recorder| recorder.changes.lock().len())
+    }
+
+    /// Discards the changes recorded after `mark`.
+    pub(crate) fn truncate(&self, mark: usize) {
+        if let Some(recorder) = self.recorder() {
+            recorder.changes.lock().truncate(mark);
+        }
+    }
+
+    /// Links a nested transaction's recorder to that of its parent.
+    ///
+    /// If the parent records changes, the nested transaction records them as
+    /// well.
+    pub(crate) fn set_parent(&self, parent: &Self) {
+        if let Some(recorder) = parent.recorder() {
+            let _ = self.recorder.set(Recorder::new(recorder.old_values));
+        }
+        let _ = self.parent.set(Arc::clone(&parent.recorder));
+    }
+
+    /// Handles the recorded changes of a transaction that committed.
+    ///
+    /// For a nested transaction, the changes and subscribers are moved to
+    /// the parent. Otherwise the changes are passed to the subscribers.
+    pub(crate) fn committed(&self, txn_id: u64) {
+        let Some(recorder) = self.recorder() else { return };
+        let changes = mem::take(&mut *recorder.changes.lock());
+        let mut subscribers = mem::take(&mut *recorder.subscribers.lock());
+
+        if let Some(parent) = self.parent.get() {
+            let parent = parent.get_or_init(|| Recorder::new(recorder.old_values));
+            parent.changes.lock().extend(changes);
+            parent.subscribers.lock().extend(subscribers);
+            return;
+        }
+
+        let set = ChangeSet { txn_id, changes };
+        if let Some(last) = subscribers.pop() {
+            for subscriber in subscribers {
+                subscriber(set.clone());
+            }
+            last(set);
+        }
+    }
+}
@@ -1,13 +1,22 @@
 use crate::{
-    Database, ObjectLength, ReadError, ReadResult, TableObject, TableObjectOwned, TransactionKind,
-    codec_try_optional,
-    error::{MdbxResult, mdbx_result},
+    ChangeOp, Database, FixedValue, ObjectLength, ReadResult, TableObject, TableObjectOwned,
+    TableObjectRef, TransactionKind, codec_try_optional,
+    error::{IntoMdbxResult, MdbxResult, mdbx_result},
     flags::*,
+    sys::registry::DbiRegistry,
     tx::{
         TxPtrAccess,
-        aliases::IterKeyVals,
-        iter::{Iter, IterDup, IterDupFixed, IterDupFixedOfKey, IterDupOfKey},
+        aliases::{
+            IterDupFixedRev, IterDupOfKeyRev, IterDupRangeRev, IterKeyVals, IterKeyValsRev,
+            IterRangeRev,
+        },
+        iter::{
+            FixedPage, Iter, IterBatched, IterDoubleEnded, IterDup, IterDupFixed,
+            IterDupFixedOfKey, IterDupFixedPages, IterDupOfKey, IterDupRange, IterRange, Stop,
+        },
         kind::WriteMarker,
+        predicate::{ScanContext, ScanControl, ScanStart, ScanTurn},
+        unbound::UnboundCursor,
     },
 };
 use ffi::{
@@ -17,7 +26,15 @@ use ffi::{
     MDBX_SEEK_AND_GET_MULTIPLE, MDBX_SET, MDBX_SET_KEY, MDBX_SET_LOWERBOUND, MDBX_SET_RANGE,
     MDBX_cursor_op,
 };
-use std::{ffi::c_void, fmt, marker::PhantomData, ptr};
+use std::{
+    cmp::Ordering,
+    ffi::{c_int, c_void},
+    fmt,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ops::{Bound, ControlFlow, RangeBounds},
+    ptr,
+};
 
 #[cfg(debug_assertions)]
 use crate::tx::assertions;
@@ -33,6 +50,7 @@ where
     K: TransactionKind,
 {
     access: &'tx K::Access,
+    registry: &'tx DbiRegistry,
     cursor: *mut ffi::MDBX_cursor,
     db: Database,
     _kind: PhantomData<K>,
@@ -43,23 +61,41 @@ where
     K: TransactionKind,
 {
     /// Creates a new cursor from a reference to a transaction access type.
-    pub(crate) fn new(access: &'tx K::Access, db: Database) -> MdbxResult<Self> {
+    ///
+    /// The cursor is registered with the environment's handle registry, so
+    /// that the database cannot be closed or dropped while it is open.
+    pub(crate) fn new(
+        access: &'tx K::Access,
+        registry: &'tx DbiRegistry,
+        db: Database,
+    ) -> MdbxResult<Self> {
+        registry.acquire_cursor(db)?;
         let mut cursor: *mut ffi::MDBX_cursor = ptr::null_mut();
-        access.with_txn_ptr(|txn_ptr| unsafe {
+        if let Err(e) = access.try_with_txn_ptr(|txn_ptr| unsafe {
             mdbx_result(ffi::mdbx_cursor_open(txn_ptr, db.dbi(), &mut cursor))
-        })?;
-        Ok(Self { access, cursor, db, _kind: PhantomData })
+        }) {
+            registry.release_cursor(db.dbi());
+            return Err(e);
+        }
+        Ok(Self { access, registry, cursor, db, _kind: PhantomData })
     }
 
     /// Helper function for `Clone`. This should only be invoked within
     /// a `with_txn_ptr` call to ensure safety.
     fn new_at_position(other: &Self) -> MdbxResult<Self> {
+        other.registry.acquire_cursor(other.db)?;
         unsafe {
             let cursor = ffi::mdbx_cursor_create(ptr::null_mut());
 
             let res = ffi::mdbx_cursor_copy(other.cursor(), cursor);
 
-            let s = Self { access: other.access, cursor, db: other.db, _kind: PhantomData };
+            let s = Self {
+                access: other.access,
+                registry: other.registry,
+                cursor,
+                db: other.db,
+                _kind: PhantomData,
+            };
 
             mdbx_result(res)?;
 
@@ -67,6 +103,48 @@ where
         }
     }
 
+    /// Binds an unbound cursor to a database, using `bind` to attach it to
+    /// the transaction.
+    ///
+    /// If binding fails, the cursor is closed.
+    pub(crate) fn from_unbound(
+        access: &'tx K::Access,
+        registry: &'tx DbiRegistry,
+        db: Database,
+        unbound: UnboundCursor,
+        bind: impl FnOnce(*mut ffi::MDBX_txn, *mut ffi::MDBX_cursor) -> c_int,
+    ) -> MdbxResult<Self> {
+        registry.acquire_cursor(db)?;
+        if let Err(e) =
+            access.try_with_txn_ptr(|txn_ptr| mdbx_result(bind(txn_ptr, unbound.as_ptr())))
+        {
+            registry.release_cursor(db.dbi());
+            return Err(e);
+        }
+        Ok(Self { access, registry, cursor: unbound.into_raw(), db, _kind: PhantomData })
+    }
+
+    /// Unbinds the cursor from its transaction, so that it can be bound to
+    /// another one with [`Tx::bind_cursor`] or [`Tx::renew_cursor`].
+    ///
+    /// If unbinding fails, the cursor is closed.
+    ///
+    /// [`Tx::bind_cursor`]: crate::tx::Tx::bind_cursor
+    /// [`Tx::renew_cursor`]: crate::tx::Tx::renew_cursor
+    pub fn unbind(self) -> MdbxResult<UnboundCursor> {
+        let this = ManuallyDrop::new(self);
+        let res = this.access.try_with_txn_ptr(|_| {
+            // SAFETY: cursor is valid within try_with_txn_ptr block
+            mdbx_result(unsafe { ffi::mdbx_cursor_unbind(this.cursor) })
+        });
+        if let Err(e) = res {
+            drop(ManuallyDrop::into_inner(this));
+            return Err(e);
+        }
+        this.registry.release_cursor(this.db.dbi());
+        Ok(UnboundCursor::from_raw(this.cursor, this.db))
+    }
+
     /// Returns a reference to the transaction access type.
     pub(crate) const fn access(&self) -> &'tx K::Access {
         self.access
@@ -90,6 +168,24 @@ where
         self.db.flags()
     }
 
+    /// Compares two keys in the order of the cursor's database.
+    pub(crate) fn cmp_keys(&self, a: &[u8], b: &[u8]) -> Ordering {
+        self.access.with_txn_ptr(|txn| {
+            // SAFETY: txn is valid within with_txn_ptr, and the cursor's
+            // database is open in it.
+            unsafe { crate::tx::ops::cmp_keys_raw(txn, self.db.dbi(), a, b) }
+        })
+    }
+
+    /// Compares two duplicate values in the order of the cursor's database.
+    pub(crate) fn cmp_values(&self, a: &[u8], b: &[u8]) -> Ordering {
+        self.access.with_txn_ptr(|txn| {
+            // SAFETY: txn is valid within with_txn_ptr, and the cursor's
+            // database is open in it.
+            unsafe { crate::tx::ops::cmp_values_raw(txn, self.db.dbi(), a, b) }
+        })
+    }
+
     /// Returns `true` if the cursor is at EOF or not positioned.
     ///
     /// This can be used to check if the cursor has valid data before
@@ -99,12 +195,144 @@ where
             == ffi::MDBX_RESULT_TRUE
     }
 
+    /// Returns `true` if the cursor is positioned on the first key of the
+    /// database.
+    ///
+    /// For [`DatabaseFlags::DUP_SORT`] databases, only the key is considered;
+    /// combine with [`Cursor::is_on_first_dup`] to check for the first value.
+    pub fn is_on_first(&self) -> bool {
+        self.access.with_txn_ptr(|_| unsafe { ffi::mdbx_cursor_on_first(self.cursor) })
+            == ffi::MDBX_RESULT_TRUE
+    }
+
+    /// Returns `true` if the cursor is positioned on the last key of the
+    /// database.
+    ///
+    /// For [`DatabaseFlags::DUP_SORT`] databases, only the key is considered;
+    /// combine with [`Cursor::is_on_last_dup`] to check for the last value.
+    pub fn is_on_last(&self) -> bool {
+        self.access.with_txn_ptr(|_| unsafe { ffi::mdbx_cursor_on_last(self.cursor) })
+            == ffi::MDBX_RESULT_TRUE
+    }
+
+    /// Returns `true` if the cursor is positioned on the first (or only)
+    /// value of the current key.
+    ///
+    /// For databases without [`DatabaseFlags::DUP_SORT`], this is always
+    /// `true`.
+    pub fn is_on_first_dup(&self) -> bool {
+        self.access.with_txn_ptr(|_| unsafe { ffi::mdbx_cursor_on_first_dup(self.cursor) })
+            == ffi::MDBX_RESULT_TRUE
+    }
+
+    /// Returns `true` if the cursor is positioned on the last (or only) value
+    /// of the current key.
+    ///
+    /// For databases without [`DatabaseFlags::DUP_SORT`], this is always
+    /// `true`.
+    pub fn is_on_last_dup(&self) -> bool {
+        self.access.with_txn_ptr(|_| unsafe { ffi::mdbx_cursor_on_last_dup(self.cursor) })
+            == ffi::MDBX_RESULT_TRUE
+    }
+
+    /// Compares the position of this cursor with the position of `other`.
+    ///
+    /// Positions are compared in database order, without reading keys or
+    /// values. If `ignore_multival` is `true`, cursors on different values of
+    /// the same key in a [`DatabaseFlags::DUP_SORT`] database compare equal.
+    ///
+    /// Both cursors should belong to the same transaction and database. Two
+    /// unpositioned cursors compare equal. Fails with
+    /// [`MdbxError::Incompatible`] if the positions cannot be compared, e.g.
+    /// if only one of the cursors is positioned, or the cursors belong to
+    /// different transactions or databases.
+    ///
+    /// [`MdbxError::Incompatible`]: crate::MdbxError::Incompatible
+    pub fn compare(&self, other: &Cursor<'tx, K>, ignore_multival: bool) -> MdbxResult<Ordering> {
+        self.access.try_with_txn_ptr(|_| {
+            // SAFETY: Both cursors are valid within try_with_txn_ptr.
+            let rc =
+                unsafe { ffi::mdbx_cursor_compare(self.cursor, other.cursor, ignore_multival) };
+            // Results beyond the i16 range mean the cursors are not
+            // comparable.
+            if rc.unsigned_abs() > i16::MAX as u32 {
+                return Err(crate::MdbxError::Incompatible);
+            }
+            Ok(rc.cmp(&0))
+        })
+    }
+
+    /// Estimates the number of items from the position of this cursor to the
+    /// position of `other`.
+    ///
+    /// The result is negative if `other` is positioned before this cursor.
+    /// Both cursors must be positioned, and belong to the same transaction
+    /// and database. See [`Tx::estimate_range`] for the accuracy of
+    /// estimates.
+    ///
+    /// [`Tx::estimate_range`]: crate::tx::Tx::estimate_range
+    pub fn estimate_distance(&self, other: &Cursor<'tx, K>) -> MdbxResult<isize> {
+        self.access.try_with_txn_ptr(|_| {
+            let mut distance: isize = 0;
+            // SAFETY: Both cursors are valid within try_with_txn_ptr.
+            mdbx_result(unsafe {
+                ffi::mdbx_estimate_distance(self.cursor, other.cursor, &mut distance)
+            })?;
+            Ok(distance)
+        })
+    }
+
+    /// Estimates the number of items the cursor would move over by
+    /// performing `op`, without moving it.
+    ///
+    /// `key` is the key to seek for operations such as [`MDBX_SET_RANGE`],
+    /// and is ignored by relative operations such as [`MDBX_NEXT`]. The
+    /// cursor must be positioned. Operations that also seek a value, such as
+    /// [`MDBX_GET_BOTH`], are not supported and return an error.
+    pub fn estimate_move(&self, op: MDBX_cursor_op, key: Option<&[u8]>) -> MdbxResult<isize> {
+        let mut key_val = key.map(|key| slice_to_val(Some(key)));
+        let mut data_val = slice_to_val(None);
+        let data_ptr: *mut ffi::MDBX_val = match op {
+            MDBX_GET_BOTH | MDBX_GET_BOTH_RANGE => ptr::null_mut(),
+            _ => &mut data_val,
+        };
+        self.access.try_with_txn_ptr(|_| {
+            let mut distance: isize = 0;
+            // SAFETY: cursor is valid within try_with_txn_ptr, and the key
+            // outlives the call.
+            mdbx_result(unsafe {
+                ffi::mdbx_estimate_move(
+                    self.cursor,
+                    key_val.as_mut().map_or(ptr::null_mut(), ptr::from_mut),
+                    data_ptr,
+                    op,
+                    &mut distance,
+                )
+            })?;
+            Ok(distance)
+        })
+    }
+
+    /// Resets the cursor, leaving it unpositioned.
+    ///
+    /// After a reset, relative operations such as [`Cursor::next`] and
+    /// [`Cursor::get_current`] find nothing until the cursor is positioned
+    /// again with an absolute operation such as [`Cursor::first`] or
+    /// [`Cursor::set`].
+    pub fn reset(&mut self) -> MdbxResult<()> {
+        self.access.try_with_txn_ptr(|_| {
+            // SAFETY: cursor is valid within try_with_txn_ptr block
+            mdbx_result(unsafe { ffi::mdbx_cursor_reset(self.cursor) })?;
+            Ok(())
+        })
+    }
+
     /// Returns the count of duplicate values for the current key.
     ///
     /// For databases without `DUP_SORT`, this always returns 1.
     /// The cursor must be positioned at a valid key.
     pub fn dup_count(&self) -> MdbxResult<usize> {
-        self.access.with_txn_ptr(|_| {
+        self.access.try_with_txn_ptr(|_| {
             // SAFETY: cursor is valid within with_txn_ptr block
             unsafe { crate::tx::ops::cursor_dup_count(self.cursor) }
         })
@@ -127,7 +355,7 @@ where
         let key_ptr = key_val.iov_base;
         let data_ptr = data_val.iov_base;
 
-        self.access.with_txn_ptr(|txn| {
+        self.access.try_with_txn_ptr(|txn| {
             // SAFETY:
             // The cursor is valid as long as self is alive.
             // The transaction is also valid as long as self is alive.
@@ -400,7 +628,14 @@ where
     {
         #[cfg(debug_assertions)]
         assertions::debug_assert_dup_fixed(self.db_flags());
-        self.get_full(None, None, MDBX_PREV_MULTIPLE)
+        // MDBX_PREV_MULTIPLE does not return the key.
+        let Some(value) = self.get_value(None, None, MDBX_PREV_MULTIPLE)? else {
+            return Ok(None);
+        };
+        let Some((key, ())) = self.get_current()? else {
+            return Ok(None);
+        };
+        Ok(Some((key, value)))
     }
 
     /// Position at first key-value pair greater than or equal to specified, return both key and
@@ -427,6 +662,178 @@ where
         Ok(Some((found, k.unwrap(), v)))
     }
 
+    /// Finds the first item satisfying a predicate, evaluating it inside the
+    /// libmdbx scan loop.
+    ///
+    /// The cursor is positioned by `start`, then moved by `turn` until the
+    /// predicate returns [`ScanControl::Stop`] or the end of the data is
+    /// reached. Returns `true` if the predicate stopped the scan, leaving the
+    /// cursor on the matching item for [`Cursor::get_current`].
+    ///
+    /// Items are passed to the predicate as raw bytes, without decoding. A
+    /// panic in the predicate ends the scan and is resumed by this method.
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, ScanControl, ScanStart, ScanTurn, WriteFlags};
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let env = Environment::builder().open(dir.path()).unwrap();
+    /// # let txn = env.begin_rw_sync().unwrap();
+    /// # let db = txn.open_db(None).unwrap();
+    /// # txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+    /// # txn.put(db, b"b", b"22", WriteFlags::empty()).unwrap();
+    /// let mut cursor = txn.cursor(db).unwrap();
+    /// let found = cursor
+    ///     .scan(ScanStart::First, ScanTurn::Next, |_, value| {
+    ///         if value.len() > 1 { ScanControl::Stop } else { ScanControl::Continue }
+    ///     })
+    ///     .unwrap();
+    /// assert!(found);
+    /// assert_eq!(cursor.get_current().unwrap(), Some((b"b".to_vec(), b"22".to_vec())));
+    /// ```
+    pub fn scan<F>(&mut self, start: ScanStart, turn: ScanTurn, predicate: F) -> MdbxResult<bool>
+    where
+        F: FnMut(&[u8], &[u8]) -> ScanControl,
+    {
+        let mut ctx = ScanContext::new(predicate);
+        let rc = self.access.with_txn_ptr(|_| {
+            // SAFETY: The cursor is valid within with_txn_ptr, and ctx
+            // outlives the call.
+            unsafe {
+                ffi::mdbx_cursor_scan(
+                    self.cursor,
+                    ctx.func(),
+                    (&raw mut ctx).cast(),
+                    start.op(),
+                    turn.op(),
+                    ptr::null_mut(),
+                )
+            }
+        });
+        scan_result(ctx.finish(rc))
+    }
+
+    /// Finds the first item satisfying a predicate, starting from `key`.
+    ///
+    /// The scan starts at the first key at or after `key` when `turn` moves
+    /// forward, and at the last key at or before `key` when it moves
+    /// backward. See [`Cursor::scan`].
+    pub fn scan_from<F>(&mut self, key: &[u8], turn: ScanTurn, predicate: F) -> MdbxResult<bool>
+    where
+        F: FnMut(&[u8], &[u8]) -> ScanControl,
+    {
+        #[cfg(debug_assertions)]
+        assertions::debug_assert_integer_key(self.db_flags(), key);
+
+        let mut key_val = slice_to_val(Some(key));
+        let mut ctx = ScanContext::new(predicate);
+        let rc = self.access.with_txn_ptr(|_| {
+            // SAFETY: The cursor is valid within with_txn_ptr, and ctx and
+            // key outlive the call.
+            unsafe {
+                ffi::mdbx_cursor_scan_from(
+                    self.cursor,
+                    ctx.func(),
+                    (&raw mut ctx).cast(),
+                    turn.seek_op(),
+                    &mut key_val,
+                    ptr::null_mut(),
+                    turn.op(),
+                    ptr::null_mut(),
+                )
+            }
+        });
+        scan_result(ctx.finish(rc))
+    }
+
+    /// Calls `f` on each item, from the item selected by `start` to the end
+    /// of the database, until it returns [`ControlFlow::Break`].
+    ///
+    /// The slices passed to `f` point directly into the database and are
+    /// only valid for the duration of the call. Unlike iterators, this never
+    /// checks whether pages are dirty, nor copies items from them.
+    ///
+    /// Returns the value `f` broke with, leaving the cursor on that item, or
+    /// [`ControlFlow::Continue`] if the end of the database was reached. A
+    /// panic in `f` ends the iteration and is resumed by this method.
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, ScanStart, WriteFlags};
+    /// # use std::ops::ControlFlow;
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let env = Environment::builder().open(dir.path()).unwrap();
+    /// # let txn = env.begin_rw_sync().unwrap();
+    /// # let db = txn.open_db(None).unwrap();
+    /// # txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+    /// # txn.put(db, b"b", b"22", WriteFlags::empty()).unwrap();
+    /// let mut cursor = txn.cursor(db).unwrap();
+    /// let mut total = 0;
+    /// let flow = cursor.for_each_raw(ScanStart::First, |_, value| {
+    ///     total += value.len();
+    ///     ControlFlow::<()>::Continue(())
+    /// });
+    /// assert_eq!(flow, Ok(ControlFlow::Continue(())));
+    /// assert_eq!(total, 3);
+    /// ```
+    pub fn for_each_raw<B, F>(&mut self, start: ScanStart, mut f: F) -> MdbxResult<ControlFlow<B>>
+    where
+        F: FnMut(&[u8], &[u8]) -> ControlFlow<B>,
+    {
+        let mut output = None;
+        self.scan(start, ScanTurn::Next, |key, value| match f(key, value) {
+            ControlFlow::Continue(()) => ScanControl::Continue,
+            ControlFlow::Break(b) => {
+                output = Some(b);
+                ScanControl::Stop
+            }
+        })?;
+        Ok(output.map_or(ControlFlow::Continue(()), ControlFlow::Break))
+    }
+
+    /// Calls `f` on each decoded item, from the item selected by `start` to
+    /// the end of the database, until it returns [`ControlFlow::Break`].
+    ///
+    /// Keys and values are decoded with [`TableObjectRef`], so `[u8]` passes
+    /// them as slices that are only valid for the duration of the call. See
+    /// [`Cursor::for_each_raw`]. A decoding error ends the iteration, leaving
+    /// the cursor on the item that failed to decode.
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, ScanStart, WriteFlags};
+    /// # use std::ops::ControlFlow;
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let env = Environment::builder().open(dir.path()).unwrap();
+    /// # let txn = env.begin_rw_sync().unwrap();
+    /// # let db = txn.open_db(None).unwrap();
+    /// # txn.put(db, b"a", 1u32.to_be_bytes(), WriteFlags::empty()).unwrap();
+    /// # txn.put(db, b"b", 2u32.to_be_bytes(), WriteFlags::empty()).unwrap();
+    /// let mut cursor = txn.cursor(db).unwrap();
+    /// let found = cursor.for_each::<[u8], [u8; 4], _, _>(ScanStart::First, |key, value| {
+    ///     if u32::from_be_bytes(value) == 2 { ControlFlow::Break(key.to_vec()) } else { ControlFlow::Continue(()) }
+    /// });
+    /// assert_eq!(found.unwrap(), ControlFlow::Break(b"b".to_vec()));
+    /// ```
+    pub fn for_each<Key, Value, B, F>(
+        &mut self,
+        start: ScanStart,
+        mut f: F,
+    ) -> ReadResult<ControlFlow<B>>
+    where
+        Key: TableObjectRef + ?Sized,
+        Value: TableObjectRef + ?Sized,
+        F: for<'a> FnMut(Key::Ref<'a>, Value::Ref<'a>) -> ControlFlow<B>,
+    {
+        let flow = self.for_each_raw(start, |key, value| {
+            match Key::decode_ref(key).and_then(|key| Ok((key, Value::decode_ref(value)?))) {
+                Ok((key, value)) => f(key, value).map_break(Ok),
+                Err(err) => ControlFlow::Break(Err(err)),
+            }
+        })?;
+        match flow {
+            ControlFlow::Continue(()) => Ok(ControlFlow::Continue(())),
+            ControlFlow::Break(output) => output.map(ControlFlow::Break),
+        }
+    }
+
     /// Returns an iterator over database items.
     ///
     /// The iterator will begin with item next after the cursor, and continue
@@ -508,6 +915,238 @@ where
         Ok(Iter::new_with(self, first))
     }
 
+    /// Iterate over database items in descending order, starting from the end
+    /// of the database.
+    ///
+    /// For databases with duplicate data items ([`DatabaseFlags::DUP_SORT`]),
+    /// the duplicate data items of each key will be returned in descending
+    /// order before moving on to the previous key.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use signet_libmdbx::Environment;
+    /// # use std::path::Path;
+    /// # let env = Environment::builder().open(Path::new("/tmp/ex")).unwrap();
+    /// let txn = env.begin_ro_sync().unwrap();
+    /// let db = txn.open_db(None).unwrap();
+    /// let mut cursor = txn.cursor(db).unwrap();
+    ///
+    /// // The 10 latest entries.
+    /// let latest = cursor
+    ///     .iter_rev_end::<Vec<u8>, Vec<u8>>()
+    ///     .unwrap()
+    ///     .take(10)
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// ```
+    pub fn iter_rev_end<'cur, Key, Value>(
+        &'cur mut self,
+    ) -> ReadResult<IterKeyValsRev<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        let Some(last) = self.last()? else {
+            return Ok(Iter::new_end(self));
+        };
+
+        Ok(Iter::new_with(self, last))
+    }
+
+    /// Iterate over database items in descending order, starting from the
+    /// given key or, if it is absent, the greatest key less than it.
+    ///
+    /// For databases with duplicate data items ([`DatabaseFlags::DUP_SORT`]),
+    /// iteration starts from the last duplicate of the starting key.
+    pub fn iter_rev_from<'cur, Key, Value>(
+        &'cur mut self,
+        key: &[u8],
+    ) -> ReadResult<IterKeyValsRev<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        let found = self.set_lowerbound::<std::borrow::Cow<'tx, [u8]>, ()>(key)?;
+        let first = match found {
+            Some((_, found, ())) if *found == *key => {
+                if self.db_flags().contains(DatabaseFlags::DUP_SORT) {
+                    self.last_dup::<()>()?;
+                }
+                self.get_current()?
+            }
+            // Positioned on a greater key.
+            Some(_) => self.prev()?,
+            // Every key is less than the given key.
+            None => self.last()?,
+        };
+        let Some(first) = first else {
+            return Ok(Iter::new_end(self));
+        };
+
+        Ok(Iter::new_with(self, first))
+    }
+
+    /// Iterate over the database items whose keys are within `range`.
+    ///
+    /// Keys are compared with the table's comparator, so the range follows
+    /// the order of [`DatabaseFlags::REVERSE_KEY`] and
+    /// [`DatabaseFlags::INTEGER_KEY`] tables. For databases with duplicate
+    /// data items ([`DatabaseFlags::DUP_SORT`]), every duplicate of each key
+    /// in the range is yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, WriteFlags};
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let env = Environment::builder().open(dir.path()).unwrap();
+    /// # let txn = env.begin_rw_sync().unwrap();
+    /// # let db = txn.open_db(None).unwrap();
+    /// for key in [b"a", b"b", b"c", b"d"] {
+    ///     txn.put(db, key, b"", WriteFlags::empty()).unwrap();
+    /// }
+    /// let mut cursor = txn.cursor(db).unwrap();
+    /// let keys = cursor
+    ///     .range::<Vec<u8>, ()>(&b"b"[..]..=&b"c"[..])
+    ///     .unwrap()
+    ///     .map(|item| item.map(|(key, ())| key))
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap();
+    /// assert_eq!(keys, [b"b", b"c"]);
+    /// ```
+    pub fn range<'cur, 'r, Key, Value>(
+        &'cur mut self,
+        range: impl RangeBounds<&'r [u8]>,
+    ) -> ReadResult<IterRange<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        let (start, end) = self.range_bounds(&range);
+        IterRange::new(self, start, Stop::Bound(end.map(<[u8]>::to_vec)))
+    }
+
+    /// Iterate over the database items whose keys are within `range`, in
+    /// descending order. See [`Self::range`].
+    pub fn range_rev<'cur, 'r, Key, Value>(
+        &'cur mut self,
+        range: impl RangeBounds<&'r [u8]>,
+    ) -> ReadResult<IterRangeRev<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        let (start, end) = self.range_bounds(&range);
+        IterRange::new(self, end, Stop::Bound(start.map(<[u8]>::to_vec)))
+    }
+
+    /// Iterate over the database items whose keys start with `prefix`,
+    /// stopping at the first key that does not.
+    ///
+    /// In [`DatabaseFlags::REVERSE_KEY`] tables, keys are ordered by their
+    /// last bytes, so this matches keys ending with `prefix` instead. Prefixes
+    /// are not meaningful for [`DatabaseFlags::INTEGER_KEY`] tables, see
+    /// [`Self::range`].
+    pub fn prefix<'cur, Key, Value>(
+        &'cur mut self,
+        prefix: &[u8],
+    ) -> ReadResult<IterRange<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        let suffix = self.db_flags().contains(DatabaseFlags::REVERSE_KEY);
+        let stop = Stop::Prefix { prefix: prefix.to_vec(), suffix };
+        IterRange::new(self, Bound::Included(prefix), stop)
+    }
+
+    /// Returns the bounds of a key range, checking them in debug builds.
+    fn range_bounds<'r>(
+        &self,
+        range: &impl RangeBounds<&'r [u8]>,
+    ) -> (Bound<&'r [u8]>, Bound<&'r [u8]>) {
+        let start = range.start_bound().map(|key| *key);
+        let end = range.end_bound().map(|key| *key);
+        #[cfg(debug_assertions)]
+        for key in [start, end] {
+            if let Bound::Included(key) | Bound::Excluded(key) = key {
+                assertions::debug_assert_integer_key(self.db_flags(), key);
+            }
+        }
+        (start, end)
+    }
+
+    /// Iterate over database items from both ends, using `back` as the
+    /// cursor moving backward from the end of the database.
+    ///
+    /// Returns [`MdbxError::Incompatible`] if `back` belongs to a different
+    /// transaction or database. See [`IterDoubleEnded`].
+    ///
+    /// [`MdbxError::Incompatible`]: crate::MdbxError::Incompatible
+    pub fn iter_double_ended<'cur, Key, Value>(
+        &'cur mut self,
+        back: &'cur mut Cursor<'tx, K>,
+    ) -> ReadResult<IterDoubleEnded<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        if !ptr::eq(self.access, back.access) || self.db.dbi() != back.db.dbi() {
+            return Err(crate::MdbxError::Incompatible.into());
+        }
+
+        if self.first::<(), ()>()?.is_none() || back.last::<(), ()>()?.is_none() {
+            return Ok(IterDoubleEnded::new_end(self, back));
+        }
+
+        Ok(IterDoubleEnded::new(self, back))
+    }
+
+    /// Iterate over database items starting from the beginning of the
+    /// database, fetching up to `batch_size` items per FFI call.
+    ///
+    /// `batch_size` is raised to at least 2. The database must not have
+    /// [`DatabaseFlags::DUP_SORT`] set, see [`IterBatched`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use signet_libmdbx::Environment;
+    /// # use std::path::Path;
+    /// # let env = Environment::builder().open(Path::new("/tmp/ex")).unwrap();
+    /// let txn = env.begin_ro_sync().unwrap();
+    /// let db = txn.open_db(None).unwrap();
+    /// let mut cursor = txn.cursor(db).unwrap();
+    ///
+    /// for result in cursor.iter_batched::<Vec<u8>, Vec<u8>>(256).unwrap() {
+    ///     let (key, value) = result.unwrap();
+    ///     println!("{:?} => {:?}", key, value);
+    /// }
+    /// ```
+    pub fn iter_batched<'cur, Key, Value>(
+        &'cur mut self,
+        batch_size: usize,
+    ) -> ReadResult<IterBatched<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        Value: TableObject<'tx>,
+    {
+        // Batches are fetched from the item the cursor is positioned on.
+        if self.first::<(), ()>()?.is_none() {
+            return Ok(IterBatched::new_end(self, batch_size));
+        }
+
+        Ok(IterBatched::new(self, batch_size))
+    }
+
     /// Iterate over duplicate database items.
     ///
     /// The iterator will produce an iterator for each key in the database,
@@ -589,6 +1228,88 @@ where
         Ok(IterDupOfKey::new_with(self, value))
     }
 
+    /// [`DatabaseFlags::DUP_SORT`]-only: Iterate over the duplicates of the
+    /// item with the given key whose values are within `range`.
+    ///
+    /// The cursor is positioned with `MDBX_GET_BOTH_RANGE`, and values are
+    /// compared with the table's value comparator, so the range follows the
+    /// order of [`DatabaseFlags::REVERSE_DUP`] and
+    /// [`DatabaseFlags::INTEGER_DUP`] tables.
+    pub fn dup_range<'cur, 'r, Value>(
+        &'cur mut self,
+        key: &[u8],
+        range: impl RangeBounds<&'r [u8]>,
+    ) -> ReadResult<IterDupRange<'tx, 'cur, K, Value>>
+    where
+        'tx: 'cur,
+        Value: TableObject<'tx>,
+    {
+        #[cfg(debug_assertions)]
+        assertions::debug_assert_integer_key(self.db_flags(), key);
+        let (start, end) = self.dup_range_bounds(&range);
+        IterDupRange::new(self, key, start, end.map(<[u8]>::to_vec))
+    }
+
+    /// [`DatabaseFlags::DUP_SORT`]-only: Iterate over the duplicates of the
+    /// item with the given key whose values are within `range`, in descending
+    /// order. See [`Self::dup_range`].
+    pub fn dup_range_rev<'cur, 'r, Value>(
+        &'cur mut self,
+        key: &[u8],
+        range: impl RangeBounds<&'r [u8]>,
+    ) -> ReadResult<IterDupRangeRev<'tx, 'cur, K, Value>>
+    where
+        'tx: 'cur,
+        Value: TableObject<'tx>,
+    {
+        #[cfg(debug_assertions)]
+        assertions::debug_assert_integer_key(self.db_flags(), key);
+        let (start, end) = self.dup_range_bounds(&range);
+        IterDupRange::new(self, key, end, start.map(<[u8]>::to_vec))
+    }
+
+    /// Returns the bounds of a value range, checking them in debug builds.
+    fn dup_range_bounds<'r>(
+        &self,
+        range: &impl RangeBounds<&'r [u8]>,
+    ) -> (Bound<&'r [u8]>, Bound<&'r [u8]>) {
+        let start = range.start_bound().map(|value| *value);
+        let end = range.end_bound().map(|value| *value);
+        #[cfg(debug_assertions)]
+        {
+            assertions::debug_assert_dup_sort(self.db_flags());
+            for value in [start, end] {
+                if let Bound::Included(value) | Bound::Excluded(value) = value {
+                    assertions::debug_assert_integer_dup(self.db_flags(), value);
+                }
+            }
+        }
+        (start, end)
+    }
+
+    /// [`DatabaseFlags::DUP_SORT`]-only: Iterate over the duplicates of the
+    /// item in the database with the given key, in descending order.
+    ///
+    /// This iterator yields just the values for the specified key, starting
+    /// from the last. When all values are exhausted, iteration stops.
+    pub fn iter_dup_rev_of<'cur, Value>(
+        &'cur mut self,
+        key: &[u8],
+    ) -> ReadResult<IterDupOfKeyRev<'tx, 'cur, K, Value>>
+    where
+        'tx: 'cur,
+        Value: TableObject<'tx>,
+    {
+        if self.set::<()>(key)?.is_none() {
+            return Ok(IterDupOfKey::new_end(self));
+        }
+        let Some(value) = self.last_dup::<Value>()? else {
+            return Ok(IterDupOfKey::new_end(self));
+        };
+
+        Ok(IterDupOfKey::new_with(self, value))
+    }
+
     /// [`DatabaseFlags::DUP_FIXED`]-only: Iterate over all fixed-size duplicate
     /// values starting from the beginning of the database.
     ///
@@ -651,6 +1372,66 @@ where
         Ok(IterDupFixed::new_with(self, key, page, value_size))
     }
 
+    /// [`DatabaseFlags::DUP_FIXED`]-only: Iterate over the pages of
+    /// fixed-size duplicate values, as slices of `T`.
+    ///
+    /// Each page is yielded whole, with the key its values belong to, so a
+    /// table of `u64` or `[u8; 32]` values can be processed a page at a time.
+    /// Pages are borrowed in place when they are clean and aligned for `T`,
+    /// and copied otherwise. See [`IterDupFixedPages`].
+    ///
+    /// Fails with [`MdbxError::DecodeErrorLenDiff`] if the values of the table
+    /// are not the size of `T`.
+    ///
+    /// ```
+    /// # use signet_libmdbx::{Environment, DatabaseFlags, WriteFlags};
+    /// # let dir = tempfile::tempdir().unwrap();
+    /// # let env = Environment::builder().open(dir.path()).unwrap();
+    /// let txn = env.begin_rw_sync().unwrap();
+    /// let flags = DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED | DatabaseFlags::INTEGER_DUP;
+    /// let db = txn.create_db(None, flags).unwrap();
+    /// for value in 0..10u64 {
+    ///     txn.put(db, b"key", value.to_ne_bytes(), WriteFlags::empty()).unwrap();
+    /// }
+    ///
+    /// let mut cursor = txn.cursor(db).unwrap();
+    /// let mut total = 0;
+    /// for page in cursor.dupfixed_pages::<Vec<u8>, u64>().unwrap() {
+    ///     let (_key, values) = page.unwrap();
+    ///     total += values.iter().sum::<u64>();
+    /// }
+    /// assert_eq!(total, 45);
+    /// ```
+    ///
+    /// [`MdbxError::DecodeErrorLenDiff`]: crate::MdbxError::DecodeErrorLenDiff
+    pub fn dupfixed_pages<'cur, Key, T>(
+        &'cur mut self,
+    ) -> ReadResult<IterDupFixedPages<'tx, 'cur, K, Key, T>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx>,
+        T: FixedValue,
+    {
+        #[cfg(debug_assertions)]
+        assertions::debug_assert_dup_fixed(self.db_flags());
+
+        let Some(((), ObjectLength(value_size))) = self.first::<(), ObjectLength>()? else {
+            return Ok(IterDupFixedPages::new_end(self));
+        };
+        if value_size != size_of::<T>() {
+            return Err(crate::MdbxError::DecodeErrorLenDiff.into());
+        }
+
+        let Some(FixedPage(page)) = self.get_multiple::<FixedPage<'tx, T>>()? else {
+            return Ok(IterDupFixedPages::new_end(self));
+        };
+        // Re-fetch the key since get_multiple doesn't return it
+        let Some((key, ())) = self.get_current::<Key, ()>()? else {
+            return Ok(IterDupFixedPages::new_end(self));
+        };
+        Ok(IterDupFixedPages::new_with(self, key, page))
+    }
+
     /// [`DatabaseFlags::DUP_FIXED`]-only: Iterate over all fixed-size duplicate
     /// values starting from the given key or the first key greater than it.
     ///
@@ -708,6 +1489,43 @@ where
         Ok(IterDupFixed::new_with(self, found_key, page, value_size))
     }
 
+    /// [`DatabaseFlags::DUP_FIXED`]-only: Iterate over all fixed-size duplicate
+    /// values in descending order, starting from the end of the database.
+    ///
+    /// Keys and their values are both yielded in descending order, fetching
+    /// pages of values with `MDBX_PREV_MULTIPLE`. As with
+    /// [`Self::iter_dupfixed_start`], the first value yielded for each key is
+    /// a [`DupItem::NewKey`].
+    ///
+    /// [`DupItem::NewKey`]: crate::DupItem::NewKey
+    pub fn iter_dupfixed_rev<'cur, Key, Value>(
+        &'cur mut self,
+    ) -> ReadResult<IterDupFixedRev<'tx, 'cur, K, Key, Value>>
+    where
+        'tx: 'cur,
+        Key: TableObject<'tx> + Clone,
+        Value: TableObjectOwned,
+    {
+        #[cfg(debug_assertions)]
+        assertions::debug_assert_dup_fixed(self.db_flags());
+
+        // Position at the last value of the last key
+        let Some((key, ObjectLength(value_size))) = self.last::<Key, ObjectLength>()? else {
+            return Ok(IterDupFixed::new_end(self));
+        };
+
+        if value_size == 0 {
+            return Ok(IterDupFixed::new_end(self));
+        }
+
+        // Get the last page of values, which holds the cursor's value
+        let Some(page) = self.get_multiple::<std::borrow::Cow<'tx, [u8]>>()? else {
+            return Ok(IterDupFixed::new_end(self));
+        };
+
+        Ok(IterDupFixed::new_with(self, key, page, value_size))
+    }
+
     /// [`DatabaseFlags::DUP_FIXED`]-only: Iterate over all fixed-size duplicate
     /// values for a specific key.
     ///
@@ -795,18 +1613,61 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
             ffi::MDBX_val { iov_len: key.len(), iov_base: key.as_ptr() as *mut c_void };
         let mut data_val: ffi::MDBX_val =
             ffi::MDBX_val { iov_len: data.len(), iov_base: data.as_ptr() as *mut c_void };
-        mdbx_result(self.access.with_txn_ptr(|_| unsafe {
-            ffi::mdbx_cursor_put(self.cursor, &key_val, &mut data_val, flags.bits())
-        }))
-        .map(drop)
+        self.access.try_with_txn_ptr(|txn_ptr| {
+            let recorder = self.access.changes().recorder();
+            // SAFETY: txn_ptr is valid from with_txn_ptr.
+            let old_value =
+                recorder.map(|r| unsafe { r.old_value(txn_ptr, self.db, key) }).transpose()?;
+            mdbx_result(unsafe {
+                ffi::mdbx_cursor_put(self.cursor, &key_val, &mut data_val, flags.bits())
+            })?;
+            if let Some(recorder) = recorder {
+                if flags.contains(WriteFlags::ALLDUPS) {
+                    recorder.push(self.db, ChangeOp::Del, key, None, None);
+                }
+                recorder.push(self.db, ChangeOp::Put, key, Some(data), old_value.flatten());
+            }
+            Ok(())
+        })
     }
 
     fn del_inner(&mut self, flags: WriteFlags) -> MdbxResult<()> {
-        mdbx_result(
-            self.access
-                .with_txn_ptr(|_| unsafe { ffi::mdbx_cursor_del(self.cursor, flags.bits()) }),
-        )
-        .map(drop)
+        self.access.try_with_txn_ptr(|txn_ptr| {
+            let Some(recorder) = self.access.changes().recorder() else {
+                return mdbx_result(unsafe { ffi::mdbx_cursor_del(self.cursor, flags.bits()) })
+                    .map(drop);
+            };
+
+            // Read the current item before it is deleted.
+            let mut key_val = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
+            let mut data_val = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
+            // SAFETY: cursor and txn_ptr are valid. The key and value are
+            // copied before the transaction is modified.
+            let (key, value) = unsafe {
+                mdbx_result(ffi::mdbx_cursor_get(
+                    self.cursor,
+                    &mut key_val,
+                    &mut data_val,
+                    MDBX_GET_CURRENT,
+                ))?;
+                (
+                    std::slice::from_raw_parts(key_val.iov_base as *const u8, key_val.iov_len)
+                        .to_vec(),
+                    std::slice::from_raw_parts(data_val.iov_base as *const u8, data_val.iov_len)
+                        .to_vec(),
+                )
+            };
+            // SAFETY: txn_ptr is valid from with_txn_ptr.
+            let old_value = unsafe { recorder.old_value(txn_ptr, self.db, &key)? };
+
+            mdbx_result(unsafe { ffi::mdbx_cursor_del(self.cursor, flags.bits()) })?;
+
+            let single_dup = self.db.flags().contains(DatabaseFlags::DUP_SORT)
+                && !flags.contains(WriteFlags::ALLDUPS);
+            let value = single_dup.then_some(value.as_slice());
+            recorder.push(self.db, ChangeOp::Del, &key, value, old_value);
+            Ok(())
+        })
     }
 
     /// Deletes the current key/data pair.
@@ -840,11 +1701,8 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
         #[cfg(debug_assertions)]
         assertions::debug_assert_dup_sort(self.db_flags());
 
-        // Position at the key. Convert the error to MdbxResult.
-        let found = self.set::<()>(key).map_err(|e| match e {
-            ReadError::Mdbx(e) => e,
-            _ => unreachable!("() can always be decoded"),
-        })?;
+        // Position at the key.
+        let found = self.set::<()>(key).into_mdbx()?;
 
         if found.is_none() {
             // Key not found, nothing to delete
@@ -855,6 +1713,116 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
         self.del_inner(WriteFlags::ALLDUPS)
     }
 
+    /// Deletes items from the current position onward, while `predicate`
+    /// returns `true` for their key and value.
+    ///
+    /// Deletion stops at the first item the predicate rejects, leaving the
+    /// cursor positioned on it, or at the end of the database. If the cursor
+    /// is not positioned on an item, nothing is deleted.
+    ///
+    /// In a [`DatabaseFlags::DUP_SORT`] database, the predicate is called
+    /// once per key with its first value, and each accepted key is removed
+    /// with all of its values in a single [`WriteFlags::ALLDUPS`] delete.
+    ///
+    /// Returns the number of key/value pairs deleted, counting every value
+    /// of a deleted DUPSORT key.
+    pub fn delete_while<F>(&mut self, mut predicate: F) -> MdbxResult<usize>
+    where
+        F: FnMut(&[u8], &[u8]) -> bool,
+    {
+        self.delete_while_raw(|_, key, value| predicate(key, value))
+    }
+
+    /// Deletes the items within a key range.
+    ///
+    /// The bounds are compared with the table's key order. See
+    /// [`Tx::delete_range`].
+    ///
+    /// [`Tx::delete_range`]: crate::tx::Tx::delete_range
+    pub(crate) fn delete_range<'r>(
+        &mut self,
+        range: impl RangeBounds<&'r [u8]>,
+    ) -> MdbxResult<usize> {
+        let (start, end) = self.range_bounds(&range);
+
+        // Position at the first item of the range. Convert the error to
+        // MdbxResult.
+        let found = match start {
+            Bound::Unbounded => self.first::<(), ()>().map(|found| found.is_some()),
+            Bound::Included(key) => self.set_range::<(), ()>(key).map(|found| found.is_some()),
+            Bound::Excluded(key) => match self.set_range::<Vec<u8>, ()>(key) {
+                Ok(Some((found, ()))) if found == key => {
+                    self.next_nodup::<(), ()>().map(|found| found.is_some())
+                }
+                found => found.map(|found| found.is_some()),
+            },
+        }
+        .into_mdbx()?;
+
+        if !found {
+            return Ok(0);
+        }
+
+        let stop = Stop::Bound(end.map(<[u8]>::to_vec));
+        let dbi = self.db.dbi();
+        self.delete_while_raw(|txn, key, _| {
+            // SAFETY: txn is the live transaction of the table dbi, and key
+            // points into it.
+            unsafe { stop.contains(ffi::mdbx_cmp, txn, dbi, &slice_to_val(Some(key)), false) }
+        })
+    }
+
+    /// Deletes items from the current position onward, while `predicate`
+    /// returns `true`. The predicate also receives the transaction pointer.
+    fn delete_while_raw<F>(&mut self, mut predicate: F) -> MdbxResult<usize>
+    where
+        F: FnMut(*mut ffi::MDBX_txn, &[u8], &[u8]) -> bool,
+    {
+        let dup_sort = self.db.flags().contains(DatabaseFlags::DUP_SORT);
+        let flags = if dup_sort { WriteFlags::ALLDUPS } else { WriteFlags::CURRENT };
+        let mut deleted = 0;
+        loop {
+            let accepted = self.access.try_with_txn_ptr(|txn| {
+                // SAFETY: cursor and txn are valid within try_with_txn_ptr.
+                // Deleting the last item leaves the cursor at the end, where
+                // MDBX_GET_CURRENT would return the item before it.
+                if unsafe { ffi::mdbx_cursor_eof(self.cursor) } == ffi::MDBX_RESULT_TRUE {
+                    return Ok(false);
+                }
+                let mut key_val = slice_to_val(None);
+                let mut data_val = slice_to_val(None);
+                // SAFETY: cursor and txn are valid within try_with_txn_ptr.
+                // After a delete, MDBX_GET_CURRENT moves to the next item.
+                let rc = unsafe {
+                    ffi::mdbx_cursor_get(self.cursor, &mut key_val, &mut data_val, MDBX_GET_CURRENT)
+                };
+                match rc {
+                    ffi::MDBX_SUCCESS => {}
+                    ffi::MDBX_NOTFOUND | ffi::MDBX_ENODATA => return Ok(false),
+                    other => return Err(crate::MdbxError::from_err_code(other)),
+                }
+                // SAFETY: MDBX returned valid pointers, which stay valid until
+                // the transaction is modified below.
+                let (key, value) = unsafe {
+                    (
+                        std::slice::from_raw_parts(key_val.iov_base as *const u8, key_val.iov_len),
+                        std::slice::from_raw_parts(
+                            data_val.iov_base as *const u8,
+                            data_val.iov_len,
+                        ),
+                    )
+                };
+                Ok(predicate(txn, key, value))
+            })?;
+            if !accepted {
+                return Ok(deleted);
+            }
+
+            deleted += if dup_sort { self.dup_count()? } else { 1 };
+            self.del_inner(flags)?;
+        }
+    }
+
     /// Appends a key/data pair to the end of the database.
     ///
     /// The key must be greater than all existing keys (or less than, for
@@ -869,7 +1837,7 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
         let mut data_val: ffi::MDBX_val =
             ffi::MDBX_val { iov_len: data.len(), iov_base: data.as_ptr() as *mut c_void };
 
-        mdbx_result(self.access.with_txn_ptr(|_txn_ptr| {
+        self.access.try_with_txn_ptr(|_txn_ptr| {
             #[cfg(debug_assertions)]
             // SAFETY: txn_ptr is valid from with_txn_ptr.
             unsafe {
@@ -883,16 +1851,19 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
             };
 
             // SAFETY: cursor and txn_ptr are valid.
-            unsafe {
+            mdbx_result(unsafe {
                 ffi::mdbx_cursor_put(
                     self.cursor,
                     &key_val,
                     &mut data_val,
                     WriteFlags::APPEND.bits(),
                 )
+            })?;
+            if let Some(recorder) = self.access.changes().recorder() {
+                recorder.push(self.db, ChangeOp::Put, key, Some(data), None);
             }
-        }))
-        .map(drop)
+            Ok(())
+        })
     }
 
     /// Appends duplicate data for [`DatabaseFlags::DUP_SORT`] databases.
@@ -912,7 +1883,7 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
         let mut data_val: ffi::MDBX_val =
             ffi::MDBX_val { iov_len: data.len(), iov_base: data.as_ptr() as *mut c_void };
 
-        mdbx_result(self.access.with_txn_ptr(|_txn_ptr| {
+        self.access.try_with_txn_ptr(|_txn_ptr| {
             #[cfg(debug_assertions)]
             // SAFETY: _txn_ptr is valid from with_txn_ptr.
             unsafe {
@@ -926,16 +1897,19 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
             };
 
             // SAFETY: cursor and txn_ptr are valid.
-            unsafe {
+            mdbx_result(unsafe {
                 ffi::mdbx_cursor_put(
                     self.cursor,
                     &key_val,
                     &mut data_val,
                     WriteFlags::APPEND_DUP.bits(),
                 )
+            })?;
+            if let Some(recorder) = self.access.changes().recorder() {
+                recorder.push(self.db, ChangeOp::Put, key, Some(data), None);
             }
-        }))
-        .map(drop)
+            Ok(())
+        })
     }
 
     /// [`DatabaseFlags::DUP_FIXED`]-only: Store multiple contiguous fixed-size
@@ -1040,12 +2014,24 @@ impl<'tx, K: TransactionKind + WriteMarker> Cursor<'tx, K> {
 
         // SAFETY: cursor and txn_ptr are valid within with_txn_ptr block.
         // data_vals is properly structured per MDBX_MULTIPLE requirements.
-        mdbx_result(self.access.with_txn_ptr(|_| unsafe {
-            ffi::mdbx_cursor_put(self.cursor, &key_val, data_vals.as_mut_ptr(), flags.bits())
-        }))?;
+        self.access.try_with_txn_ptr(|_| {
+            mdbx_result(unsafe {
+                ffi::mdbx_cursor_put(self.cursor, &key_val, data_vals.as_mut_ptr(), flags.bits())
+            })
+        })?;
+
+        let written = data_vals[1].iov_len;
+        if let Some(recorder) = self.access.changes().recorder() {
+            if flags.contains(WriteFlags::ALLDUPS) {
+                recorder.push(self.db, ChangeOp::Del, key, None, None);
+            }
+            for value in values.chunks_exact(value_size).take(written) {
+                recorder.push(self.db, ChangeOp::Put, key, Some(value), None);
+            }
+        }
 
         // Return actual count written
-        Ok(data_vals[1].iov_len)
+        Ok(written)
     }
 }
 
@@ -1077,6 +2063,16 @@ where
         // To be able to close a cursor of a timed out transaction, we need to
         // renew it first. Hence the usage of `with_txn_ptr_for_cleanup` here.
         self.access.with_txn_ptr(|_| unsafe { ffi::mdbx_cursor_close(self.cursor) });
+        self.registry.release_cursor(self.db.dbi());
+    }
+}
+
+/// Converts the return code of a scan to whether the predicate matched.
+const fn scan_result(rc: c_int) -> MdbxResult<bool> {
+    match rc {
+        ffi::MDBX_RESULT_TRUE => Ok(true),
+        ffi::MDBX_RESULT_FALSE | ffi::MDBX_NOTFOUND | ffi::MDBX_ENODATA => Ok(false),
+        other => Err(crate::MdbxError::from_err_code(other)),
     }
 }
 
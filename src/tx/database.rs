@@ -6,35 +6,47 @@ use crate::flags::DatabaseFlags;
 /// environment.
 ///
 /// `Database` is a simple data container holding the database handle index
-/// (dbi) and its flags. It does not own any resources and can be freely
-/// copied.
+/// (dbi), its flags, and the generation of the handle at the time it was
+/// opened. It does not own any resources and can be freely copied.
 ///
-/// # Lifetime
+/// Handles compare equal only if they were opened in the same generation,
+/// so a handle to a closed or dropped table never equals a handle that
+/// later reuses its dbi.
 ///
-/// The database handle is only valid within the lifetime of the environment
-/// that created it. Users must ensure that `Database` instances are not used
-/// after the environment has been closed.
-#[derive(Debug, Clone, Copy)]
+/// # Validity
+///
+/// Handles are validated against the environment's handle registry before
+/// use. Closing a database with [`Tx::close_db`] or dropping it with
+/// [`Tx::drop_db`] invalidates every copy of the handle, and later use of
+/// those copies fails with [`MdbxError::BadDbi`] instead of touching a
+/// released dbi. Re-open the database to obtain a fresh handle.
+///
+/// [`Tx::close_db`]: crate::tx::Tx::close_db
+/// [`Tx::drop_db`]: crate::tx::Tx::drop_db
+/// [`MdbxError::BadDbi`]: crate::MdbxError::BadDbi
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Database {
     dbi: ffi::MDBX_dbi,
     flags: DatabaseFlags,
+    generation: u32,
 }
 
 impl Database {
-    /// Creates a new Database from a dbi and flags.
-    pub(crate) const fn new(dbi: ffi::MDBX_dbi, flags: DatabaseFlags) -> Self {
-        Self { dbi, flags }
+    /// Creates a new Database from a dbi, flags, and handle generation.
+    pub(crate) const fn new(dbi: ffi::MDBX_dbi, flags: DatabaseFlags, generation: u32) -> Self {
+        Self { dbi, flags, generation }
     }
 
     /// Opens the freelist database with DBI `0`.
     pub const fn freelist_db() -> Self {
-        Self { dbi: 0, flags: DatabaseFlags::empty() }
+        Self { dbi: 0, flags: DatabaseFlags::empty(), generation: 0 }
     }
 
     /// Returns the underlying MDBX database handle.
     ///
-    /// The caller **must** ensure that the handle is not used after the
-    /// lifetime of the environment, or after the database has been closed.
+    /// Raw dbi values are not validated. The caller **must** ensure that the
+    /// handle is not used after the lifetime of the environment, or after the
+    /// database has been closed.
     pub const fn dbi(&self) -> ffi::MDBX_dbi {
         self.dbi
     }
@@ -43,4 +55,9 @@ impl Database {
     pub const fn flags(&self) -> DatabaseFlags {
         self.flags
     }
+
+    /// Returns the generation of the handle when it was opened.
+    pub(crate) const fn generation(&self) -> u32 {
+        self.generation
+    }
 }
@@ -0,0 +1,147 @@
+use crate::CommitLatency;
+use parking_lot::Mutex;
+use std::{
+    fmt, mem,
+    sync::{Arc, OnceLock},
+};
+
+/// A callback run after a write transaction commits.
+type CommitHook = Box<dyn FnOnce(CommitInfo) + Send>;
+
+/// A callback run after a write transaction is aborted.
+type AbortHook = Box<dyn FnOnce() + Send>;
+
+/// Information about a committed write transaction, passed to the callbacks
+/// registered with [`Tx::on_commit`].
+///
+/// [`Tx::on_commit`]: crate::tx::Tx::on_commit
+#[derive(Debug, Clone, Copy)]
+pub struct CommitInfo {
+    txn_id: u64,
+    latency: CommitLatency,
+}
+
+impl CommitInfo {
+    /// Creates a new `CommitInfo`.
+    pub(crate) const fn new(txn_id: u64, latency: CommitLatency) -> Self {
+        Self { txn_id, latency }
+    }
+
+    /// The ID of the committed transaction.
+    #[inline]
+    pub const fn txn_id(&self) -> u64 {
+        self.txn_id
+    }
+
+    /// Latency information of the commit.
+    #[inline]
+    pub const fn latency(&self) -> &CommitLatency {
+        &self.latency
+    }
+}
+
+/// Callbacks registered on a transaction.
+///
+/// Dropping the set runs the abort callbacks, so a transaction that does not
+/// commit runs them when it is aborted or dropped.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    on_commit: Vec<CommitHook>,
+    on_abort: Vec<AbortHook>,
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field("on_commit", &self.on_commit.len())
+            .field("on_abort", &self.on_abort.len())
+            .finish()
+    }
+}
+
+impl Hooks {
+    /// Returns `true` if any commit callbacks are registered.
+    pub(crate) fn has_commit_hooks(&self) -> bool {
+        !self.on_commit.is_empty()
+    }
+}
+
+impl Drop for Hooks {
+    fn drop(&mut self) {
+        for hook in self.on_abort.drain(..) {
+            hook();
+        }
+    }
+}
+
+/// The callbacks of a transaction, and those of its parent if it is nested.
+///
+/// Public only because it appears in the sealed [`TxPtrAccess`] trait.
+///
+/// [`TxPtrAccess`]: crate::tx::TxPtrAccess
+#[allow(unreachable_pub)]
+#[derive(Debug, Default)]
+pub struct TxHooks {
+    hooks: Arc<Mutex<Hooks>>,
+    parent: OnceLock<Arc<Mutex<Hooks>>>,
+}
+
+impl TxHooks {
+    /// Registers a commit callback.
+    pub(crate) fn on_commit(&self, f: impl FnOnce(CommitInfo) + Send + 'static) {
+        self.hooks.lock().on_commit.push(Box::new(f));
+    }
+
+    /// Registers an abort callback.
+    pub(crate) fn on_abort(&self, f: impl FnOnce() + Send + 'static) {
+        self.hooks.lock().on_abort.push(Box::new(f));
+    }
+
+    /// Links a nested transaction's callbacks to those of its parent.
+    pub(crate) fn set_parent(&self, parent: &Self) {
+        let _ = self.parent.set(Arc::clone(&parent.hooks));
+    }
+
+    /// Returns `true` if the transaction is nested.
+    #[inline]
+    pub(crate) fn is_nested(&self) -> bool {
+        self.parent.get().is_some()
+    }
+
+    /// Takes the registered callbacks, leaving none behind.
+    ///
+    /// Dropping the returned set runs its abort callbacks.
+    pub(crate) fn take(&self) -> Hooks {
+        mem::take(&mut *self.hooks.lock())
+    }
+
+    /// Puts back callbacks taken with [`TxHooks::take`].
+    pub(crate) fn restore(&self, hooks: Hooks) {
+        *self.hooks.lock() = hooks;
+    }
+
+    /// Handles the callbacks taken from a transaction that committed.
+    ///
+    /// For a nested transaction, they are moved to the parent, to run once
+    /// the parent finishes. Otherwise the commit callbacks run with `info`,
+    /// and the abort callbacks are discarded.
+    pub(crate) fn committed(&self, mut hooks: Hooks, info: impl FnOnce() -> CommitInfo) {
+        let on_commit = mem::take(&mut hooks.on_commit);
+        let on_abort = mem::take(&mut hooks.on_abort);
+
+        if let Some(parent) = self.parent.get() {
+            let mut parent = parent.lock();
+            parent.on_commit.extend(on_commit);
+            parent.on_abort.extend(on_abort);
+            return;
+        }
+
+        if on_commit.is_empty() {
+            return;
+        }
+        let info = info();
+        for hook in on_commit {
+            hook(info);
+        }
+    }
+}
@@ -1,25 +1,30 @@
 use crate::{
-    CommitLatency, Cursor, Database, DatabaseFlags, Environment, MdbxError, MdbxResult, ReadResult,
-    Ro, Rw, Stat, TableObject, TransactionKind, WriteFlags,
+    CancelHandle, ChangeOp, ChangeSet, CommitInfo, CommitLatency, Cursor, Database, DatabaseFlags,
+    Environment, MdbxError, MdbxResult, ReadResult, Ro, Rw, Savepoint, Stat, TableObject,
+    TransactionKind, TxInfo, UnboundCursor, UserData, WriteFlags,
     error::mdbx_result,
     sys::txn_manager::{Begin, Commit, CommitLatencyPtr, RawTxPtr},
     tx::aliases::{RoTxSync, RoTxUnsync, RwTxUnsync},
     tx::{
         PtrSync, PtrUnsync, TxPtrAccess,
         cache::{Cache, CachedDb},
+        changes::{Recorder, TxChanges},
         kind::{RoSync, SyncKind, WriteMarker, WriterKind},
         ops,
     },
+    user_data,
 };
 use core::fmt;
-use ffi::MDBX_commit_latency;
 use smallvec::SmallVec;
 use std::{
+    any::Any,
     ffi::CStr,
+    ops::RangeBounds,
+    panic::Location,
     ptr,
     sync::{Arc, mpsc::sync_channel},
     thread::sleep,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tracing::{debug_span, instrument, warn};
 
@@ -106,9 +111,48 @@ impl RoTxSync {
 }
 
 impl RwTxUnsync {
+    #[track_caller]
     pub(crate) fn begin(env: Environment) -> Result<Self, MdbxError> {
+        let opened_at = Location::caller();
+        env.rw_txn_owner().check()?;
         let tx = Rw::new_from_env(env.clone())?;
-        Ok(Self::from_access_and_env(tx, env))
+        Ok(Self::from_access_and_env(tx, env).with_owner(opened_at))
+    }
+
+    /// Begins a transaction, failing with [`MdbxError::Busy`] instead of
+    /// blocking if the write lock is held.
+    #[track_caller]
+    pub(crate) fn try_begin(env: Environment) -> MdbxResult<Self> {
+        let opened_at = Location::caller();
+        env.rw_txn_owner().check()?;
+        Self::try_begin_unchecked(&env).map(|tx| tx.with_owner(opened_at))
+    }
+
+    /// Begins a transaction, waiting at most `timeout` for the write lock.
+    #[track_caller]
+    pub(crate) fn begin_timeout(env: Environment, timeout: Duration) -> MdbxResult<Self> {
+        let opened_at = Location::caller();
+        env.rw_txn_owner().check()?;
+        let deadline = Instant::now() + timeout;
+        begin_rw_with_backoff(&env, Some(deadline), || Self::try_begin_unchecked(&env))
+            .map(|tx| tx.with_owner(opened_at))
+    }
+
+    /// Makes a single non-blocking attempt to begin a transaction, without
+    /// checking or recording the owning thread.
+    fn try_begin_unchecked(env: &Environment) -> MdbxResult<Self> {
+        let mut txn: *mut ffi::MDBX_txn = ptr::null_mut();
+        // SAFETY: env_ptr is valid for the lifetime of env.
+        unsafe {
+            mdbx_result(ffi::mdbx_txn_begin_ex(
+                env.env_ptr(),
+                ptr::null_mut(),
+                Rw::OPEN_FLAGS | ffi::MDBX_TXN_TRY,
+                &mut txn,
+                ptr::null_mut(),
+            ))?;
+        }
+        Ok(Self::from_ptr_and_env(txn, env.clone()))
     }
 }
 
@@ -125,11 +169,15 @@ where
     K: TransactionKind,
 {
     /// Provides access to the raw transaction pointer.
-    fn with_txn_ptr<F, R>(&self, f: F) -> R
+    ///
+    /// Fails with [`MdbxError::TxnBroken`] if the transaction was broken by a
+    /// [`CancelHandle`].
+    fn with_txn_ptr<F, T, E>(&self, f: F) -> Result<T, E>
     where
-        F: FnOnce(*mut ffi::MDBX_txn) -> R,
+        F: FnOnce(*mut ffi::MDBX_txn) -> Result<T, E>,
+        E: From<MdbxError>,
     {
-        self.txn.with_txn_ptr(f)
+        self.txn.try_with_txn_ptr(f)
     }
 
     /// Returns the transaction id.
@@ -141,25 +189,56 @@ where
         })
     }
 
+    /// Retrieves information about the transaction.
+    ///
+    /// For write transactions, [`TxInfo::space_dirty`] can be used to bound
+    /// the size of a transaction before committing it.
+    pub fn info(&self) -> MdbxResult<TxInfo> {
+        self.with_txn_ptr(|txn_ptr| {
+            // SAFETY: txn_ptr is valid from with_txn_ptr.
+            unsafe {
+                let mut info = TxInfo(std::mem::zeroed());
+                mdbx_result(ffi::mdbx_txn_info(txn_ptr, &mut info.0, false))?;
+                Ok(info)
+            }
+        })
+    }
+
     /// Gets an item from a database.
-    pub fn get<'a, Key>(&'a self, dbi: ffi::MDBX_dbi, key: &[u8]) -> ReadResult<Option<Key>>
+    pub fn get<'a, Key>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<Key>>
     where
         Key: TableObject<'a>,
     {
+        self.check_db(db)?;
         self.with_txn_ptr(|txn_ptr| {
             // SAFETY: txn_ptr is valid from with_txn_ptr.
             unsafe {
-                let data_val = ops::get_raw(txn_ptr, dbi, key)?;
+                let data_val = ops::get_raw(txn_ptr, db.dbi(), key)?;
                 data_val.map(|val| Key::decode_val::<K>(txn_ptr, val)).transpose()
             }
         })
     }
 
     /// Opens a handle to an MDBX database.
+    ///
+    /// Handles are cached in the transaction. Handles opened by write
+    /// transactions are also shared through the environment once they
+    /// commit, with the transactions whose snapshot includes that commit.
     pub fn open_db(&self, name: Option<&str>) -> MdbxResult<Database> {
         let name_hash = CachedDb::hash_name(name);
+        let registry = self.env().dbi_registry();
+
+        if let Some(db) = self.cache.read_db(name_hash)
+            && registry.is_current(db)
+        {
+            return Ok(db);
+        }
 
-        if let Some(db) = self.cache.read_db(name_hash) {
+        if let Some(txn_id) = self.txn.tx_id()
+            && let Some(db) = self.env().db_cache().read_db(name_hash, txn_id as u64)
+            && registry.is_current(db)
+        {
+            self.cache.write_db(CachedDb::from_hash(name_hash, db));
             return Ok(db);
         }
 
@@ -190,12 +269,46 @@ where
         });
         let name_ptr = c_name.as_ref().map_or(ptr::null(), |s| s.as_ptr());
 
-        let (dbi, db_flags) = self.with_txn_ptr(|txn_ptr| {
+        let (dbi, db_flags, created) = self.with_txn_ptr(|txn_ptr| {
             // SAFETY: txn_ptr is valid from with_txn_ptr, name_ptr is valid or null.
             unsafe { ops::open_db_raw(txn_ptr, name_ptr, flags) }
         })?;
 
-        Ok(CachedDb::new(name, Database::new(dbi, db_flags)))
+        // MDBX hands out the handle of a table opened elsewhere in the
+        // environment without checking that the table exists in the
+        // snapshot of a read transaction, which then fails on use.
+        if K::IS_READ_ONLY && name.is_some() {
+            self.db_stat_by_dbi(dbi).map_err(|err| match err {
+                MdbxError::BadDbi => MdbxError::NotFound,
+                err => err,
+            })?;
+        }
+
+        let generation = self.env().dbi_registry().generation(dbi);
+        let db = Database::new(dbi, db_flags, generation);
+
+        // MDBX closes handles to tables created by a transaction that
+        // aborts, so every copy of the handle must be invalidated with it.
+        if created {
+            let env = self.env().clone();
+            self.txn.hooks().on_abort(move || {
+                let _ = env.dbi_registry().invalidate(db);
+                env.db_cache().remove_dbi(db.dbi());
+            });
+        }
+
+        Ok(CachedDb::new(name, db))
+    }
+
+    /// Shares the handles opened by a top-level write transaction that
+    /// committed as `txn_id` with later transactions.
+    fn publish_dbs(&self, txn_id: u64) {
+        if K::IS_READ_ONLY || self.txn.hooks().is_nested() {
+            return;
+        }
+        let registry = self.env().dbi_registry();
+        let entries = self.cache.entries().into_iter().filter(|db| registry.is_current(db.db()));
+        self.env().db_cache().publish(entries, txn_id);
     }
 
     /// Gets the option flags for the given database.
@@ -214,6 +327,7 @@ where
 
     /// Retrieves database statistics.
     pub fn db_stat(&self, db: &Database) -> MdbxResult<Stat> {
+        self.check_db(*db)?;
         self.db_stat_by_dbi(db.dbi())
     }
 
@@ -225,17 +339,53 @@ where
         })
     }
 
+    /// Estimates the number of items between two keys of a database.
+    ///
+    /// `begin` is inclusive and `end` exclusive, and `None` stands for the
+    /// start or the end of the database. The result is negative if `end`
+    /// precedes `begin`.
+    ///
+    /// The estimate is derived from the shape of the b-tree without reading
+    /// the items, so it is cheap but approximate. It is usually within a few
+    /// percent, but may be off by a factor of several on unbalanced trees.
+    pub fn estimate_range(
+        &self,
+        db: Database,
+        begin: Option<&[u8]>,
+        end: Option<&[u8]>,
+    ) -> MdbxResult<isize> {
+        self.check_db(db)?;
+        self.with_txn_ptr(|txn| {
+            // SAFETY: txn is a valid transaction pointer from with_txn_ptr,
+            // and the keys outlive the call.
+            unsafe { ops::estimate_range_raw(txn, db.dbi(), begin, end) }
+        })
+    }
+
+    /// Returns [`MdbxError::BadDbi`] if the handle was closed or dropped
+    /// after it was opened.
+    #[inline]
+    fn check_db(&self, db: Database) -> MdbxResult<()> {
+        self.env().dbi_registry().check(db)
+    }
+
     /// Closes the database handle.
     ///
-    /// # Safety
+    /// Every copy of `db` is invalidated, and later use of those copies
+    /// fails with [`MdbxError::BadDbi`].
     ///
-    /// This will invalidate data cached in [`Database`] instances with the
-    /// DBI, and may result in bad behavior when using those instances after
-    /// calling this function.
-    pub unsafe fn close_db(&self, dbi: ffi::MDBX_dbi) -> MdbxResult<()> {
-        // SAFETY: Caller ensures no other references exist.
-        unsafe { ops::close_db_raw(self.meta.env.env_ptr(), dbi) }?;
-        self.cache.remove_dbi(dbi);
+    /// Fails with [`MdbxError::DbiInUse`] if any cursor is open on the
+    /// database, or with [`MdbxError::BadDbi`] if the handle is already
+    /// stale.
+    pub fn close_db(&self, db: Database) -> MdbxResult<()> {
+        let invalidation = self.env().dbi_registry().begin_invalidate(db)?;
+        // SAFETY: The registry guarantees no cursor is open on the dbi, and
+        // every other copy of the handle fails validation until the
+        // invalidation is finished or dropped.
+        unsafe { ops::close_db_raw(self.meta.env.env_ptr(), db.dbi()) }?;
+        invalidation.finish();
+        self.cache.remove_dbi(db.dbi());
+        self.env().db_cache().remove_dbi(db.dbi());
         Ok(())
     }
 
@@ -244,8 +394,118 @@ where
     /// Multiple cursors can be open simultaneously on different databases
     /// within the same transaction. The cursor borrows the transaction's
     /// inner access type, allowing concurrent cursor operations.
+    ///
+    /// While the cursor is open, the database cannot be closed or dropped.
     pub fn cursor(&self, db: Database) -> MdbxResult<Cursor<'_, K>> {
-        Cursor::new(&self.txn, db)
+        Cursor::new(&self.txn, self.env().dbi_registry(), db)
+    }
+
+    /// Binds an [`UnboundCursor`] to a database in this transaction.
+    ///
+    /// This reuses the cursor's allocation instead of opening a new one.
+    /// Use [`Cursor::unbind`] to get the cursor back before the transaction
+    /// ends. If binding fails, the cursor is closed.
+    pub fn bind_cursor(&self, cursor: UnboundCursor, db: Database) -> MdbxResult<Cursor<'_, K>> {
+        Cursor::from_unbound(&self.txn, self.env().dbi_registry(), db, cursor, |txn, cursor| {
+            // SAFETY: txn is valid from try_with_txn_ptr, and the cursor is
+            // not bound to any transaction.
+            unsafe { ffi::mdbx_cursor_bind(txn, cursor, db.dbi()) }
+        })
+    }
+
+    /// Binds an [`UnboundCursor`] to the database it was last bound to, in
+    /// this transaction.
+    ///
+    /// Fails with [`MdbxError::BadDbi`] if the cursor was never bound, or if
+    /// that database was closed since. If binding fails, the cursor is
+    /// closed.
+    pub fn renew_cursor(&self, cursor: UnboundCursor) -> MdbxResult<Cursor<'_, K>> {
+        let db = cursor.db().ok_or(MdbxError::BadDbi)?;
+        Cursor::from_unbound(&self.txn, self.env().dbi_registry(), db, cursor, |txn, cursor| {
+            // SAFETY: txn is valid from try_with_txn_ptr, and the cursor is
+            // not bound to any transaction.
+            unsafe { ffi::mdbx_cursor_renew(txn, cursor) }
+        })
+    }
+
+    /// Aborts the transaction, discarding any pending operations.
+    ///
+    /// Dropping a transaction also aborts it, but ignores any failure. This
+    /// reports the result of the abort instead.
+    #[instrument(skip(self), parent = &self.meta.span)]
+    pub fn abort(self) -> MdbxResult<()> {
+        let res = self.txn.abort();
+        // Run the abort callbacks now, even if clones of a synchronized
+        // transaction are still alive.
+        drop(self.txn.hooks().take());
+        res
+    }
+}
+
+// User data, which must be `Sync` wherever the transaction can be shared or
+// moved to another thread while a handle to the data stays behind.
+impl<K> Tx<K, Arc<PtrSync>>
+where
+    K: TransactionKind<Access = Arc<PtrSync>>,
+{
+    /// Attaches application data to the transaction, replacing and dropping
+    /// any previously attached data.
+    ///
+    /// The data is released when the transaction is committed, aborted or
+    /// dropped, and dropped once no [`UserData`] handle to it remains. It is
+    /// shared between clones of the transaction.
+    pub fn set_user_data<T: Any + Send + Sync>(&self, data: T) {
+        user_data::set_tx(self.txn.user_data(), data);
+    }
+
+    /// Returns the data attached with
+    /// [`set_user_data`](Self::set_user_data), if it is of type `T`.
+    ///
+    /// The returned handle shares the data without locking it.
+    pub fn user_data<T: Any + Send + Sync>(&self) -> Option<UserData<T>> {
+        user_data::get_tx(self.txn.user_data())
+    }
+}
+
+impl RoTxUnsync {
+    /// Attaches application data to the transaction, replacing and dropping
+    /// any previously attached data.
+    ///
+    /// The data is released when the transaction is committed, aborted or
+    /// dropped, and dropped once no [`UserData`] handle to it remains. It
+    /// must be `Sync`, as the transaction can move to another thread while
+    /// a handle stays behind.
+    pub fn set_user_data<T: Any + Send + Sync>(&self, data: T) {
+        user_data::set_tx(self.txn.user_data(), data);
+    }
+
+    /// Returns the data attached with
+    /// [`set_user_data`](Self::set_user_data), if it is of type `T`.
+    ///
+    /// The returned handle shares the data without locking it.
+    pub fn user_data<T: Any + Send + Sync>(&self) -> Option<UserData<T>> {
+        user_data::get_tx(self.txn.user_data())
+    }
+}
+
+impl RwTxUnsync {
+    /// Attaches application data to the transaction, replacing and dropping
+    /// any previously attached data.
+    ///
+    /// The data is released when the transaction is committed, aborted or
+    /// dropped, and dropped once no [`UserData`] handle to it remains. The
+    /// transaction never leaves its thread, so the data need not be `Sync`,
+    /// and may use a [`RefCell`](std::cell::RefCell) for mutable state.
+    pub fn set_user_data<T: Any + Send>(&self, data: T) {
+        user_data::set_tx(self.txn.user_data(), data);
+    }
+
+    /// Returns the data attached with
+    /// [`set_user_data`](Self::set_user_data), if it is of type `T`.
+    ///
+    /// The returned handle shares the data without locking it.
+    pub fn user_data<T: Any + Send>(&self) -> Option<UserData<T>> {
+        user_data::get_tx(self.txn.user_data())
     }
 }
 
@@ -271,6 +531,79 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         self.open_db_with_flags(name, flags | DatabaseFlags::CREATE).map(Into::into)
     }
 
+    /// Records the current thread as the owner of this top-level transaction.
+    fn with_owner(self, opened_at: &'static Location<'static>) -> Self {
+        let txn = self.txn.with_txn_ptr(|txn| txn);
+        self.env().rw_txn_owner().acquire(txn, opened_at);
+        self
+    }
+
+    /// Returns a [`CancelHandle`] that can break this transaction from
+    /// another thread.
+    ///
+    /// Once cancelled, operations on the transaction fail with
+    /// [`MdbxError::TxnBroken`], and committing it is refused.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.txn.cancel_handle()
+    }
+
+    /// Registers a callback to run after the transaction commits.
+    ///
+    /// The callback receives the ID of the committed transaction and the
+    /// commit latency. It runs on the committing thread, once the commit has
+    /// succeeded. If the transaction is aborted, dropped, or fails to
+    /// commit, the callback is discarded.
+    ///
+    /// For a nested transaction, the callback is moved to the parent when
+    /// the nested transaction commits, and runs only if the parent commits.
+    pub fn on_commit(&self, f: impl FnOnce(CommitInfo) + Send + 'static) {
+        self.txn.hooks().on_commit(f);
+    }
+
+    /// Registers a callback to run after the transaction is aborted.
+    ///
+    /// The callback runs when the transaction is aborted, dropped without
+    /// committing, or fails to commit. It is discarded if the transaction
+    /// commits.
+    ///
+    /// For a nested transaction, the callback runs if the nested transaction
+    /// is aborted. If it commits, the callback is moved to the parent, and
+    /// runs only if the parent is aborted.
+    pub fn on_abort(&self, f: impl FnOnce() + Send + 'static) {
+        self.txn.hooks().on_abort(f);
+    }
+
+    /// Records every write made through the transaction and its cursors, and
+    /// passes the resulting [`ChangeSet`] to `subscriber` once the
+    /// transaction commits.
+    ///
+    /// If `old_values` is `true`, puts and deletes in tables without
+    /// [`DatabaseFlags::DUP_SORT`] also record the value they replace, at
+    /// the cost of an extra lookup per write. Recording starts with the
+    /// first call; later calls add subscribers, which receive the same
+    /// changes, and their `old_values` setting is ignored.
+    ///
+    /// If the transaction is aborted, the recorded changes are discarded.
+    ///
+    /// Nested transactions of a recording transaction record their writes
+    /// as well. They are added to the parent's changes when the nested
+    /// transaction commits, and discarded when it aborts. If recording
+    /// starts in a nested transaction, the parent records from the nested
+    /// transaction's commit onwards, and the subscriber runs when the parent
+    /// commits.
+    pub fn record_changes(
+        &self,
+        old_values: bool,
+        subscriber: impl FnOnce(ChangeSet) + Send + 'static,
+    ) {
+        self.txn.changes().subscribe(old_values, subscriber);
+    }
+
+    /// Returns the change recorder of the transaction.
+    pub(crate) fn changes(&self) -> &TxChanges {
+        self.txn.changes()
+    }
+
     /// Stores an item into a database.
     ///
     /// This function stores key/data pairs in the database. The default
@@ -284,6 +617,7 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         data: impl AsRef<[u8]>,
         flags: WriteFlags,
     ) -> MdbxResult<()> {
+        self.check_db(db)?;
         let key = key.as_ref();
         let data = data.as_ref();
 
@@ -296,8 +630,19 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         }
 
         self.with_txn_ptr(|txn| {
+            let recorder = self.txn.changes().recorder();
             // SAFETY: txn is a valid RW transaction pointer from with_txn_ptr.
-            unsafe { ops::put_raw(txn, db.dbi(), key, data, flags) }
+            unsafe {
+                let old_value = recorder.map(|r| r.old_value(txn, db, key)).transpose()?.flatten();
+                ops::put_raw(txn, db.dbi(), key, data, flags)?;
+                if let Some(recorder) = recorder {
+                    if flags.contains(WriteFlags::ALLDUPS) {
+                        recorder.push(db, ChangeOp::Del, key, None, None);
+                    }
+                    recorder.push(db, ChangeOp::Put, key, Some(data), old_value);
+                }
+            }
+            Ok(())
         })
     }
 
@@ -315,6 +660,7 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         key: impl AsRef<[u8]>,
         data: impl AsRef<[u8]>,
     ) -> MdbxResult<()> {
+        self.check_db(db)?;
         let key = key.as_ref();
         let data = data.as_ref();
 
@@ -326,7 +672,11 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
             }
 
             // SAFETY: txn is a valid RW transaction pointer from with_txn_ptr.
-            unsafe { ops::put_raw(txn, db.dbi(), key, data, WriteFlags::APPEND) }
+            unsafe { ops::put_raw(txn, db.dbi(), key, data, WriteFlags::APPEND)? };
+            if let Some(recorder) = self.txn.changes().recorder() {
+                recorder.push(db, ChangeOp::Put, key, Some(data), None);
+            }
+            Ok(())
         })
     }
 
@@ -347,6 +697,7 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         #[cfg(debug_assertions)]
         assertions::debug_assert_dup_sort(db.flags());
 
+        self.check_db(db)?;
         let key = key.as_ref();
         let data = data.as_ref();
 
@@ -358,7 +709,11 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
             }
 
             // SAFETY: txn is a valid RW transaction pointer from with_txn_ptr.
-            unsafe { ops::put_raw(txn, db.dbi(), key, data, WriteFlags::APPEND_DUP) }
+            unsafe { ops::put_raw(txn, db.dbi(), key, data, WriteFlags::APPEND_DUP)? };
+            if let Some(recorder) = self.txn.changes().recorder() {
+                recorder.push(db, ChangeOp::Put, key, Some(data), None);
+            }
+            Ok(())
         })
     }
 
@@ -368,6 +723,9 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
     ///
     /// This should not be used on dupsort tables.
     ///
+    /// When recording changes, the write is recorded without its value. Use
+    /// [`Tx::with_reservation`] to record it with its value.
+    ///
     /// # Safety
     ///
     /// The caller must ensure that the returned buffer is not used after the
@@ -383,6 +741,29 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         flags: WriteFlags,
     ) -> MdbxResult<&mut [u8]> {
         let key = key.as_ref();
+        // SAFETY: upheld by the caller.
+        let (buf, recorded) = unsafe { self.reserve_inner(db, key, len, flags)? };
+        if let Some((recorder, old_value)) = recorded {
+            recorder.push(db, ChangeOp::Put, key, None, old_value);
+        }
+        Ok(buf)
+    }
+
+    /// Reserves space for a value, returning the recorder and the old
+    /// value if changes are recorded.
+    ///
+    /// # Safety
+    ///
+    /// See [`Tx::reserve`].
+    #[allow(clippy::mut_from_ref, clippy::type_complexity)]
+    unsafe fn reserve_inner(
+        &self,
+        db: Database,
+        key: &[u8],
+        len: usize,
+        flags: WriteFlags,
+    ) -> MdbxResult<(&mut [u8], Option<(&Recorder, Option<Vec<u8>>)>)> {
+        self.check_db(db)?;
 
         #[cfg(debug_assertions)]
         {
@@ -392,12 +773,17 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
             assertions::debug_assert_key(pagesize, db.flags(), key);
         }
 
-        let ptr = self.with_txn_ptr(|txn| {
+        let (ptr, recorded) = self.with_txn_ptr(|txn| {
+            let recorder = self.txn.changes().recorder();
             // SAFETY: txn is a valid RW transaction pointer from with_txn_ptr.
-            unsafe { ops::reserve_raw(txn, db.dbi(), key, len, flags) }
+            unsafe {
+                let old_value = recorder.map(|r| r.old_value(txn, db, key)).transpose()?.flatten();
+                let ptr = ops::reserve_raw(txn, db.dbi(), key, len, flags)?;
+                MdbxResult::Ok((ptr, recorder.map(|r| (r, old_value))))
+            }
         })?;
         // SAFETY: ptr is valid from reserve_raw, len matches.
-        Ok(unsafe { ops::slice_from_reserved(ptr, len) })
+        Ok((unsafe { ops::slice_from_reserved(ptr, len) }, recorded))
     }
 
     /// Reserves space for a value of the given length at the given key, and
@@ -412,8 +798,12 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         flags: WriteFlags,
         f: impl FnOnce(&mut [u8]),
     ) -> MdbxResult<()> {
-        let buf = unsafe { self.reserve(db, key, len, flags)? };
+        let key = key.as_ref();
+        let (buf, recorded) = unsafe { self.reserve_inner(db, key, len, flags)? };
         f(buf);
+        if let Some((recorder, old_value)) = recorded {
+            recorder.push(db, ChangeOp::Put, key, Some(buf), old_value);
+        }
         Ok(())
     }
 
@@ -433,6 +823,7 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         key: impl AsRef<[u8]>,
         data: Option<&[u8]>,
     ) -> MdbxResult<bool> {
+        self.check_db(db)?;
         let key = key.as_ref();
 
         #[cfg(debug_assertions)]
@@ -447,34 +838,75 @@ impl<K: TransactionKind + WriteMarker> Tx<K> {
         }
 
         self.with_txn_ptr(|txn| {
+            let recorder = self.txn.changes().recorder();
             // SAFETY: txn is a valid RW transaction pointer from with_txn_ptr.
-            unsafe { ops::del_raw(txn, db.dbi(), key, data) }
+            unsafe {
+                let old_value = recorder.map(|r| r.old_value(txn, db, key)).transpose()?.flatten();
+                let deleted = ops::del_raw(txn, db.dbi(), key, data)?;
+                if let Some(recorder) = recorder.filter(|_| deleted) {
+                    recorder.push(db, ChangeOp::Del, key, data, old_value);
+                }
+                Ok(deleted)
+            }
         })
     }
 
+    /// Deletes every key/value pair whose key is within `range`.
+    ///
+    /// The bounds are compared with the table's key order, so
+    /// [`DatabaseFlags::REVERSE_KEY`] and [`DatabaseFlags::INTEGER_KEY`]
+    /// tables work as expected. In a [`DatabaseFlags::DUP_SORT`] database,
+    /// each key is removed with all of its values in a single
+    /// [`WriteFlags::ALLDUPS`] delete.
+    ///
+    /// Returns the number of key/value pairs deleted, counting every value
+    /// of a deleted DUPSORT key. See also [`Cursor::delete_while`].
+    pub fn delete_range<'r>(
+        &self,
+        db: Database,
+        range: impl RangeBounds<&'r [u8]>,
+    ) -> MdbxResult<usize> {
+        self.check_db(db)?;
+        self.cursor(db)?.delete_range(range)
+    }
+
     /// Empties the given database. All items will be removed.
     pub fn clear_db(&self, db: Database) -> MdbxResult<()> {
+        self.check_db(db)?;
         self.with_txn_ptr(|txn| {
             // SAFETY: txn is a valid RW transaction pointer from with_txn_ptr.
-            unsafe { ops::clear_db_raw(txn, db.dbi()) }
+            unsafe { ops::clear_db_raw(txn, db.dbi())? };
+            if let Some(recorder) = self.txn.changes().recorder() {
+                recorder.push(db, ChangeOp::Clear, &[], None, None);
+            }
+            Ok(())
         })
     }
 
     /// Drops the database from the environment.
     ///
-    /// # Safety
+    /// Every copy of `db` is invalidated, and later use of those copies
+    /// fails with [`MdbxError::BadDbi`]. This holds even if the transaction
+    /// is later aborted, in which case the database must be re-opened.
     ///
-    /// Caller must ensure no [`Cursor`] or other references to the database
-    /// exist. [`Database`] instances with the DBI will be invalidated, and
-    /// use after calling this function may result in bad behavior.
-    pub unsafe fn drop_db(&self, db: Database) -> MdbxResult<()> {
+    /// Fails with [`MdbxError::DbiInUse`] if any [`Cursor`] is open on the
+    /// database, or with [`MdbxError::BadDbi`] if the handle is already
+    /// stale.
+    pub fn drop_db(&self, db: Database) -> MdbxResult<()> {
+        let invalidation = self.env().dbi_registry().begin_invalidate(db)?;
         self.with_txn_ptr(|txn| {
-            // SAFETY: txn is a valid RW transaction pointer, caller ensures
-            // no other references to dbi exist.
+            // SAFETY: txn is a valid RW transaction pointer, and the registry
+            // guarantees no cursor is open on the dbi.
             unsafe { ops::drop_db_raw(txn, db.dbi()) }
         })?;
+        invalidation.finish();
+
+        if let Some(recorder) = self.txn.changes().recorder() {
+            recorder.push(db, ChangeOp::Drop, &[], None, None);
+        }
 
         self.cache.remove_dbi(db.dbi());
+        self.env().db_cache().remove_dbi(db.dbi());
 
         Ok(())
     }
@@ -490,22 +922,49 @@ where
     ///
     /// Any pending operations will be saved.
     ///
-    /// SAFETY: latency pointer must be valid for the duration of the commit.
-    fn commit_inner(self, latency: *mut MDBX_commit_latency) -> MdbxResult<()> {
-        let was_aborted = self.with_txn_ptr(|txn| {
+    /// Runs the commit callbacks once the commit succeeds.
+    fn commit_inner(self, latency: Option<&mut CommitLatency>) -> MdbxResult<()> {
+        if self.txn.detach_cancel() {
+            return Err(MdbxError::TxnBroken);
+        }
+
+        // If the commit fails, the callbacks are restored, so that the abort
+        // callbacks run once the transaction is aborted.
+        let hooks = self.txn.hooks().take();
+        let mut hook_latency = CommitLatency::new();
+        let mut latency =
+            if hooks.has_commit_hooks() { latency.or(Some(&mut hook_latency)) } else { latency };
+        let txn_id = if !K::IS_READ_ONLY
+            || hooks.has_commit_hooks()
+            || self.txn.changes().recorder().is_some()
+        {
+            self.txn.tx_id().unwrap_or_default() as u64
+        } else {
+            0
+        };
+        let latency_ptr = latency.as_mut().map_or(ptr::null_mut(), |l| l.mdb_commit_latency());
+
+        let res = self.with_txn_ptr(|txn| {
             if K::IS_READ_ONLY {
-                mdbx_result(unsafe { ffi::mdbx_txn_commit_ex(txn, latency) })
+                mdbx_result(unsafe { ffi::mdbx_txn_commit_ex(txn, latency_ptr) })
             } else {
                 let (sender, rx) = sync_channel(0);
                 self.env().txn_manager().send(Commit {
                     tx: RawTxPtr(txn),
-                    latency: CommitLatencyPtr(latency),
+                    latency: CommitLatencyPtr(latency_ptr),
                     span: debug_span!("tx_manager_commit"),
                     sender,
                 });
                 rx.recv().unwrap()
             }
-        })?;
+        });
+        let was_aborted = match res {
+            Ok(was_aborted) => was_aborted,
+            Err(err) => {
+                self.txn.hooks().restore(hooks);
+                return Err(err);
+            }
+        };
 
         self.txn.mark_committed();
 
@@ -514,13 +973,18 @@ where
             return Err(MdbxError::BotchedTransaction);
         }
 
+        self.publish_dbs(txn_id);
+        self.txn.changes().committed(txn_id);
+        self.txn.hooks().committed(hooks, || {
+            CommitInfo::new(txn_id, latency.map_or_else(CommitLatency::new, |l| *l))
+        });
         Ok(())
     }
 
     /// Commits the transaction.
     #[instrument(skip(self), parent = &self.meta.span)]
     pub fn commit(self) -> MdbxResult<()> {
-        self.commit_inner(ptr::null_mut())
+        self.commit_inner(None)
     }
 
     /// Commits the transaction, returning commit latency information.
@@ -528,7 +992,7 @@ where
     pub fn commit_with_latency(self) -> MdbxResult<CommitLatency> {
         let mut latency = CommitLatency::new();
 
-        self.commit_inner(latency.mdb_commit_latency())?;
+        self.commit_inner(Some(&mut latency))?;
 
         tracing::debug!(latency_whole_ms = latency.whole().as_millis(), "commit latency");
         Ok(latency)
@@ -540,14 +1004,42 @@ where
     K: TransactionKind<Access = PtrUnsync>,
 {
     /// Commits the transaction (inner implementation).
-    fn commit_inner(self, latency: *mut ffi::MDBX_commit_latency) -> MdbxResult<()> {
+    ///
+    /// Runs the commit callbacks once the commit succeeds.
+    fn commit_inner(self, latency: Option<&mut CommitLatency>) -> MdbxResult<()> {
         // Self is dropped at end of function, so RwTxPtr::drop will be within
         // span scope.
         let _guard = self.meta.span.clone().entered();
 
+        if self.txn.detach_cancel() {
+            return Err(MdbxError::TxnBroken);
+        }
+
+        // If the commit fails, the callbacks are restored, so that the abort
+        // callbacks run once the transaction is aborted.
+        let hooks = self.txn.hooks().take();
+        let mut hook_latency = CommitLatency::new();
+        let mut latency =
+            if hooks.has_commit_hooks() { latency.or(Some(&mut hook_latency)) } else { latency };
+        let txn_id = if !K::IS_READ_ONLY
+            || hooks.has_commit_hooks()
+            || self.txn.changes().recorder().is_some()
+        {
+            self.txn.tx_id().unwrap_or_default() as u64
+        } else {
+            0
+        };
+        let latency_ptr = latency.as_mut().map_or(ptr::null_mut(), |l| l.mdb_commit_latency());
+
         // SAFETY: txn_ptr is valid from with_txn_ptr.
-        let was_aborted =
-            self.with_txn_ptr(|txn_ptr| unsafe { ops::commit_raw(txn_ptr, latency) })?;
+        let res = self.with_txn_ptr(|txn_ptr| unsafe { ops::commit_raw(txn_ptr, latency_ptr) });
+        let was_aborted = match res {
+            Ok(was_aborted) => was_aborted,
+            Err(err) => {
+                self.txn.hooks().restore(hooks);
+                return Err(err);
+            }
+        };
 
         self.txn.mark_committed();
 
@@ -556,13 +1048,18 @@ where
             return Err(MdbxError::BotchedTransaction);
         }
 
+        self.publish_dbs(txn_id);
+        self.txn.changes().committed(txn_id);
+        self.txn.hooks().committed(hooks, || {
+            CommitInfo::new(txn_id, latency.map_or_else(CommitLatency::new, |l| *l))
+        });
         Ok(())
     }
 
     /// Commits the transaction.
     #[instrument(skip(self), parent = &self.meta.span)]
     pub fn commit(self) -> MdbxResult<()> {
-        self.commit_inner(ptr::null_mut())
+        self.commit_inner(None)
     }
 
     /// Commits the transaction, returning commit latency information.
@@ -570,7 +1067,7 @@ where
     pub fn commit_with_latency(self) -> MdbxResult<CommitLatency> {
         let mut latency = CommitLatency::new();
 
-        self.commit_inner(latency.mdb_commit_latency())?;
+        self.commit_inner(Some(&mut latency))?;
 
         tracing::debug!(latency_whole_ms = latency.whole().as_millis(), "commit latency");
         Ok(latency)
@@ -584,30 +1081,64 @@ where
     K: TransactionKind<Access = Arc<PtrSync>> + WriteMarker,
 {
     /// Begins a new [`RwTxSync`](crate::tx::aliases::RwTxSync) transaction.
+    ///
+    /// Fails with [`MdbxError::WriteTxnAlreadyHeldByThread`] if the calling
+    /// thread opened the write transaction that is currently open.
+    ///
+    /// The transaction is `Send`, so the thread that opened it is only
+    /// recorded until the transaction is first used from another thread.
+    /// From then on, the opener waits for it like any other thread.
+    #[track_caller]
     pub fn begin(env: Environment) -> MdbxResult<Self> {
-        let mut warned = false;
-        let txn = loop {
-            let (tx, rx) = sync_channel(0);
-            env.txn_manager().send(Begin {
-                parent: RawTxPtr(ptr::null_mut()),
-                flags: Rw::OPEN_FLAGS,
-                sender: tx,
-                span: debug_span!("txn_manager_begin"),
-            });
-            let res = rx.recv().unwrap();
-            if matches!(&res, Err(MdbxError::Busy)) {
-                if !warned {
-                    warned = true;
-                    warn!(target: "libmdbx", "Process stalled, awaiting read-write transaction lock.");
-                }
-                sleep(Duration::from_millis(250));
-                continue;
-            }
+        let opened_at = Location::caller();
+        env.rw_txn_owner().check()?;
+        begin_rw_with_backoff(&env, None, || Self::begin_with_flags(&env, Rw::OPEN_FLAGS))
+            .map(|tx| tx.with_sync_owner(opened_at))
+    }
+
+    /// Begins a transaction, failing with [`MdbxError::Busy`] instead of
+    /// blocking if the write lock is held.
+    #[track_caller]
+    pub(crate) fn try_begin(env: Environment) -> MdbxResult<Self> {
+        let opened_at = Location::caller();
+        env.rw_txn_owner().check()?;
+        Self::begin_with_flags(&env, Rw::OPEN_FLAGS | ffi::MDBX_TXN_TRY)
+            .map(|tx| tx.with_sync_owner(opened_at))
+    }
+
+    /// Begins a transaction, waiting at most `timeout` for the write lock.
+    #[track_caller]
+    pub(crate) fn begin_timeout(env: Environment, timeout: Duration) -> MdbxResult<Self> {
+        let opened_at = Location::caller();
+        env.rw_txn_owner().check()?;
+        let deadline = Instant::now() + timeout;
+        begin_rw_with_backoff(&env, Some(deadline), || {
+            Self::begin_with_flags(&env, Rw::OPEN_FLAGS | ffi::MDBX_TXN_TRY)
+        })
+        .map(|tx| tx.with_sync_owner(opened_at))
+    }
 
-            break res;
-        }?;
+    /// Records the current thread as the owner of this top-level
+    /// transaction, until it is first used from another thread.
+    fn with_sync_owner(self, opened_at: &'static Location<'static>) -> Self {
+        self.txn.track_owner();
+        let txn = self.txn.with_txn_ptr(|txn| txn);
+        self.env().rw_txn_owner().acquire(txn, opened_at);
+        self
+    }
 
-        Ok(Self::from_ptr_and_env(txn.0, env))
+    /// Makes a single attempt to begin a transaction via the txn manager,
+    /// without checking or recording the owning thread.
+    fn begin_with_flags(env: &Environment, flags: ffi::MDBX_txn_flags_t) -> MdbxResult<Self> {
+        let (tx, rx) = sync_channel(0);
+        env.txn_manager().send(Begin {
+            parent: RawTxPtr(ptr::null_mut()),
+            flags,
+            sender: tx,
+            span: debug_span!("txn_manager_begin"),
+        });
+        let txn = rx.recv().unwrap()?;
+        Ok(Self::from_ptr_and_env(txn.0, env.clone()))
     }
 
     /// Begins a new nested transaction inside of this transaction.
@@ -624,9 +1155,35 @@ where
                 span: debug_span!("tx_manager_begin_nested"),
             });
 
-            rx.recv().unwrap().map(|txn| Self::from_ptr_and_env(txn.0, self.env().clone()))
+            let nested =
+                rx.recv().unwrap().map(|txn| Self::from_ptr_and_env(txn.0, self.env().clone()))?;
+            nested.txn.hooks().set_parent(self.txn.hooks());
+            nested.txn.changes().set_parent(self.txn.changes());
+            nested.txn.set_cancel_parent(self.txn.cancel_state());
+            Ok(nested)
         })
     }
+
+    /// Begins a [`Savepoint`] inside of this transaction.
+    ///
+    /// In WRITEMAP environments, where nested transactions are unsupported,
+    /// the savepoint is emulated with an undo log.
+    pub fn begin_savepoint(&self) -> MdbxResult<Savepoint<'_, K>> {
+        if self.env().is_write_map() {
+            return Ok(Savepoint::emulated(self));
+        }
+        self.begin_nested_txn().map(|txn| Savepoint::nested(txn, Self::commit))
+    }
+
+    /// Runs `f` in a [`Savepoint`], keeping its writes if it returns `Ok`,
+    /// and rolling them back if it returns `Err` or panics.
+    pub fn savepoint<F, T, E>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Savepoint<'_, K>) -> Result<T, E>,
+        E: From<MdbxError>,
+    {
+        self.begin_savepoint()?.run(f)
+    }
 }
 
 impl<K> Tx<K, PtrUnsync>
@@ -649,10 +1206,68 @@ where
                     &mut nested_txn,
                     ptr::null_mut(),
                 ))?;
-                Ok(Self::from_ptr_and_env(nested_txn, self.env().clone()))
+                let nested = Self::from_ptr_and_env(nested_txn, self.env().clone());
+                nested.txn.hooks().set_parent(self.txn.hooks());
+                nested.txn.changes().set_parent(self.txn.changes());
+                nested.txn.set_cancel_parent(self.txn.cancel_state());
+                Ok(nested)
             }
         })
     }
+
+    /// Begins a [`Savepoint`] inside of this transaction.
+    ///
+    /// In WRITEMAP environments, where nested transactions are unsupported,
+    /// the savepoint is emulated with an undo log.
+    pub fn begin_savepoint(&mut self) -> MdbxResult<Savepoint<'_, K>> {
+        if self.env().is_write_map() {
+            return Ok(Savepoint::emulated(self));
+        }
+        self.begin_nested_txn().map(|txn| Savepoint::nested(txn, Self::commit))
+    }
+
+    /// Runs `f` in a [`Savepoint`], keeping its writes if it returns `Ok`,
+    /// and rolling them back if it returns `Err` or panics.
+    pub fn savepoint<F, T, E>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Savepoint<'_, K>) -> Result<T, E>,
+        E: From<MdbxError>,
+    {
+        self.begin_savepoint()?.run(f)
+    }
+}
+
+/// Retries `begin` while the write lock is busy, sleeping for the
+/// environment's backoff interval between attempts.
+///
+/// Gives up with [`MdbxError::Busy`] once `deadline` has passed.
+fn begin_rw_with_backoff<T>(
+    env: &Environment,
+    deadline: Option<Instant>,
+    mut begin: impl FnMut() -> MdbxResult<T>,
+) -> MdbxResult<T> {
+    let mut warned = false;
+    loop {
+        match begin() {
+            Err(MdbxError::Busy) => {}
+            res => return res,
+        }
+
+        let mut backoff = env.rw_txn_backoff();
+        if let Some(deadline) = deadline {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Err(MdbxError::Busy);
+            }
+            backoff = backoff.min(remaining);
+        }
+
+        if !warned && env.rw_txn_wait_warning() {
+            warned = true;
+            warn!(target: "libmdbx", "Process stalled, awaiting read-write transaction lock.");
+        }
+        sleep(backoff);
+    }
 }
 
 #[cfg(test)]
@@ -677,13 +1292,13 @@ mod tests {
         let txn = TxUnsync::<Ro>::begin(env.clone()).unwrap();
 
         let db = txn.open_db(None).unwrap();
-        let value: Option<Vec<u8>> = txn.get(db.dbi(), b"key1").unwrap();
+        let value: Option<Vec<u8>> = txn.get(db, b"key1").unwrap();
         assert_eq!(value.as_deref(), Some(b"value1".as_slice()));
 
-        let value: Option<Vec<u8>> = txn.get(db.dbi(), b"key2").unwrap();
+        let value: Option<Vec<u8>> = txn.get(db, b"key2").unwrap();
         assert_eq!(value.as_deref(), Some(b"value2".as_slice()));
 
-        let value: Option<Vec<u8>> = txn.get(db.dbi(), b"nonexistent").unwrap();
+        let value: Option<Vec<u8>> = txn.get(db, b"nonexistent").unwrap();
         assert!(value.is_none());
     }
 
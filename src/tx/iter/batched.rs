@@ -0,0 +1,191 @@
+//! Batched iterator for non-DUPSORT tables.
+
+use crate::{
+    Cursor, MdbxError, ReadError, ReadResult, TableObject, TableObjectOwned, TransactionKind,
+    tx::TxPtrAccess,
+};
+use std::{collections::VecDeque, marker::PhantomData, ptr};
+
+/// An iterator over the key/value pairs of a table without
+/// [`DatabaseFlags::DUP_SORT`], fetching pairs in batches.
+///
+/// Where [`Iter`] makes one FFI call per pair, this iterator fills a buffer
+/// of up to `batch_size` pairs with a single call to
+/// `mdbx_cursor_get_batch`, and yields them one at a time. Each batch is
+/// decoded as it is fetched, so borrowed values follow the same dirty-page
+/// rules as [`Iter`].
+///
+/// While iterating, the cursor is positioned after the last pair fetched,
+/// which may be ahead of the last pair yielded.
+///
+/// Iterating a [`DatabaseFlags::DUP_SORT`] table returns
+/// [`MdbxError::Incompatible`].
+///
+/// [`DatabaseFlags::DUP_SORT`]: crate::DatabaseFlags::DUP_SORT
+/// [`Iter`]: super::Iter
+pub struct IterBatched<
+    'tx,
+    'cur,
+    K: TransactionKind,
+    Key = std::borrow::Cow<'tx, [u8]>,
+    Value = std::borrow::Cow<'tx, [u8]>,
+> {
+    cursor: &'cur mut Cursor<'tx, K>,
+    /// Keys and values filled by `mdbx_cursor_get_batch`, interleaved.
+    pairs: Vec<ffi::MDBX_val>,
+    /// Decoded pairs not yet yielded.
+    buffer: VecDeque<(Key, Value)>,
+    /// A decoding error, yielded after the pairs decoded before it.
+    error: Option<ReadError>,
+    /// When true, no more batches will be fetched.
+    exhausted: bool,
+    _marker: PhantomData<fn() -> (Key, Value)>,
+}
+
+impl<K, Key, Value> core::fmt::Debug for IterBatched<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterBatched")
+            .field("batch_size", &self.batch_size())
+            .field("buffered", &self.buffer.len())
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, Value> IterBatched<'tx, 'cur, K, Key, Value>
+where
+    K: TransactionKind,
+{
+    /// Create a new iterator, fetching batches from the item the cursor is
+    /// positioned on.
+    pub(crate) fn new(cursor: &'cur mut Cursor<'tx, K>, batch_size: usize) -> Self {
+        let empty = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
+        IterBatched {
+            cursor,
+            pairs: vec![empty; batch_size.max(2) * 2],
+            buffer: VecDeque::new(),
+            error: None,
+            exhausted: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new iterator that is already exhausted.
+    ///
+    /// Iteration will immediately return `None`.
+    pub(crate) fn new_end(cursor: &'cur mut Cursor<'tx, K>, batch_size: usize) -> Self {
+        let mut iter = Self::new(cursor, batch_size);
+        iter.exhausted = true;
+        iter
+    }
+
+    /// Returns the maximum number of pairs fetched per batch.
+    pub const fn batch_size(&self) -> usize {
+        self.pairs.len() / 2
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, Value> IterBatched<'tx, 'cur, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObject<'tx>,
+    Value: TableObject<'tx>,
+{
+    /// Fetch and decode the next batch of pairs.
+    fn refill(&mut self) -> ReadResult<()> {
+        let cursor = self.cursor.cursor();
+        let pairs = &mut self.pairs;
+        let buffer = &mut self.buffer;
+
+        let (last, error) = self.cursor.access().try_with_txn_ptr(|txn| {
+            let mut count = 0;
+            // SAFETY: pairs holds pairs.len() values, and the cursor is valid
+            // within try_with_txn_ptr.
+            let rc = unsafe {
+                ffi::mdbx_cursor_get_batch(
+                    cursor,
+                    &mut count,
+                    pairs.as_mut_ptr(),
+                    pairs.len(),
+                    ffi::MDBX_NEXT,
+                )
+            };
+            let last = match rc {
+                ffi::MDBX_SUCCESS => false,
+                ffi::MDBX_RESULT_TRUE => true,
+                ffi::MDBX_NOTFOUND | ffi::MDBX_ENODATA => return Ok((true, None)),
+                other => return Err(ReadError::from(MdbxError::from_err_code(other))),
+            };
+
+            for pair in pairs[..count].chunks_exact(2) {
+                // SAFETY: decode_val checks for dirty writes and copies if
+                // needed. The lifetime 'tx guarantees the Cow cannot outlive
+                // the transaction.
+                let decoded = unsafe {
+                    TableObject::decode_val::<K>(txn, pair[0])
+                        .and_then(|key| Ok((key, TableObject::decode_val::<K>(txn, pair[1])?)))
+                };
+                match decoded {
+                    Ok(item) => buffer.push_back(item),
+                    Err(err) => return Ok((true, Some(err))),
+                }
+            }
+            Ok((last, None))
+        })?;
+
+        self.exhausted = last;
+        self.error = error;
+        Ok(())
+    }
+
+    /// Borrow the next key/value pair from the iterator.
+    ///
+    /// Returns `Ok(Some((key, value)))` if a key/value pair was found,
+    /// `Ok(None)` if no more key/value pairs are available, or `Err` on DB
+    /// access error.
+    pub fn borrow_next(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Ok(Some(item));
+            }
+            if let Some(err) = self.error.take() {
+                return Err(err);
+            }
+            if self.exhausted {
+                return Ok(None);
+            }
+            if let Err(err) = self.refill() {
+                self.exhausted = true;
+                return Err(err);
+            }
+        }
+    }
+}
+
+impl<K, Key, Value> IterBatched<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    /// Own the next key/value pair from the iterator.
+    pub fn owned_next(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        self.borrow_next()
+    }
+}
+
+impl<K, Key, Value> Iterator for IterBatched<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    type Item = ReadResult<(Key, Value)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.owned_next().transpose()
+    }
+}
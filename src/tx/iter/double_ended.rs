@@ -0,0 +1,181 @@
+//! Double-ended iterator bounded by a second cursor.
+
+use crate::{Cursor, ReadResult, TableObject, TableObjectOwned, TransactionKind};
+use std::{cmp::Ordering, marker::PhantomData};
+
+/// A double-ended iterator over the key/value pairs in an MDBX database.
+///
+/// The front cursor moves forward from the first item, and the back cursor
+/// moves backward from the last item. Iteration ends when the cursors meet,
+/// so every item is yielded exactly once from one end or the other.
+///
+/// For databases with duplicate data items ([`DatabaseFlags::DUP_SORT`]),
+/// each duplicate is a separate item.
+///
+/// # Example
+///
+/// ```no_run
+/// # use signet_libmdbx::Environment;
+/// # use std::path::Path;
+/// # let env = Environment::builder().open(Path::new("/tmp/double_ended_example")).unwrap();
+/// let txn = env.begin_ro_sync().unwrap();
+/// let db = txn.open_db(None).unwrap();
+/// let mut front = txn.cursor(db).unwrap();
+/// let mut back = txn.cursor(db).unwrap();
+///
+/// let mut iter = front.iter_double_ended::<Vec<u8>, Vec<u8>>(&mut back).unwrap();
+/// let first = iter.next();
+/// let last = iter.next_back();
+/// ```
+///
+/// [`DatabaseFlags::DUP_SORT`]: crate::DatabaseFlags::DUP_SORT
+pub struct IterDoubleEnded<
+    'tx,
+    'cur,
+    K: TransactionKind,
+    Key = std::borrow::Cow<'tx, [u8]>,
+    Value = std::borrow::Cow<'tx, [u8]>,
+> {
+    /// Positioned on the next item to yield from the front.
+    front: &'cur mut Cursor<'tx, K>,
+    /// Positioned on the next item to yield from the back.
+    back: &'cur mut Cursor<'tx, K>,
+    /// When true, the iterator is exhausted and will always return `None`.
+    exhausted: bool,
+    _marker: PhantomData<fn() -> (Key, Value)>,
+}
+
+impl<K, Key, Value> core::fmt::Debug for IterDoubleEnded<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterDoubleEnded").field("exhausted", &self.exhausted).finish()
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, Value> IterDoubleEnded<'tx, 'cur, K, Key, Value>
+where
+    K: TransactionKind,
+{
+    /// Create a new iterator from cursors positioned on the first and last
+    /// items of the range.
+    pub(crate) const fn new(
+        front: &'cur mut Cursor<'tx, K>,
+        back: &'cur mut Cursor<'tx, K>,
+    ) -> Self {
+        IterDoubleEnded { front, back, exhausted: false, _marker: PhantomData }
+    }
+
+    /// Create a new iterator that is already exhausted.
+    ///
+    /// Iteration will immediately return `None`.
+    pub(crate) const fn new_end(
+        front: &'cur mut Cursor<'tx, K>,
+        back: &'cur mut Cursor<'tx, K>,
+    ) -> Self {
+        IterDoubleEnded { front, back, exhausted: true, _marker: PhantomData }
+    }
+
+    /// Compares the positions of the front and back cursors.
+    fn compare(&self) -> ReadResult<Ordering> {
+        Ok(self.front.compare(self.back, false)?)
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, Value> IterDoubleEnded<'tx, 'cur, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObject<'tx>,
+    Value: TableObject<'tx>,
+{
+    /// Yields the item under one cursor, then moves it towards the other.
+    fn step(&mut self, from_back: bool) -> ReadResult<Option<(Key, Value)>> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let ord = self.compare()?;
+        if ord == Ordering::Greater {
+            self.exhausted = true;
+            return Ok(None);
+        }
+
+        let cursor = if from_back { &mut *self.back } else { &mut *self.front };
+        let Some(item) = cursor.get_current::<Key, Value>()? else {
+            self.exhausted = true;
+            return Ok(None);
+        };
+
+        // The cursors met, so this is the last item.
+        if ord == Ordering::Equal {
+            self.exhausted = true;
+            return Ok(Some(item));
+        }
+
+        let moved = if from_back { cursor.prev::<(), ()>()? } else { cursor.next::<(), ()>()? };
+        if moved.is_none() {
+            self.exhausted = true;
+        }
+        Ok(Some(item))
+    }
+
+    /// Borrow the next key/value pair from the front of the iterator.
+    ///
+    /// Returns `Ok(Some((key, value)))` if a key/value pair was found,
+    /// `Ok(None)` if no more key/value pairs are available, or `Err` on DB
+    /// access error.
+    pub fn borrow_next(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        self.step(false)
+    }
+
+    /// Borrow the next key/value pair from the back of the iterator.
+    ///
+    /// Returns `Ok(Some((key, value)))` if a key/value pair was found,
+    /// `Ok(None)` if no more key/value pairs are available, or `Err` on DB
+    /// access error.
+    pub fn borrow_next_back(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        self.step(true)
+    }
+}
+
+impl<K, Key, Value> IterDoubleEnded<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    /// Own the next key/value pair from the front of the iterator.
+    pub fn owned_next(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        self.step(false)
+    }
+
+    /// Own the next key/value pair from the back of the iterator.
+    pub fn owned_next_back(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        self.step(true)
+    }
+}
+
+impl<K, Key, Value> Iterator for IterDoubleEnded<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    type Item = ReadResult<(Key, Value)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.owned_next().transpose()
+    }
+}
+
+impl<K, Key, Value> DoubleEndedIterator for IterDoubleEnded<'_, '_, K, Key, Value>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.owned_next_back().transpose()
+    }
+}
@@ -153,7 +153,7 @@ where
         let mut key = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
         let mut data = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
 
-        self.cursor.access().with_txn_ptr(|txn| {
+        self.cursor.access().try_with_txn_ptr(|txn| {
             let res = unsafe {
                 ffi::mdbx_cursor_get(self.cursor.cursor(), &mut key, &mut data, ffi::MDBX_NEXT)
             };
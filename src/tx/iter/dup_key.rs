@@ -11,6 +11,9 @@ use std::{marker::PhantomData, ptr};
 /// `(Key, Value)` pairs, this iterator only yields values for a single key.
 /// When all values for that key are exhausted, iteration stops.
 ///
+/// The iteration order is determined by the `OP` const generic parameter,
+/// either `MDBX_NEXT_DUP` or `MDBX_PREV_DUP`.
+///
 /// # Type Parameters
 ///
 /// - `'tx`: The transaction lifetime
@@ -43,7 +46,13 @@ use std::{marker::PhantomData, ptr};
 ///     println!("value: {:?}", value);
 /// }
 /// ```
-pub struct IterDupOfKey<'tx, 'cur, K: TransactionKind, Value = std::borrow::Cow<'tx, [u8]>> {
+pub struct IterDupOfKey<
+    'tx,
+    'cur,
+    K: TransactionKind,
+    Value = std::borrow::Cow<'tx, [u8]>,
+    const OP: u32 = { ffi::MDBX_NEXT_DUP },
+> {
     cursor: &'cur mut Cursor<'tx, K>,
     /// Pre-fetched value from cursor positioning, yielded before calling FFI.
     pending: Option<Value>,
@@ -52,7 +61,7 @@ pub struct IterDupOfKey<'tx, 'cur, K: TransactionKind, Value = std::borrow::Cow<
     _marker: PhantomData<fn() -> Value>,
 }
 
-impl<K, Value> core::fmt::Debug for IterDupOfKey<'_, '_, K, Value>
+impl<K, Value, const OP: u32> core::fmt::Debug for IterDupOfKey<'_, '_, K, Value, OP>
 where
     K: TransactionKind,
 {
@@ -61,7 +70,7 @@ where
     }
 }
 
-impl<'tx: 'cur, 'cur, K, Value> IterDupOfKey<'tx, 'cur, K, Value>
+impl<'tx: 'cur, 'cur, K, Value, const OP: u32> IterDupOfKey<'tx, 'cur, K, Value, OP>
 where
     K: TransactionKind,
 {
@@ -78,20 +87,19 @@ where
     }
 }
 
-impl<'tx: 'cur, 'cur, K, Value> IterDupOfKey<'tx, 'cur, K, Value>
+impl<'tx: 'cur, 'cur, K, Value, const OP: u32> IterDupOfKey<'tx, 'cur, K, Value, OP>
 where
     K: TransactionKind,
     Value: TableObject<'tx>,
 {
-    /// Execute MDBX_NEXT_DUP and decode the value.
-    fn execute_next_dup(&self) -> ReadResult<Option<Value>> {
+    /// Execute the MDBX operation and decode the value.
+    fn execute_op(&self) -> ReadResult<Option<Value>> {
         let mut key = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
         let mut data = ffi::MDBX_val { iov_len: 0, iov_base: ptr::null_mut() };
 
-        self.cursor.access().with_txn_ptr(|txn| {
-            let res = unsafe {
-                ffi::mdbx_cursor_get(self.cursor.cursor(), &mut key, &mut data, ffi::MDBX_NEXT_DUP)
-            };
+        self.cursor.access().try_with_txn_ptr(|txn| {
+            let res =
+                unsafe { ffi::mdbx_cursor_get(self.cursor.cursor(), &mut key, &mut data, OP) };
 
             match res {
                 ffi::MDBX_SUCCESS => {
@@ -120,7 +128,7 @@ where
         if let Some(v) = self.pending.take() {
             return Ok(Some(v));
         }
-        let result = self.execute_next_dup()?;
+        let result = self.execute_op()?;
         if result.is_none() {
             self.exhausted = true;
         }
@@ -128,7 +136,7 @@ where
     }
 }
 
-impl<K, Value> IterDupOfKey<'_, '_, K, Value>
+impl<K, Value, const OP: u32> IterDupOfKey<'_, '_, K, Value, OP>
 where
     K: TransactionKind,
     Value: TableObjectOwned,
@@ -141,7 +149,7 @@ where
         if let Some(v) = self.pending.take() {
             return Ok(Some(v));
         }
-        let result = self.execute_next_dup()?;
+        let result = self.execute_op()?;
         if result.is_none() {
             self.exhausted = true;
         }
@@ -149,7 +157,7 @@ where
     }
 }
 
-impl<K, Value> Iterator for IterDupOfKey<'_, '_, K, Value>
+impl<K, Value, const OP: u32> Iterator for IterDupOfKey<'_, '_, K, Value, OP>
 where
     K: TransactionKind,
     Value: TableObjectOwned,
@@ -15,6 +15,11 @@ use std::{borrow::Cow, marker::PhantomData};
 /// for the first value of each key, and [`DupItem::SameKey`] for subsequent
 /// values of the same key.
 ///
+/// The iteration order is determined by the `OP` const generic parameter.
+/// With `MDBX_NEXT_MULTIPLE`, keys and their values are yielded in ascending
+/// order. With `MDBX_PREV_MULTIPLE`, both are yielded in descending order,
+/// and [`DupItem::NewKey`] carries the last value of each key.
+///
 /// # Type Parameters
 ///
 /// - `'tx`: The transaction lifetime
@@ -72,14 +77,21 @@ use std::{borrow::Cow, marker::PhantomData};
 ///     }
 /// }
 /// ```
-pub struct IterDupFixed<'tx, 'cur, K: TransactionKind, Key = Cow<'tx, [u8]>, Value = Cow<'tx, [u8]>>
-{
+pub struct IterDupFixed<
+    'tx,
+    'cur,
+    K: TransactionKind,
+    Key = Cow<'tx, [u8]>,
+    Value = Cow<'tx, [u8]>,
+    const OP: u32 = { ffi::MDBX_NEXT_MULTIPLE },
+> {
     cursor: &'cur mut Cursor<'tx, K>,
-    /// The current key being iterated.
+    /// The current key, taken when its first value is yielded.
     current_key: Option<Key>,
     /// The current page of values.
     current_page: Cow<'tx, [u8]>,
-    /// Current offset into the page, incremented as values are yielded.
+    /// Current offset into the page, moved towards the end of the page (or
+    /// its start, in reverse) as values are yielded.
     page_offset: usize,
     /// The fixed value size, determined at construction.
     value_size: usize,
@@ -90,30 +102,28 @@ pub struct IterDupFixed<'tx, 'cur, K: TransactionKind, Key = Cow<'tx, [u8]>, Val
     _marker: PhantomData<fn() -> (Key, Value)>,
 }
 
-impl<K, Key, Value> core::fmt::Debug for IterDupFixed<'_, '_, K, Key, Value>
+impl<K, Key, Value, const OP: u32> core::fmt::Debug for IterDupFixed<'_, '_, K, Key, Value, OP>
 where
     K: TransactionKind,
     Key: core::fmt::Debug,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let remaining_in_page = if self.value_size > 0 {
-            self.current_page.len().saturating_sub(self.page_offset) / self.value_size
-        } else {
-            0
-        };
         f.debug_struct("IterDupFixed")
             .field("exhausted", &self.exhausted)
             .field("value_size", &self.value_size)
-            .field("remaining_in_page", &remaining_in_page)
+            .field("remaining_in_page", &self.remaining_in_page())
             .field("remaining_for_key", &self.remaining)
             .finish()
     }
 }
 
-impl<'tx: 'cur, 'cur, K, Key, Value> IterDupFixed<'tx, 'cur, K, Key, Value>
+impl<'tx: 'cur, 'cur, K, Key, Value, const OP: u32> IterDupFixed<'tx, 'cur, K, Key, Value, OP>
 where
     K: TransactionKind,
 {
+    /// Whether values are yielded in descending order.
+    const REVERSE: bool = OP == ffi::MDBX_PREV_MULTIPLE;
+
     /// Returns the fixed value size (determined at construction).
     pub const fn value_size(&self) -> usize {
         self.value_size
@@ -145,20 +155,42 @@ where
         debug_assert!(value_size > 0, "DUPFIXED value size must be greater than zero");
         // Get the count of duplicates for the current key.
         let remaining = cursor.dup_count().unwrap_or(1);
+        let page_offset = Self::page_start(&page);
         IterDupFixed {
             cursor,
             current_key: Some(key),
             current_page: page,
-            page_offset: 0,
+            page_offset,
             value_size,
             remaining,
             exhausted: false,
             _marker: PhantomData,
         }
     }
+
+    /// Returns the offset values are consumed from in a freshly fetched page.
+    const fn page_start(page: &[u8]) -> usize {
+        if Self::REVERSE { page.len() } else { 0 }
+    }
+
+    /// Returns the number of values left in the current page.
+    fn remaining_in_page(&self) -> usize {
+        let bytes = if Self::REVERSE {
+            self.page_offset
+        } else {
+            self.current_page.len().saturating_sub(self.page_offset)
+        };
+        bytes.checked_div(self.value_size).unwrap_or(0)
+    }
+
+    /// Replaces the current page.
+    fn set_page(&mut self, page: Cow<'tx, [u8]>) {
+        self.page_offset = Self::page_start(&page);
+        self.current_page = page;
+    }
 }
 
-impl<'tx: 'cur, 'cur, K, Key, Value> IterDupFixed<'tx, 'cur, K, Key, Value>
+impl<'tx: 'cur, 'cur, K, Key, Value, const OP: u32> IterDupFixed<'tx, 'cur, K, Key, Value, OP>
 where
     K: TransactionKind,
     Key: TableObject<'tx>,
@@ -168,13 +200,17 @@ where
     /// Returns `Some(Cow<'tx, [u8]>)` containing exactly `value_size` bytes,
     /// or `None` if the page is exhausted.
     fn consume_value(&mut self) -> Option<Cow<'tx, [u8]>> {
-        let end = self.page_offset.checked_add(self.value_size)?;
+        let (start, end) = if Self::REVERSE {
+            let start = self.page_offset.checked_sub(self.value_size)?;
+            (start, self.page_offset)
+        } else {
+            (self.page_offset, self.page_offset.checked_add(self.value_size)?)
+        };
         if end > self.current_page.len() {
             return None;
         }
 
-        let start = self.page_offset;
-        self.page_offset = end;
+        self.page_offset = if Self::REVERSE { start } else { end };
 
         match &self.current_page {
             Cow::Borrowed(slice) => Some(Cow::Borrowed(&slice[start..end])),
@@ -184,27 +220,37 @@ where
 
     /// Fetch the next page of values.
     ///
-    /// First tries `next_multiple` to get more pages for the current key.
-    /// If that fails, moves to the next key with `next_nodup` and fetches
-    /// its first page with `get_multiple`.
+    /// First tries `next_multiple` (or `prev_multiple`) to get more pages for
+    /// the current key. If that fails, moves to the next key with
+    /// `next_nodup` (or `prev_nodup`, which positions at the last value of
+    /// the previous key) and fetches the page holding the cursor's value with
+    /// `get_multiple`.
     ///
     /// Returns `Ok(true)` if a new page was fetched, `Ok(false)` if exhausted.
     fn fetch_next_page(&mut self) -> ReadResult<bool> {
         // Try to get next page for current key
-        if let Some((key, page)) = self.cursor.next_multiple::<Key, Cow<'tx, [u8]>>()? {
-            self.current_key = Some(key);
-            self.current_page = page;
-            self.page_offset = 0;
+        let page = if Self::REVERSE {
+            self.cursor.prev_multiple::<(), Cow<'tx, [u8]>>()?
+        } else {
+            self.cursor.next_multiple::<(), Cow<'tx, [u8]>>()?
+        };
+        if let Some((_, page)) = page {
+            self.set_page(page);
             return Ok(true);
         }
 
         // No more pages for current key, move to next key
-        if self.cursor.next_nodup::<Key, ()>()?.is_none() {
+        let moved = if Self::REVERSE {
+            self.cursor.prev_nodup::<(), ()>()?
+        } else {
+            self.cursor.next_nodup::<(), ()>()?
+        };
+        if moved.is_none() {
             self.exhausted = true;
             return Ok(false);
         }
 
-        // Get first page for new key
+        // Get the page holding the cursor's value for the new key
         let Some(page) = self.cursor.get_multiple::<Cow<'tx, [u8]>>()? else {
             self.exhausted = true;
             return Ok(false);
@@ -220,8 +266,7 @@ where
         self.remaining = self.cursor.dup_count().unwrap_or(1);
 
         self.current_key = Some(key);
-        self.current_page = page;
-        self.page_offset = 0;
+        self.set_page(page);
         Ok(true)
     }
 
@@ -248,23 +293,13 @@ where
             }
         };
 
-        if self.remaining == 0 {
-            // This is a new key (we got here via fetch_next_page which set remaining)
-            self.remaining = self.remaining.saturating_sub(1);
-            let key = self.current_key.take().expect("key should be set after fetch");
-            return Ok(Some(DupItem::NewKey(key, value)));
-        }
+        self.remaining = self.remaining.saturating_sub(1);
 
-        // Check if this is the first value for the current key
-        // (remaining was just set and key is present)
-        if self.current_key.is_some() {
-            self.remaining -= 1;
-            let key = self.current_key.take().expect("key should be set");
-            return Ok(Some(DupItem::NewKey(key, value)));
+        // The key is present until the first value of a new key is yielded.
+        match self.current_key.take() {
+            Some(key) => Ok(Some(DupItem::NewKey(key, value))),
+            None => Ok(Some(DupItem::SameKey(value))),
         }
-
-        self.remaining = self.remaining.saturating_sub(1);
-        Ok(Some(DupItem::SameKey(value)))
     }
 
     /// Get the next item as owned data.
@@ -284,7 +319,8 @@ where
     }
 }
 
-impl<'tx: 'cur, 'cur, K, Key, Value> Iterator for IterDupFixed<'tx, 'cur, K, Key, Value>
+impl<'tx: 'cur, 'cur, K, Key, Value, const OP: u32> Iterator
+    for IterDupFixed<'tx, 'cur, K, Key, Value, OP>
 where
     K: TransactionKind,
     Key: TableObject<'tx>,
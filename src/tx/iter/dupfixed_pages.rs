@@ -0,0 +1,158 @@
+//! Page iterator for DUPFIXED tables.
+
+use crate::{
+    Cursor, FixedValue, ReadResult, TableObject, TableObjectOwned, TransactionKind, tx::ops,
+};
+use ffi::MDBX_txn;
+use std::{borrow::Cow, marker::PhantomData, slice};
+
+/// A page of fixed-size values, borrowed in place when possible.
+pub(crate) struct FixedPage<'tx, T: FixedValue>(pub(crate) Cow<'tx, [T]>);
+
+impl<'tx, T: FixedValue> TableObject<'tx> for FixedPage<'tx, T> {
+    fn decode_borrow(data: Cow<'tx, [u8]>) -> ReadResult<Self> {
+        match data {
+            Cow::Borrowed(data) => T::decode_slice(data).map(Self),
+            Cow::Owned(data) => T::copy_slice(&data).map(|values| Self(Cow::Owned(values))),
+        }
+    }
+
+    unsafe fn decode_val<K: TransactionKind>(
+        txn: *const MDBX_txn,
+        data_val: ffi::MDBX_val,
+    ) -> ReadResult<Self> {
+        // SAFETY: Caller ensures the tx is active, slice is valid for lifetime
+        // 'tx.
+        let data =
+            unsafe { slice::from_raw_parts(data_val.iov_base as *const u8, data_val.iov_len) };
+
+        // SAFETY: txn is valid from caller, data_val.iov_base points to db pages.
+        let is_dirty = (!K::IS_READ_ONLY) && unsafe { ops::is_dirty_raw(txn, data_val.iov_base) }?;
+
+        // Dirty pages are copied straight into values, rather than into bytes
+        // first.
+        if is_dirty {
+            T::copy_slice(data).map(|values| Self(Cow::Owned(values)))
+        } else {
+            T::decode_slice(data).map(Self)
+        }
+    }
+}
+
+/// An iterator over the pages of a DUPFIXED table, as typed slices.
+///
+/// Where [`IterDupFixed`] splits each page into values one by one, this
+/// iterator yields each page whole, as a slice of [`FixedValue`]s, along with
+/// the key it belongs to. A key whose values span several pages is yielded
+/// once per page.
+///
+/// Pages are borrowed in place when they are clean and suitably aligned for
+/// `T`. Dirty pages, and pages that are not aligned for `T`, are copied into
+/// an owned [`Vec`]. Values in MDBX pages are only 4-byte aligned in
+/// practice, so pages of types such as `u64` are usually copied.
+///
+/// [`IterDupFixed`]: super::IterDupFixed
+pub struct IterDupFixedPages<'tx, 'cur, K: TransactionKind, Key = Cow<'tx, [u8]>, T = u8>
+where
+    T: FixedValue,
+{
+    cursor: &'cur mut Cursor<'tx, K>,
+    /// The page the cursor was positioned on, yielded first.
+    first: Option<(Key, Cow<'tx, [T]>)>,
+    /// When true, no more pages will be fetched.
+    exhausted: bool,
+    _marker: PhantomData<fn() -> (Key, T)>,
+}
+
+impl<K, Key, T> core::fmt::Debug for IterDupFixedPages<'_, '_, K, Key, T>
+where
+    K: TransactionKind,
+    T: FixedValue,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterDupFixedPages").field("exhausted", &self.exhausted).finish()
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, T> IterDupFixedPages<'tx, 'cur, K, Key, T>
+where
+    K: TransactionKind,
+    T: FixedValue,
+{
+    /// Create a new iterator, yielding the given page first.
+    pub(crate) const fn new_with(
+        cursor: &'cur mut Cursor<'tx, K>,
+        key: Key,
+        page: Cow<'tx, [T]>,
+    ) -> Self {
+        IterDupFixedPages {
+            cursor,
+            first: Some((key, page)),
+            exhausted: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new iterator that is already exhausted.
+    ///
+    /// Iteration will immediately return `None`.
+    pub(crate) const fn new_end(cursor: &'cur mut Cursor<'tx, K>) -> Self {
+        IterDupFixedPages { cursor, first: None, exhausted: true, _marker: PhantomData }
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, T> IterDupFixedPages<'tx, 'cur, K, Key, T>
+where
+    K: TransactionKind,
+    Key: TableObject<'tx>,
+    T: FixedValue,
+{
+    /// Fetch the next page, moving to the next key when the pages of the
+    /// current key are exhausted.
+    fn fetch_next_page(&mut self) -> ReadResult<Option<(Key, Cow<'tx, [T]>)>> {
+        if let Some((key, FixedPage(page))) =
+            self.cursor.next_multiple::<Key, FixedPage<'tx, T>>()?
+        {
+            return Ok(Some((key, page)));
+        }
+        if self.cursor.next_nodup::<(), ()>()?.is_none() {
+            return Ok(None);
+        }
+        let Some(FixedPage(page)) = self.cursor.get_multiple::<FixedPage<'tx, T>>()? else {
+            return Ok(None);
+        };
+        // Re-fetch the key since get_multiple doesn't return it
+        Ok(self.cursor.get_current::<Key, ()>()?.map(|(key, ())| (key, page)))
+    }
+
+    /// Borrow the next page from the iterator.
+    ///
+    /// Returns `Ok(Some((key, values)))` if a page was found, `Ok(None)` if
+    /// no more pages are available, or `Err` on DB access error.
+    pub fn borrow_next(&mut self) -> ReadResult<Option<(Key, Cow<'tx, [T]>)>> {
+        if let Some(first) = self.first.take() {
+            return Ok(Some(first));
+        }
+        if self.exhausted {
+            return Ok(None);
+        }
+        let page = self.fetch_next_page();
+        if !matches!(page, Ok(Some(_))) {
+            self.exhausted = true;
+        }
+        page
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, T> Iterator for IterDupFixedPages<'tx, 'cur, K, Key, T>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    T: FixedValue,
+{
+    type Item = ReadResult<(Key, Cow<'tx, [T]>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.borrow_next().transpose()
+    }
+}
@@ -8,10 +8,15 @@
 //! | Iterator | Yields | Use Case |
 //! |----------|--------|----------|
 //! | [`Iter`] | `(Key, Value)` | Base iterator, configurable cursor op |
+//! | [`IterBatched`] | `(Key, Value)` | Batched iteration over non-DUPSORT tables |
+//! | [`IterDoubleEnded`] | `(Key, Value)` | Iteration from both ends, bounded by a second cursor |
+//! | [`IterRange`] | `(Key, Value)` | Iteration over a key range or prefix |
+//! | [`IterDupRange`] | `Value` | Iteration over a value range of a single DUPSORT key |
 //! | [`IterDup`] | `(Key, Value)` | Flat iteration over DUPSORT tables |
 //! | [`IterDupOfKey`] | `Value` | Single-key DUPSORT iteration |
 //! | [`IterDupFixed`] | `(Key, Value)` | Flat iteration over DUPFIXED tables |
 //! | [`IterDupFixedOfKey`] | `Value` | Single-key DUPFIXED iteration |
+//! | [`IterDupFixedPages`] | `(Key, Cow<[T]>)` | Page-at-a-time DUPFIXED iteration as typed slices |
 //!
 //! # Borrowing vs Owning
 //!
@@ -53,6 +58,16 @@
 mod base;
 pub use base::Iter;
 
+mod batched;
+pub use batched::IterBatched;
+
+mod double_ended;
+pub use double_ended::IterDoubleEnded;
+
+mod range;
+pub(crate) use range::Stop;
+pub use range::{IterDupRange, IterRange};
+
 mod dup;
 pub use dup::IterDup;
 
@@ -65,6 +80,10 @@ pub use dupfixed::IterDupFixed;
 mod dupfixed_key;
 pub use dupfixed_key::IterDupFixedOfKey;
 
+mod dupfixed_pages;
+pub(crate) use dupfixed_pages::FixedPage;
+pub use dupfixed_pages::IterDupFixedPages;
+
 /// An item from a duplicate-key iterator.
 ///
 /// This enum avoids cloning the key for every value when iterating
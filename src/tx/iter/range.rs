@@ -0,0 +1,486 @@
+//! Bounded range and prefix iterators.
+
+use crate::{
+    Cursor, DatabaseFlags, MdbxError, MdbxResult, ReadResult, TableObject, TableObjectOwned,
+    TransactionKind, tx::TxPtrAccess,
+};
+use ffi::{MDBX_cursor_op, MDBX_val};
+use std::{ffi::c_int, marker::PhantomData, ops::Bound, ptr};
+
+/// A comparison function with the signature of `mdbx_cmp` and `mdbx_dcmp`.
+type CmpFn = unsafe extern "C" fn(
+    *const ffi::MDBX_txn,
+    ffi::MDBX_dbi,
+    *const MDBX_val,
+    *const MDBX_val,
+) -> c_int;
+
+/// Where a range iterator stops.
+#[derive(Debug)]
+pub(crate) enum Stop {
+    /// Stop at the first item beyond this bound, in iteration order.
+    Bound(Bound<Vec<u8>>),
+    /// Stop at the first key that does not start with this prefix, or that
+    /// does not end with it if `suffix` is set.
+    Prefix {
+        /// The bytes keys must share.
+        prefix: Vec<u8>,
+        /// Whether the prefix is matched against the end of keys, as for
+        /// [`DatabaseFlags::REVERSE_KEY`] tables.
+        suffix: bool,
+    },
+}
+
+impl Stop {
+    /// Returns whether `val` is within the range, comparing with `cmp`.
+    ///
+    /// # Safety
+    ///
+    /// `txn` must be the live transaction of the table `dbi`, and `val` must
+    /// point to valid memory.
+    pub(crate) unsafe fn contains(
+        &self,
+        cmp: CmpFn,
+        txn: *const ffi::MDBX_txn,
+        dbi: ffi::MDBX_dbi,
+        val: &MDBX_val,
+        reverse: bool,
+    ) -> bool {
+        match self {
+            Self::Bound(Bound::Unbounded) => true,
+            Self::Bound(Bound::Included(bound) | Bound::Excluded(bound)) => {
+                let bound_val = slice_val(bound);
+                // SAFETY: Guaranteed by the caller.
+                let ord = unsafe { cmp(txn, dbi, val, &bound_val) };
+                let ord = if reverse { -ord.signum() } else { ord.signum() };
+                ord < 0 || (ord == 0 && matches!(self, Self::Bound(Bound::Included(_))))
+            }
+            Self::Prefix { prefix, suffix } => {
+                // SAFETY: Guaranteed by the caller.
+                let bytes = unsafe { val_bytes(val) };
+                if *suffix { bytes.ends_with(prefix) } else { bytes.starts_with(prefix) }
+            }
+        }
+    }
+}
+
+/// Returns an [`MDBX_val`] pointing at `slice`.
+const fn slice_val(slice: &[u8]) -> MDBX_val {
+    MDBX_val { iov_len: slice.len(), iov_base: slice.as_ptr() as *mut _ }
+}
+
+/// Returns the bytes `val` points at.
+///
+/// # Safety
+///
+/// `val` must point to memory valid for the returned borrow.
+const unsafe fn val_bytes(val: &MDBX_val) -> &[u8] {
+    if val.iov_base.is_null() {
+        return &[];
+    }
+    // SAFETY: Guaranteed by the caller.
+    unsafe { std::slice::from_raw_parts(val.iov_base as *const u8, val.iov_len) }
+}
+
+/// Runs a cursor operation, returning whether an item was found.
+///
+/// # Safety
+///
+/// `cursor` must be valid, within its transaction's `try_with_txn_ptr`.
+unsafe fn read(
+    cursor: *mut ffi::MDBX_cursor,
+    key: &mut MDBX_val,
+    data: &mut MDBX_val,
+    op: MDBX_cursor_op,
+) -> MdbxResult<bool> {
+    // SAFETY: Guaranteed by the caller.
+    match unsafe { ffi::mdbx_cursor_get(cursor, key, data, op) } {
+        ffi::MDBX_SUCCESS => Ok(true),
+        ffi::MDBX_NOTFOUND | ffi::MDBX_ENODATA | ffi::MDBX_RESULT_TRUE => Ok(false),
+        other => Err(MdbxError::from_err_code(other)),
+    }
+}
+
+const fn empty_val() -> MDBX_val {
+    MDBX_val { iov_len: 0, iov_base: ptr::null_mut() }
+}
+
+/// An iterator over the key/value pairs in a range of keys.
+///
+/// Unlike [`Iter`](super::Iter), this iterator stops at the end of the range,
+/// comparing keys with the table's comparator, so ranges over
+/// [`DatabaseFlags::REVERSE_KEY`] and [`DatabaseFlags::INTEGER_KEY`] tables
+/// follow the table's order.
+///
+/// The iteration order is determined by the `OP` const generic parameter,
+/// either `MDBX_NEXT` or `MDBX_PREV`. For databases with duplicate data items
+/// ([`DatabaseFlags::DUP_SORT`]), every duplicate of each key in the range is
+/// yielded.
+///
+/// # Example
+///
+/// ```no_run
+/// # use signet_libmdbx::Environment;
+/// # use std::path::Path;
+/// # let env = Environment::builder().open(Path::new("/tmp/range_example")).unwrap();
+/// let txn = env.begin_ro_sync().unwrap();
+/// let db = txn.open_db(None).unwrap();
+/// let mut cursor = txn.cursor(db).unwrap();
+///
+/// for result in cursor.range::<Vec<u8>, Vec<u8>>(&b"a"[..]..&b"m"[..]).unwrap() {
+///     let (key, value) = result.unwrap();
+///     println!("{:?} => {:?}", key, value);
+/// }
+/// ```
+pub struct IterRange<
+    'tx,
+    'cur,
+    K: TransactionKind,
+    Key = std::borrow::Cow<'tx, [u8]>,
+    Value = std::borrow::Cow<'tx, [u8]>,
+    const OP: u32 = { ffi::MDBX_NEXT },
+> {
+    cursor: &'cur mut Cursor<'tx, K>,
+    /// Pre-fetched value from cursor positioning, yielded before calling FFI.
+    pending: Option<(Key, Value)>,
+    /// Where iteration stops.
+    stop: Stop,
+    /// When true, the iterator is exhausted and will always return `None`.
+    exhausted: bool,
+    _marker: PhantomData<fn() -> (Key, Value)>,
+}
+
+impl<K, Key, Value, const OP: u32> core::fmt::Debug for IterRange<'_, '_, K, Key, Value, OP>
+where
+    K: TransactionKind,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterRange")
+            .field("stop", &self.stop)
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Key, Value, const OP: u32> IterRange<'tx, 'cur, K, Key, Value, OP>
+where
+    K: TransactionKind,
+    Key: TableObject<'tx>,
+    Value: TableObject<'tx>,
+{
+    /// Whether keys are yielded in descending order.
+    const REVERSE: bool = OP == ffi::MDBX_PREV;
+
+    /// Create a new iterator, positioning the cursor on the first item at or
+    /// beyond `start` in iteration order.
+    pub(crate) fn new(
+        cursor: &'cur mut Cursor<'tx, K>,
+        start: Bound<&[u8]>,
+        stop: Stop,
+    ) -> ReadResult<Self> {
+        let dup_sort = cursor.db_flags().contains(DatabaseFlags::DUP_SORT);
+        let first = cursor.access().try_with_txn_ptr(|txn| -> ReadResult<_> {
+            let c = cursor.cursor();
+            let dbi = cursor.db().dbi();
+            let mut key = empty_val();
+            let mut data = empty_val();
+
+            // SAFETY: The cursor is valid within try_with_txn_ptr, and the
+            // bound outlives the calls reading it.
+            let found = unsafe {
+                match start {
+                    Bound::Unbounded => {
+                        let op = if Self::REVERSE { ffi::MDBX_LAST } else { ffi::MDBX_FIRST };
+                        read(c, &mut key, &mut data, op)?
+                    }
+                    Bound::Included(bound) | Bound::Excluded(bound) => {
+                        let bound_val = slice_val(bound);
+                        key = bound_val;
+                        let found = read(c, &mut key, &mut data, ffi::MDBX_SET_RANGE)?;
+                        let equal = found && ffi::mdbx_cmp(txn, dbi, &key, &bound_val) == 0;
+                        let included = matches!(start, Bound::Included(_));
+                        match (Self::REVERSE, found) {
+                            (false, _) if equal && !included => {
+                                read(c, &mut key, &mut data, ffi::MDBX_NEXT_NODUP)?
+                            }
+                            (false, found) => found,
+                            // Every key is less than the bound.
+                            (true, false) => read(c, &mut key, &mut data, ffi::MDBX_LAST)?,
+                            (true, true) if equal && included => {
+                                !dup_sort || read(c, &mut key, &mut data, ffi::MDBX_LAST_DUP)?
+                            }
+                            (true, true) => read(c, &mut key, &mut data, ffi::MDBX_PREV)?,
+                        }
+                    }
+                }
+            };
+
+            // SAFETY: key points into the transaction's pages.
+            if !found || !unsafe { stop.contains(ffi::mdbx_cmp, txn, dbi, &key, Self::REVERSE) } {
+                return Ok(None);
+            }
+            // SAFETY: decode_val checks for dirty writes and copies if needed.
+            // The lifetime 'tx guarantees the Cow cannot outlive the transaction.
+            unsafe {
+                let key = TableObject::decode_val::<K>(txn, key)?;
+                let data = TableObject::decode_val::<K>(txn, data)?;
+                Ok(Some((key, data)))
+            }
+        })?;
+
+        Ok(IterRange {
+            cursor,
+            exhausted: first.is_none(),
+            pending: first,
+            stop,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Execute the MDBX operation and decode the result, if it is within the
+    /// range.
+    fn execute_op(&self) -> ReadResult<Option<(Key, Value)>> {
+        let mut key = empty_val();
+        let mut data = empty_val();
+
+        self.cursor.access().try_with_txn_ptr(|txn| {
+            // SAFETY: The cursor is valid within try_with_txn_ptr.
+            if !unsafe { read(self.cursor.cursor(), &mut key, &mut data, OP)? } {
+                return Ok(None);
+            }
+            let dbi = self.cursor.db().dbi();
+            // SAFETY: key points into the transaction's pages.
+            if !unsafe { self.stop.contains(ffi::mdbx_cmp, txn, dbi, &key, Self::REVERSE) } {
+                return Ok(None);
+            }
+            // SAFETY: decode_val checks for dirty writes and copies if needed.
+            // The lifetime 'tx guarantees the Cow cannot outlive the transaction.
+            unsafe {
+                let key = TableObject::decode_val::<K>(txn, key)?;
+                let data = TableObject::decode_val::<K>(txn, data)?;
+                Ok(Some((key, data)))
+            }
+        })
+    }
+
+    /// Borrow the next key/value pair from the iterator.
+    ///
+    /// Returns `Ok(Some((key, value)))` if a key/value pair was found,
+    /// `Ok(None)` if the end of the range has been reached, or `Err` on DB
+    /// access error.
+    pub fn borrow_next(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        if self.exhausted {
+            return Ok(None);
+        }
+        if let Some(v) = self.pending.take() {
+            return Ok(Some(v));
+        }
+        let result = self.execute_op()?;
+        if result.is_none() {
+            self.exhausted = true;
+        }
+        Ok(result)
+    }
+}
+
+impl<K, Key, Value, const OP: u32> IterRange<'_, '_, K, Key, Value, OP>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    /// Own the next key/value pair from the iterator.
+    pub fn owned_next(&mut self) -> ReadResult<Option<(Key, Value)>> {
+        self.borrow_next()
+    }
+}
+
+impl<K, Key, Value, const OP: u32> Iterator for IterRange<'_, '_, K, Key, Value, OP>
+where
+    K: TransactionKind,
+    Key: TableObjectOwned,
+    Value: TableObjectOwned,
+{
+    type Item = ReadResult<(Key, Value)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.owned_next().transpose()
+    }
+}
+
+/// An iterator over a range of the values of a single key in a
+/// [`DatabaseFlags::DUP_SORT`] table.
+///
+/// Values are compared with the table's value comparator, so ranges over
+/// [`DatabaseFlags::REVERSE_DUP`] and [`DatabaseFlags::INTEGER_DUP`] tables
+/// follow the table's order.
+///
+/// The iteration order is determined by the `OP` const generic parameter,
+/// either `MDBX_NEXT_DUP` or `MDBX_PREV_DUP`.
+pub struct IterDupRange<
+    'tx,
+    'cur,
+    K: TransactionKind,
+    Value = std::borrow::Cow<'tx, [u8]>,
+    const OP: u32 = { ffi::MDBX_NEXT_DUP },
+> {
+    cursor: &'cur mut Cursor<'tx, K>,
+    /// Pre-fetched value from cursor positioning, yielded before calling FFI.
+    pending: Option<Value>,
+    /// Where iteration stops.
+    stop: Stop,
+    /// When true, the iterator is exhausted and will always return `None`.
+    exhausted: bool,
+    _marker: PhantomData<fn() -> Value>,
+}
+
+impl<K, Value, const OP: u32> core::fmt::Debug for IterDupRange<'_, '_, K, Value, OP>
+where
+    K: TransactionKind,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterDupRange")
+            .field("stop", &self.stop)
+            .field("exhausted", &self.exhausted)
+            .finish()
+    }
+}
+
+impl<'tx: 'cur, 'cur, K, Value, const OP: u32> IterDupRange<'tx, 'cur, K, Value, OP>
+where
+    K: TransactionKind,
+    Value: TableObject<'tx>,
+{
+    /// Whether values are yielded in descending order.
+    const REVERSE: bool = OP == ffi::MDBX_PREV_DUP;
+
+    /// Create a new iterator, positioning the cursor on the first value of
+    /// `key` at or beyond `start` in iteration order.
+    pub(crate) fn new(
+        cursor: &'cur mut Cursor<'tx, K>,
+        key: &[u8],
+        start: Bound<&[u8]>,
+        stop: Bound<Vec<u8>>,
+    ) -> ReadResult<Self> {
+        let stop = Stop::Bound(stop);
+        let first = cursor.access().try_with_txn_ptr(|txn| -> ReadResult<_> {
+            let c = cursor.cursor();
+            let dbi = cursor.db().dbi();
+            let mut key_val = slice_val(key);
+            let mut data = empty_val();
+
+            // SAFETY: The cursor is valid within try_with_txn_ptr, and the
+            // key and bound outlive the calls reading them.
+            let found = unsafe {
+                let last_of_key = |key_val: &mut MDBX_val, data: &mut MDBX_val| {
+                    *key_val = slice_val(key);
+                    Ok::<_, MdbxError>(
+                        read(c, key_val, data, ffi::MDBX_SET_KEY)?
+                            && read(c, key_val, data, ffi::MDBX_LAST_DUP)?,
+                    )
+                };
+                match start {
+                    Bound::Unbounded if Self::REVERSE => last_of_key(&mut key_val, &mut data)?,
+                    Bound::Unbounded => read(c, &mut key_val, &mut data, ffi::MDBX_SET_KEY)?,
+                    Bound::Included(bound) | Bound::Excluded(bound) => {
+                        let bound_val = slice_val(bound);
+                        data = bound_val;
+                        let found = read(c, &mut key_val, &mut data, ffi::MDBX_GET_BOTH_RANGE)?;
+                        let equal = found && ffi::mdbx_dcmp(txn, dbi, &data, &bound_val) == 0;
+                        let included = matches!(start, Bound::Included(_));
+                        match (Self::REVERSE, found) {
+                            (false, _) if equal && !included => {
+                                read(c, &mut key_val, &mut data, ffi::MDBX_NEXT_DUP)?
+                            }
+                            (false, found) => found,
+                            // Every value is less than the bound, or the key
+                            // is absent.
+                            (true, false) => last_of_key(&mut key_val, &mut data)?,
+                            (true, true) if equal && included => true,
+                            (true, true) => read(c, &mut key_val, &mut data, ffi::MDBX_PREV_DUP)?,
+                        }
+                    }
+                }
+            };
+
+            // SAFETY: data points into the transaction's pages.
+            if !found || !unsafe { stop.contains(ffi::mdbx_dcmp, txn, dbi, &data, Self::REVERSE) } {
+                return Ok(None);
+            }
+            // SAFETY: decode_val checks for dirty writes and copies if needed.
+            // The lifetime 'tx guarantees the Cow cannot outlive the transaction.
+            unsafe { TableObject::decode_val::<K>(txn, data).map(Some) }
+        })?;
+
+        Ok(IterDupRange {
+            cursor,
+            exhausted: first.is_none(),
+            pending: first,
+            stop,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Execute the MDBX operation and decode the value, if it is within the
+    /// range.
+    fn execute_op(&self) -> ReadResult<Option<Value>> {
+        let mut key = empty_val();
+        let mut data = empty_val();
+
+        self.cursor.access().try_with_txn_ptr(|txn| {
+            // SAFETY: The cursor is valid within try_with_txn_ptr.
+            if !unsafe { read(self.cursor.cursor(), &mut key, &mut data, OP)? } {
+                return Ok(None);
+            }
+            let dbi = self.cursor.db().dbi();
+            // SAFETY: data points into the transaction's pages.
+            if !unsafe { self.stop.contains(ffi::mdbx_dcmp, txn, dbi, &data, Self::REVERSE) } {
+                return Ok(None);
+            }
+            // SAFETY: decode_val checks for dirty writes and copies if needed.
+            // The lifetime 'tx guarantees the Cow cannot outlive the transaction.
+            unsafe { TableObject::decode_val::<K>(txn, data).map(Some) }
+        })
+    }
+
+    /// Borrow the next value from the iterator.
+    ///
+    /// Returns `Ok(Some(value))` if a value was found, `Ok(None)` if the end
+    /// of the range has been reached, or `Err` on DB access error.
+    pub fn borrow_next(&mut self) -> ReadResult<Option<Value>> {
+        if self.exhausted {
+            return Ok(None);
+        }
+        if let Some(v) = self.pending.take() {
+            return Ok(Some(v));
+        }
+        let result = self.execute_op()?;
+        if result.is_none() {
+            self.exhausted = true;
+        }
+        Ok(result)
+    }
+}
+
+impl<K, Value, const OP: u32> IterDupRange<'_, '_, K, Value, OP>
+where
+    K: TransactionKind,
+    Value: TableObjectOwned,
+{
+    /// Own the next value from the iterator.
+    pub fn owned_next(&mut self) -> ReadResult<Option<Value>> {
+        self.borrow_next()
+    }
+}
+
+impl<K, Value, const OP: u32> Iterator for IterDupRange<'_, '_, K, Value, OP>
+where
+    K: TransactionKind,
+    Value: TableObjectOwned,
+{
+    type Item = ReadResult<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.owned_next().transpose()
+    }
+}
@@ -5,9 +5,21 @@
 //! - [`aliases::TxSync`] - Thread-safe synchronized transaction
 //! - [`aliases::TxUnsync`] - Single-threaded unsynchronized transaction
 //! - [`Cursor`] - Database cursor for navigating entries
+//! - [`UnboundCursor`] - Cursor reused across transactions
 //! - [`Database`] - Handle to an opened database
 //! - [`Ro`], [`Rw`], [`RoSync`], [`RwSync`] - Transaction kind markers
 //! - [`CommitLatency`] - Commit timing information
+//! - [`CommitInfo`] - Information passed to commit callbacks
+//! - [`ChangeSet`], [`Change`], [`ChangeOp`] - Writes recorded for change
+//!   data capture
+//! - [`CancelHandle`] - Cross-thread cancellation of write transactions
+//! - [`WriteBatcher`], [`WriteBatch`] - Group commit for concurrent writers
+//! - [`BulkLoader`], [`LoadStats`], [`LoadPosition`], [`BulkLoadError`] -
+//!   Chunked loading of sorted data
+//! - [`ScanControl`], [`ScanStart`], [`ScanTurn`] - Predicate scanning with
+//!   [`Cursor::scan`]
+//! - [`Savepoint`] - Rollback of a block of writes within a transaction
+//! - [`ScanChunk`] - One chunk of a range scanned in parallel
 //!
 //! # Type Aliases
 //!
@@ -30,6 +42,9 @@
 
 mod assertions;
 
+mod batch;
+pub use batch::{WriteBatch, WriteBatcher};
+
 mod access;
 pub use access::{PtrSync, PtrUnsync, TxPtrAccess};
 
@@ -37,9 +52,21 @@ pub mod aliases;
 
 pub mod cache;
 
+mod bulk;
+pub use bulk::{BulkLoadError, BulkLoader, LoadPosition, LoadStats};
+
+mod cancel;
+pub use cancel::CancelHandle;
+
+mod changes;
+pub use changes::{Change, ChangeOp, ChangeSet};
+
 mod cursor;
 pub use cursor::Cursor;
 
+mod unbound;
+pub use unbound::UnboundCursor;
+
 mod database;
 pub use database::Database;
 
@@ -48,9 +75,21 @@ pub mod iter;
 mod kind;
 pub use kind::{Ro, RoSync, Rw, RwSync, SyncKind, TransactionKind, WriteMarker, WriterKind};
 
+mod hooks;
+pub use hooks::CommitInfo;
+
 mod lat;
 pub use lat::CommitLatency;
 
+mod predicate;
+pub use predicate::{ScanControl, ScanStart, ScanTurn};
+
+mod savepoint;
+pub use savepoint::Savepoint;
+
+pub(crate) mod scan;
+pub use scan::ScanChunk;
+
 /// Raw operations on transactions.
 pub mod ops;
 
@@ -58,7 +58,8 @@ pub(crate) unsafe fn get_raw(
 
 /// Opens a database and retrieves its flags.
 ///
-/// Returns `(dbi, flags)` on success.
+/// Returns `(dbi, flags, created)` on success, where `created` is `true` if
+/// the table was created by this transaction.
 ///
 /// # Safety
 ///
@@ -69,20 +70,22 @@ pub(crate) unsafe fn open_db_raw(
     txn: *mut ffi::MDBX_txn,
     name_ptr: *const c_char,
     flags: DatabaseFlags,
-) -> MdbxResult<(ffi::MDBX_dbi, DatabaseFlags)> {
+) -> MdbxResult<(ffi::MDBX_dbi, DatabaseFlags, bool)> {
     let mut dbi: ffi::MDBX_dbi = 0;
     let mut actual_flags: c_uint = 0;
-    let mut _status: c_uint = 0;
+    let mut status: c_uint = 0;
 
     // SAFETY: Caller guarantees txn is valid, name_ptr is valid or null.
     mdbx_result(unsafe { ffi::mdbx_dbi_open(txn, name_ptr, flags.bits(), &mut dbi) })?;
     // SAFETY: Caller guarantees txn is valid, dbi was just opened.
-    mdbx_result(unsafe { ffi::mdbx_dbi_flags_ex(txn, dbi, &mut actual_flags, &mut _status) })?;
+    mdbx_result(unsafe { ffi::mdbx_dbi_flags_ex(txn, dbi, &mut actual_flags, &mut status) })?;
 
     #[cfg_attr(not(windows), allow(clippy::useless_conversion))]
     let db_flags = DatabaseFlags::from_bits_truncate(actual_flags.try_into().unwrap());
 
-    Ok((dbi, db_flags))
+    let created = status & ffi::MDBX_DBI_CREAT as c_uint != 0;
+
+    Ok((dbi, db_flags, created))
 }
 
 /// Retrieves the flags for an open database.
@@ -120,6 +123,78 @@ pub(crate) unsafe fn db_stat_raw(txn: *mut ffi::MDBX_txn, dbi: ffi::MDBX_dbi) ->
     Ok(stat)
 }
 
+/// Estimates the number of items between two keys.
+///
+/// A `None` bound stands for the start or the end of the database.
+///
+/// # Safety
+///
+/// - `txn` must be a valid, non-null transaction pointer.
+/// - `dbi` must be a valid database handle for this transaction.
+#[inline(always)]
+pub(crate) unsafe fn estimate_range_raw(
+    txn: *mut ffi::MDBX_txn,
+    dbi: ffi::MDBX_dbi,
+    begin: Option<&[u8]>,
+    end: Option<&[u8]>,
+) -> MdbxResult<isize> {
+    let val = |key: &[u8]| ffi::MDBX_val { iov_len: key.len(), iov_base: key.as_ptr() as *mut _ };
+    let (begin, end) = (begin.map(val), end.map(val));
+    let mut distance: isize = 0;
+    // SAFETY: Caller guarantees txn and dbi are valid, and the keys outlive
+    // the call.
+    mdbx_result(unsafe {
+        ffi::mdbx_estimate_range(
+            txn,
+            dbi,
+            begin.as_ref().map_or(ptr::null(), ptr::from_ref),
+            ptr::null(),
+            end.as_ref().map_or(ptr::null(), ptr::from_ref),
+            ptr::null(),
+            &mut distance,
+        )
+    })?;
+    Ok(distance)
+}
+
+/// Compares two keys in the order of a table, with `mdbx_cmp`.
+///
+/// # Safety
+///
+/// - `txn` must be a valid, non-null transaction pointer.
+/// - `dbi` must be a valid database handle for this transaction.
+#[inline(always)]
+pub(crate) unsafe fn cmp_keys_raw(
+    txn: *const ffi::MDBX_txn,
+    dbi: ffi::MDBX_dbi,
+    a: &[u8],
+    b: &[u8],
+) -> std::cmp::Ordering {
+    let a = ffi::MDBX_val { iov_len: a.len(), iov_base: a.as_ptr() as *mut c_void };
+    let b = ffi::MDBX_val { iov_len: b.len(), iov_base: b.as_ptr() as *mut c_void };
+    // SAFETY: Caller guarantees txn and dbi are valid.
+    unsafe { ffi::mdbx_cmp(txn, dbi, &a, &b) }.cmp(&0)
+}
+
+/// Compares two duplicate values in the order of a table, with `mdbx_dcmp`.
+///
+/// # Safety
+///
+/// - `txn` must be a valid, non-null transaction pointer.
+/// - `dbi` must be a valid database handle for this transaction.
+#[inline(always)]
+pub(crate) unsafe fn cmp_values_raw(
+    txn: *const ffi::MDBX_txn,
+    dbi: ffi::MDBX_dbi,
+    a: &[u8],
+    b: &[u8],
+) -> std::cmp::Ordering {
+    let a = ffi::MDBX_val { iov_len: a.len(), iov_base: a.as_ptr() as *mut c_void };
+    let b = ffi::MDBX_val { iov_len: b.len(), iov_base: b.as_ptr() as *mut c_void };
+    // SAFETY: Caller guarantees txn and dbi are valid.
+    unsafe { ffi::mdbx_dcmp(txn, dbi, &a, &b) }.cmp(&0)
+}
+
 /// Commits a transaction.
 ///
 /// Returns `true` if the transaction was aborted (botched), `false` otherwise.
@@ -399,7 +474,16 @@ pub(crate) unsafe fn debug_assert_append(
     let pagesize = unsafe { get_pagesize(txn) };
     // SAFETY: Caller guarantees txn and dbi are valid.
     let last_key = unsafe { get_last_key(txn, dbi) };
-    crate::tx::assertions::debug_assert_append(pagesize, flags, key, data, last_key.as_deref());
+    // SAFETY: Caller guarantees txn and dbi are valid.
+    let cmp = |a: &[u8], b: &[u8]| unsafe { cmp_keys_raw(txn, dbi, a, b) };
+    crate::tx::assertions::debug_assert_append(
+        pagesize,
+        flags,
+        key,
+        data,
+        last_key.as_deref(),
+        cmp,
+    );
 }
 
 /// Get the count of duplicates for the current key.
@@ -439,5 +523,14 @@ pub(crate) unsafe fn debug_assert_append_dup(
     let pagesize = unsafe { get_pagesize(txn) };
     // SAFETY: Caller guarantees txn and dbi are valid.
     let last_dup = unsafe { get_last_dup(txn, dbi, key) };
-    crate::tx::assertions::debug_assert_append_dup(pagesize, flags, key, data, last_dup.as_deref());
+    // SAFETY: Caller guarantees txn and dbi are valid.
+    let cmp = |a: &[u8], b: &[u8]| unsafe { cmp_values_raw(txn, dbi, a, b) };
+    crate::tx::assertions::debug_assert_append_dup(
+        pagesize,
+        flags,
+        key,
+        data,
+        last_dup.as_deref(),
+        cmp,
+    );
 }
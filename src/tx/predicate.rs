@@ -0,0 +1,181 @@
+//! Predicate scanning for [`Cursor::scan`] and [`Cursor::scan_from`].
+//!
+//! The predicate is a Rust closure, called from the libmdbx scan loop
+//! through [`trampoline`]. Panics are caught before they reach C, and
+//! resumed once libmdbx has returned.
+//!
+//! [`Cursor::scan`]: crate::Cursor::scan
+//! [`Cursor::scan_from`]: crate::Cursor::scan_from
+
+use ffi::MDBX_cursor_op;
+use std::{
+    any::Any,
+    ffi::{c_int, c_void},
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+/// Returned by the trampoline after the predicate panicked. libmdbx returns
+/// codes other than `MDBX_RESULT_TRUE` and `MDBX_RESULT_FALSE` unchanged.
+const SCAN_PANICKED: c_int = -96100;
+
+/// The decision of a [`Cursor::scan`] predicate.
+///
+/// [`Cursor::scan`]: crate::Cursor::scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanControl {
+    /// Move to the next item.
+    Continue,
+    /// Stop, leaving the cursor on the current item.
+    Stop,
+}
+
+/// Where [`Cursor::scan`] starts.
+///
+/// [`Cursor::scan`]: crate::Cursor::scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanStart {
+    /// The first item of the table.
+    First,
+    /// The first value of the current key. [`DatabaseFlags::DUP_SORT`] only.
+    ///
+    /// [`DatabaseFlags::DUP_SORT`]: crate::DatabaseFlags::DUP_SORT
+    FirstDup,
+    /// The last item of the table.
+    Last,
+    /// The last value of the current key. [`DatabaseFlags::DUP_SORT`] only.
+    ///
+    /// [`DatabaseFlags::DUP_SORT`]: crate::DatabaseFlags::DUP_SORT
+    LastDup,
+    /// The item the cursor is positioned on.
+    Current,
+}
+
+impl ScanStart {
+    pub(crate) const fn op(self) -> MDBX_cursor_op {
+        match self {
+            Self::First => ffi::MDBX_FIRST,
+            Self::FirstDup => ffi::MDBX_FIRST_DUP,
+            Self::Last => ffi::MDBX_LAST,
+            Self::LastDup => ffi::MDBX_LAST_DUP,
+            Self::Current => ffi::MDBX_GET_CURRENT,
+        }
+    }
+}
+
+/// How [`Cursor::scan`] moves between items.
+///
+/// [`Cursor::scan`]: crate::Cursor::scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanTurn {
+    /// The next item.
+    Next,
+    /// The next value of the current key.
+    NextDup,
+    /// The first value of the next key.
+    NextNoDup,
+    /// The previous item.
+    Prev,
+    /// The previous value of the current key.
+    PrevDup,
+    /// The last value of the previous key.
+    PrevNoDup,
+}
+
+impl ScanTurn {
+    pub(crate) const fn op(self) -> MDBX_cursor_op {
+        match self {
+            Self::Next => ffi::MDBX_NEXT,
+            Self::NextDup => ffi::MDBX_NEXT_DUP,
+            Self::NextNoDup => ffi::MDBX_NEXT_NODUP,
+            Self::Prev => ffi::MDBX_PREV,
+            Self::PrevDup => ffi::MDBX_PREV_DUP,
+            Self::PrevNoDup => ffi::MDBX_PREV_NODUP,
+        }
+    }
+
+    /// The operation positioning [`Cursor::scan_from`] at its key: the first
+    /// key at or after it when moving forward, and the last key at or before
+    /// it when moving backward.
+    ///
+    /// [`Cursor::scan_from`]: crate::Cursor::scan_from
+    pub(crate) const fn seek_op(self) -> MDBX_cursor_op {
+        match self {
+            Self::Next | Self::NextDup | Self::NextNoDup => ffi::MDBX_TO_KEY_GREATER_OR_EQUAL,
+            Self::Prev | Self::PrevDup | Self::PrevNoDup => ffi::MDBX_TO_KEY_LESSER_OR_EQUAL,
+        }
+    }
+}
+
+/// The predicate of a scan, and the payload of a panic it raised.
+pub(crate) struct ScanContext<F> {
+    predicate: F,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<F> ScanContext<F>
+where
+    F: FnMut(&[u8], &[u8]) -> ScanControl,
+{
+    pub(crate) const fn new(predicate: F) -> Self {
+        Self { predicate, panic: None }
+    }
+
+    /// Returns the trampoline calling the predicate of this context.
+    pub(crate) const fn func(&self) -> ffi::MDBX_predicate_func {
+        Some(trampoline::<F>)
+    }
+
+    /// Converts the return code of a scan, resuming any panic raised by the
+    /// predicate.
+    pub(crate) fn finish(self, rc: c_int) -> c_int {
+        if let Some(payload) = self.panic {
+            panic::resume_unwind(payload);
+        }
+        rc
+    }
+}
+
+/// Returns the bytes of a value passed to a predicate.
+///
+/// # Safety
+///
+/// `val` must be null, or point to a value valid for the duration of the
+/// returned borrow.
+const unsafe fn val_bytes<'a>(val: *const ffi::MDBX_val) -> &'a [u8] {
+    // SAFETY: Guaranteed by the caller.
+    match unsafe { val.as_ref() } {
+        Some(val) if !val.iov_base.is_null() => unsafe {
+            slice::from_raw_parts(val.iov_base as *const u8, val.iov_len)
+        },
+        _ => &[],
+    }
+}
+
+/// Calls the predicate of the [`ScanContext`] behind `context`.
+///
+/// # Safety
+///
+/// `context` must point to a live `ScanContext<F>`, and `key` and `value` to
+/// values valid for the duration of the call.
+unsafe extern "C" fn trampoline<F>(
+    context: *mut c_void,
+    key: *mut ffi::MDBX_val,
+    value: *mut ffi::MDBX_val,
+    _arg: *mut c_void,
+) -> c_int
+where
+    F: FnMut(&[u8], &[u8]) -> ScanControl,
+{
+    // SAFETY: Guaranteed by the caller.
+    let ctx = unsafe { &mut *context.cast::<ScanContext<F>>() };
+    let (key, value) = unsafe { (val_bytes(key), val_bytes(value)) };
+    match panic::catch_unwind(AssertUnwindSafe(|| (ctx.predicate)(key, value))) {
+        Ok(ScanControl::Continue) => ffi::MDBX_RESULT_FALSE,
+        Ok(ScanControl::Stop) => ffi::MDBX_RESULT_TRUE,
+        Err(payload) => {
+            ctx.panic = Some(payload);
+            SCAN_PANICKED
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use crate::{
+    Database, DatabaseFlags, MdbxError, MdbxResult, ReadResult, TableObject, TransactionKind,
+    WriteFlags,
+    error::IntoMdbxResult,
+    tx::{Tx, kind::WriteMarker},
+};
+use parking_lot::Mutex;
+use std::{collections::HashSet, fmt};
+
+/// The values of a key before its first write in an emulated savepoint.
+#[derive(Debug)]
+struct UndoEntry {
+    db: Database,
+    key: Vec<u8>,
+    values: Vec<Vec<u8>>,
+}
+
+/// The recorded state of every key written in an emulated savepoint.
+#[derive(Debug, Default)]
+struct Undo {
+    entries: Vec<UndoEntry>,
+    seen: HashSet<(ffi::MDBX_dbi, Vec<u8>)>,
+    /// The number of changes the parent had recorded when the savepoint
+    /// began, or `None` once the savepoint is released or rolled back.
+    changes_mark: Option<usize>,
+}
+
+/// A savepoint emulated on top of its parent transaction.
+///
+/// Writes go straight to the parent. Before the first write to a key, all
+/// of its values are recorded, so that rolling back can restore them.
+/// Dropping the log without releasing it rolls back.
+///
+/// Rolling back also discards the parent's changes recorded since the
+/// savepoint began, which include both the savepoint's writes and the
+/// writes restoring them.
+struct UndoLog<'a, K: TransactionKind + WriteMarker> {
+    txn: &'a Tx<K>,
+    undo: Mutex<Undo>,
+}
+
+impl<K: TransactionKind + WriteMarker> UndoLog<'_, K> {
+    /// Records the values of `key`, unless they were already recorded.
+    fn record(&self, db: Database, key: &[u8]) -> MdbxResult<()> {
+        let mut undo = self.undo.lock();
+        if undo.seen.contains(&(db.dbi(), key.to_vec())) {
+            return Ok(());
+        }
+
+        let values = if db.flags().contains(DatabaseFlags::DUP_SORT) {
+            let mut cursor = self.txn.cursor(db)?;
+            cursor.iter_dup_of::<Vec<u8>>(key).and_then(Iterator::collect)
+        } else {
+            self.txn.get::<Vec<u8>>(db, key).map(Option::into_iter).map(Iterator::collect)
+        }
+        .into_mdbx()?;
+
+        undo.seen.insert((db.dbi(), key.to_vec()));
+        undo.entries.push(UndoEntry { db, key: key.to_vec(), values });
+        Ok(())
+    }
+
+    /// Restores the recorded values, in reverse order of recording.
+    fn rollback(&self) -> MdbxResult<()> {
+        let undo = std::mem::take(&mut *self.undo.lock());
+        let res = undo.entries.iter().rev().try_for_each(|entry| {
+            self.txn.del(entry.db, &entry.key, None)?;
+            for value in &entry.values {
+                self.txn.put(entry.db, &entry.key, value, WriteFlags::empty())?;
+            }
+            Ok(())
+        });
+        if let Some(mark) = undo.changes_mark {
+            self.txn.changes().truncate(mark);
+        }
+        res
+    }
+
+    /// Keeps the writes, discarding the recorded values.
+    fn release(&self) {
+        *self.undo.lock() = Undo::default();
+    }
+}
+
+impl<K: TransactionKind + WriteMarker> Drop for UndoLog<'_, K> {
+    fn drop(&mut self) {
+        let _ = self.rollback();
+    }
+}
+
+/// Commits a nested transaction.
+type CommitFn<K> = fn(Tx<K>) -> MdbxResult<()>;
+
+enum Mode<'a, K: TransactionKind + WriteMarker> {
+    Nested { txn: Tx<K>, commit: CommitFn<K> },
+    Emulated(UndoLog<'a, K>),
+}
+
+/// A block of writes inside a write transaction that can be rolled back on
+/// its own.
+///
+/// A savepoint is backed by a nested transaction. [`Savepoint::release`]
+/// commits it into the parent, and [`Savepoint::rollback`] aborts it.
+/// Dropping a savepoint, including while unwinding from a panic, rolls it
+/// back.
+///
+/// WRITEMAP environments do not support nested transactions. There, the
+/// savepoint is emulated: writes go directly to the parent transaction, and
+/// the previous values of every key written through [`Savepoint::put`] or
+/// [`Savepoint::del`] are kept in memory and restored on rollback. Writes
+/// made through the parent transaction while the savepoint is open are not
+/// rolled back, and [`Savepoint::txn`] is unavailable.
+///
+/// Created with `Tx::begin_savepoint`, or used through `Tx::savepoint`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use signet_libmdbx::{Environment, MdbxError, WriteFlags};
+/// # let env = Environment::builder().open(std::path::Path::new("db")).unwrap();
+/// let txn = env.begin_rw_sync().unwrap();
+/// let db = txn.open_db(None).unwrap();
+///
+/// // If `b` already exists, the write to `a` is rolled back as well.
+/// let res = txn.savepoint(|sp| {
+///     sp.put(db, b"a", b"1", WriteFlags::empty())?;
+///     sp.put(db, b"b", b"2", WriteFlags::NO_OVERWRITE)
+/// });
+/// assert!(res.is_ok() || res == Err(MdbxError::KeyExist));
+///
+/// txn.commit().unwrap();
+/// ```
+pub struct Savepoint<'a, K: TransactionKind + WriteMarker> {
+    mode: Mode<'a, K>,
+}
+
+impl<K: TransactionKind + WriteMarker> fmt::Debug for Savepoint<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Savepoint").field("emulated", &self.is_emulated()).finish()
+    }
+}
+
+impl<'a, K: TransactionKind + WriteMarker> Savepoint<'a, K> {
+    /// Creates a savepoint backed by a nested transaction, committed with
+    /// `commit` on release.
+    pub(crate) const fn nested(txn: Tx<K>, commit: CommitFn<K>) -> Self {
+        Self { mode: Mode::Nested { txn, commit } }
+    }
+
+    /// Creates a savepoint emulated on top of `txn`.
+    pub(crate) fn emulated(txn: &'a Tx<K>) -> Self {
+        let undo = Undo { changes_mark: Some(txn.changes().mark()), ..Undo::default() };
+        Self { mode: Mode::Emulated(UndoLog { txn, undo: Mutex::new(undo) }) }
+    }
+
+    /// Runs `f`, releasing the savepoint on `Ok` and rolling it back on
+    /// `Err`.
+    ///
+    /// If rolling back fails as well, the error from `f` is returned, and
+    /// the rollback failure is logged.
+    pub(crate) fn run<F, T, E>(self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Self) -> Result<T, E>,
+        E: From<MdbxError>,
+    {
+        match f(&self) {
+            Ok(value) => {
+                self.release()?;
+                Ok(value)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = self.rollback() {
+                    tracing::warn!(target: "libmdbx", %rollback_err, "savepoint rollback failed");
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns the transaction that reads and writes go to.
+    const fn inner(&self) -> &Tx<K> {
+        match &self.mode {
+            Mode::Nested { txn, .. } => txn,
+            Mode::Emulated(log) => log.txn,
+        }
+    }
+
+    /// Returns `true` if the savepoint is emulated with an in-memory undo
+    /// log, because the environment is WRITEMAP.
+    pub const fn is_emulated(&self) -> bool {
+        matches!(self.mode, Mode::Emulated(_))
+    }
+
+    /// Returns the nested transaction backing the savepoint, giving access
+    /// to the full transaction API.
+    ///
+    /// Fails with [`MdbxError::NestedTransactionsUnsupportedWithWriteMap`]
+    /// if the savepoint is emulated, as writes made directly to the parent
+    /// transaction could not be rolled back.
+    pub const fn txn(&self) -> MdbxResult<&Tx<K>> {
+        match &self.mode {
+            Mode::Nested { txn, .. } => Ok(txn),
+            Mode::Emulated(_) => Err(MdbxError::NestedTransactionsUnsupportedWithWriteMap),
+        }
+    }
+
+    /// Gets an item from a database. See [`Tx::get`].
+    pub fn get<'s, Key>(&'s self, db: Database, key: &[u8]) -> ReadResult<Option<Key>>
+    where
+        Key: TableObject<'s>,
+    {
+        self.inner().get(db, key)
+    }
+
+    /// Stores an item into a database. See [`Tx::put`].
+    pub fn put(
+        &self,
+        db: Database,
+        key: impl AsRef<[u8]>,
+        data: impl AsRef<[u8]>,
+        flags: WriteFlags,
+    ) -> MdbxResult<()> {
+        if let Mode::Emulated(log) = &self.mode {
+            log.record(db, key.as_ref())?;
+        }
+        self.inner().put(db, key, data, flags)
+    }
+
+    /// Deletes items from a database. See [`Tx::del`].
+    pub fn del(
+        &self,
+        db: Database,
+        key: impl AsRef<[u8]>,
+        data: Option<&[u8]>,
+    ) -> MdbxResult<bool> {
+        if let Mode::Emulated(log) = &self.mode {
+            log.record(db, key.as_ref())?;
+        }
+        self.inner().del(db, key, data)
+    }
+
+    /// Keeps the savepoint's writes in the parent transaction.
+    pub fn release(self) -> MdbxResult<()> {
+        match self.mode {
+            Mode::Nested { txn, commit } => commit(txn),
+            Mode::Emulated(log) => {
+                log.release();
+                Ok(())
+            }
+        }
+    }
+
+    /// Discards the savepoint's writes.
+    pub fn rollback(self) -> MdbxResult<()> {
+        match self.mode {
+            Mode::Nested { txn, .. } => txn.abort(),
+            Mode::Emulated(log) => log.rollback(),
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Range partitioning for [`Environment::par_scan`].
+//!
+//! A range is split into chunks of roughly equal size by bisecting the key
+//! space, using [`mdbx_estimate_range`] to count the items between the start
+//! of the range and each candidate split key. Split keys need not exist in
+//! the table: the chunks are half-open, so every key in the range falls in
+//! exactly one of them however the splits are placed. Estimates only affect
+//! how balanced the chunks are.
+//!
+//! [`Environment::par_scan`]: crate::Environment::par_scan
+//! [`mdbx_estimate_range`]: ffi::mdbx_estimate_range
+
+use crate::{
+    Cursor, DatabaseFlags, ReadResult, RoSync,
+    tx::{TxPtrAccess, iter::Iter, ops},
+};
+use std::{borrow::Cow, cmp::Ordering, ops::Bound};
+
+/// Bisection steps spent looking for each split key.
+const MAX_SPLIT_STEPS: usize = 24;
+
+/// Owned bounds of a chunk of a scanned range.
+pub(crate) type ChunkBounds = (Bound<Vec<u8>>, Bound<Vec<u8>>);
+
+/// Maps a key to a byte string that sorts lexicographically in about the
+/// order of a table with `flags`, or back.
+///
+/// This only guides the search for split keys, which are checked with the
+/// table's comparator before they are used.
+fn lex(flags: DatabaseFlags, key: &[u8]) -> Vec<u8> {
+    let little_int = flags.contains(DatabaseFlags::INTEGER_KEY) && cfg!(target_endian = "little");
+    let mut key = key.to_vec();
+    if flags.contains(DatabaseFlags::REVERSE_KEY) || little_int {
+        key.reverse();
+    }
+    key
+}
+
+/// Returns the midpoint of two keys in lexicographic form.
+///
+/// Variable-length keys are extended by one byte, so that a midpoint exists
+/// between any two distinct keys.
+fn midpoint(fixed_len: bool, a: &[u8], b: &[u8]) -> Vec<u8> {
+    let len = a.len().max(b.len()) + usize::from(!fixed_len);
+    let byte = |key: &[u8], i: usize| u16::from(key.get(i).copied().unwrap_or(0));
+
+    let mut sum = vec![0u8; len];
+    let mut carry = 0;
+    for i in (0..len).rev() {
+        let s = byte(a, i) + byte(b, i) + carry;
+        sum[i] = s as u8;
+        carry = s >> 8;
+    }
+
+    let mut rem = carry;
+    for byte in &mut sum {
+        let v = (rem << 8) | u16::from(*byte);
+        *byte = (v >> 1) as u8;
+        rem = v & 1;
+    }
+    sum
+}
+
+/// Estimates the number of items between two keys.
+fn estimate(cursor: &Cursor<'_, RoSync>, begin: &[u8], end: &[u8]) -> ReadResult<isize> {
+    cursor.access().try_with_txn_ptr(|txn| {
+        // SAFETY: txn is valid from try_with_txn_ptr, and the cursor's
+        // database is open in it.
+        Ok(unsafe { ops::estimate_range_raw(txn, cursor.db().dbi(), Some(begin), Some(end)) }?)
+    })
+}
+
+/// Splits a range of the cursor's table into at most `n` chunks of roughly
+/// equal size, in key order.
+///
+/// The chunks are disjoint, and together cover the whole range.
+pub(crate) fn split_range(
+    cursor: &mut Cursor<'_, RoSync>,
+    start: Bound<&[u8]>,
+    end: Bound<&[u8]>,
+    n: usize,
+) -> ReadResult<Vec<ChunkBounds>> {
+    let flags = cursor.db_flags();
+    let fixed_len = flags.contains(DatabaseFlags::INTEGER_KEY);
+    let whole = vec![(start.map(<[u8]>::to_vec), end.map(<[u8]>::to_vec))];
+    if n <= 1 {
+        return Ok(whole);
+    }
+
+    // The first key in the range and the end of the range bound the search.
+    let lo = match start {
+        Bound::Unbounded => cursor.first::<Vec<u8>, ()>()?,
+        Bound::Included(key) | Bound::Excluded(key) => cursor.set_range::<Vec<u8>, ()>(key)?,
+    };
+    let hi = match end {
+        Bound::Unbounded => cursor.last::<Vec<u8>, ()>()?.map(|(key, _)| key),
+        Bound::Included(key) | Bound::Excluded(key) => Some(key.to_vec()),
+    };
+    let (Some((lo, _)), Some(hi)) = (lo, hi) else { return Ok(whole) };
+    if cursor.cmp_keys(&lo, &hi) != Ordering::Less {
+        return Ok(whole);
+    }
+
+    let total = estimate(cursor, &lo, &hi)?;
+    let hi_lex = lex(flags, &hi);
+    let mut splits: Vec<Vec<u8>> = Vec::with_capacity(n - 1);
+    for i in 1..n {
+        let target = total * i as isize / n as isize;
+        let prev = splits.last().unwrap_or(&lo).clone();
+        let mut a = lex(flags, &prev);
+        let mut b = hi_lex.clone();
+        let mut best: Option<(isize, Vec<u8>)> = None;
+
+        for _ in 0..MAX_SPLIT_STEPS {
+            let mid = midpoint(fixed_len, &a, &b);
+            if mid <= a || mid >= b {
+                break;
+            }
+            let key = lex(flags, &mid);
+            if !(cursor.cmp_keys(&prev, &key).is_lt() && cursor.cmp_keys(&key, &hi).is_lt()) {
+                break;
+            }
+            let distance = estimate(cursor, &lo, &key)?;
+            let error = (distance - target).abs();
+            if best.as_ref().is_none_or(|(best, _)| error < *best) {
+                best = Some((error, key));
+            }
+            match distance.cmp(&target) {
+                Ordering::Less => a = mid,
+                Ordering::Greater => b = mid,
+                Ordering::Equal => break,
+            }
+        }
+
+        if let Some((_, key)) = best {
+            splits.push(key);
+        }
+    }
+
+    let mut chunks = Vec::with_capacity(splits.len() + 1);
+    let mut from = start.map(<[u8]>::to_vec);
+    for split in splits {
+        chunks.push((from, Bound::Excluded(split.clone())));
+        from = Bound::Included(split);
+    }
+    chunks.push((from, end.map(<[u8]>::to_vec)));
+    Ok(chunks)
+}
+
+/// An iterator over one chunk of a range scanned by
+/// [`Environment::par_scan`].
+///
+/// Yields the key/value pairs of the chunk in key order, borrowed from the
+/// worker's read transaction. For [`DatabaseFlags::DUP_SORT`] tables, every
+/// value of a key is yielded by the same chunk.
+///
+/// [`Environment::par_scan`]: crate::Environment::par_scan
+#[derive(Debug)]
+pub struct ScanChunk<'tx, 'cur> {
+    iter: Iter<'tx, 'cur, RoSync>,
+    end: &'cur Bound<Vec<u8>>,
+    done: bool,
+}
+
+impl<'tx: 'cur, 'cur> ScanChunk<'tx, 'cur> {
+    /// Positions the cursor at the start of the chunk.
+    pub(crate) fn new(
+        cursor: &'cur mut Cursor<'tx, RoSync>,
+        bounds: &'cur ChunkBounds,
+    ) -> ReadResult<Self> {
+        let first = match &bounds.0 {
+            Bound::Unbounded => cursor.first()?,
+            Bound::Included(key) => cursor.set_range(key)?,
+            Bound::Excluded(key) => match cursor.set_range::<Cow<'tx, [u8]>, _>(key)? {
+                Some((found, _)) if found.as_ref() == key.as_slice() => cursor.next_nodup()?,
+                first => first,
+            },
+        };
+        let iter = match first {
+            Some(first) => Iter::new_with(cursor, first),
+            None => Iter::new_end(cursor),
+        };
+        Ok(Self { iter, end: &bounds.1, done: false })
+    }
+}
+
+impl<'tx> Iterator for ScanChunk<'tx, '_> {
+    type Item = ReadResult<(Cow<'tx, [u8]>, Cow<'tx, [u8]>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (key, value) = match self.iter.borrow_next() {
+            Ok(Some(item)) => item,
+            Ok(None) => return None,
+            Err(err) => return Some(Err(err)),
+        };
+        let past_end = match self.end {
+            Bound::Unbounded => false,
+            Bound::Included(end) => self.iter.cursor.cmp_keys(&key, end) == Ordering::Greater,
+            Bound::Excluded(end) => self.iter.cursor.cmp_keys(&key, end) != Ordering::Less,
+        };
+        if past_end {
+            self.done = true;
+            return None;
+        }
+        Some(Ok((key, value)))
+    }
+}
@@ -0,0 +1,78 @@
+//! Cursors that outlive their transactions.
+
+use crate::{Database, MdbxError, MdbxResult};
+use std::{fmt, mem::ManuallyDrop};
+
+/// A cursor that is not bound to any transaction.
+///
+/// Opening and closing a [`Cursor`] allocates and frees the underlying MDBX
+/// cursor. Code that runs many short transactions can instead keep an
+/// `UnboundCursor`, bind it with [`Tx::bind_cursor`] or [`Tx::renew_cursor`]
+/// for the lifetime of each transaction, and get it back with
+/// [`Cursor::unbind`].
+///
+/// The bound [`Cursor`] borrows its transaction as usual, so it cannot
+/// outlive it. Dropping the bound cursor instead of unbinding it closes it.
+///
+/// [`Cursor`]: crate::Cursor
+/// [`Cursor::unbind`]: crate::Cursor::unbind
+/// [`Tx::bind_cursor`]: crate::tx::Tx::bind_cursor
+/// [`Tx::renew_cursor`]: crate::tx::Tx::renew_cursor
+pub struct UnboundCursor {
+    cursor: *mut ffi::MDBX_cursor,
+    db: Option<Database>,
+}
+
+// SAFETY: An unbound cursor is not associated with any transaction, and
+// MDBX allows it to be bound from any thread.
+unsafe impl Send for UnboundCursor {}
+
+impl UnboundCursor {
+    /// Creates a new cursor that is not bound to any transaction.
+    pub fn new() -> MdbxResult<Self> {
+        // SAFETY: Creating a cursor has no preconditions.
+        let cursor = unsafe { ffi::mdbx_cursor_create(std::ptr::null_mut()) };
+        if cursor.is_null() {
+            return Err(MdbxError::from_err_code(ffi::MDBX_ENOMEM));
+        }
+        Ok(Self { cursor, db: None })
+    }
+
+    /// Returns the database the cursor was last bound to, if any.
+    ///
+    /// [`Tx::renew_cursor`] binds the cursor to this database again.
+    ///
+    /// [`Tx::renew_cursor`]: crate::tx::Tx::renew_cursor
+    pub const fn db(&self) -> Option<Database> {
+        self.db
+    }
+
+    /// Wraps a cursor that was unbound from a transaction.
+    pub(crate) const fn from_raw(cursor: *mut ffi::MDBX_cursor, db: Database) -> Self {
+        Self { cursor, db: Some(db) }
+    }
+
+    /// Returns the raw pointer to the underlying MDBX cursor.
+    pub(crate) const fn as_ptr(&self) -> *mut ffi::MDBX_cursor {
+        self.cursor
+    }
+
+    /// Releases ownership of the underlying MDBX cursor, without closing it.
+    pub(crate) fn into_raw(self) -> *mut ffi::MDBX_cursor {
+        ManuallyDrop::new(self).cursor
+    }
+}
+
+impl fmt::Debug for UnboundCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundCursor").field("db", &self.db).finish()
+    }
+}
+
+impl Drop for UnboundCursor {
+    fn drop(&mut self) {
+        // SAFETY: The cursor is not bound to a transaction, so it can be
+        // closed without one.
+        unsafe { ffi::mdbx_cursor_close(self.cursor) }
+    }
+}
@@ -0,0 +1,92 @@
+//! Typed application data attached to environments and transactions.
+//!
+//! The data is stored on the Rust side rather than in the libmdbx `userctx`
+//! pointers, and is dropped along with the object that owns it, once no
+//! [`UserData`] handle to it remains.
+
+use parking_lot::Mutex;
+use std::{any::Any, fmt, ops::Deref, sync::Arc};
+
+/// Storage for a single user data value of any type.
+pub(crate) type UserDataCell = Mutex<Option<Arc<dyn Any + Send + Sync>>>;
+
+/// Storage for the user data of a transaction.
+///
+/// The value need not be `Sync`. Each transaction type's accessors require
+/// `Sync` where the transaction can be shared or moved while a
+/// [`UserData`] handle stays behind.
+pub(crate) type TxUserDataCell = Mutex<Option<Arc<dyn Any + Send>>>;
+
+/// Replaces the value in the cell, releasing the previous one.
+pub(crate) fn set<T: Any + Send + Sync>(cell: &UserDataCell, data: T) {
+    let previous = cell.lock().replace(Arc::new(data));
+    // Drop the previous value outside the lock, in case its destructor
+    // accesses the cell.
+    drop(previous);
+}
+
+/// Returns the value in the cell, if it is present and of type `T`.
+pub(crate) fn get<T: Any + Send + Sync>(cell: &UserDataCell) -> Option<UserData<T>> {
+    let data = cell.lock().clone()?;
+    data.downcast::<T>().ok().map(UserData)
+}
+
+/// Replaces the value in a transaction's cell, releasing the previous one.
+pub(crate) fn set_tx<T: Any + Send>(cell: &TxUserDataCell, data: T) {
+    let previous = cell.lock().replace(Arc::new(data));
+    drop(previous);
+}
+
+/// Returns the value in a transaction's cell, if it is present and of type
+/// `T`.
+pub(crate) fn get_tx<T: Any + Send>(cell: &TxUserDataCell) -> Option<UserData<T>> {
+    let data = cell.lock().clone()?;
+    if !(*data).is::<T>() {
+        return None;
+    }
+    // SAFETY: the value was checked to be a `T`, so the pointer to the
+    // trait object points to a `T`.
+    Some(UserData(unsafe { Arc::from_raw(Arc::into_raw(data).cast::<T>()) }))
+}
+
+/// A shared reference to user data attached to an [`Environment`] or a
+/// [`Tx`].
+///
+/// The handle does not lock the data, so user data can be requested and
+/// replaced again while it is alive. Replacing the data does not affect
+/// existing handles, which keep the previous value alive. Data that needs
+/// to be mutated in place should use interior mutability, such as a
+/// [`Mutex`](std::sync::Mutex), or a [`RefCell`](std::cell::RefCell) on a
+/// [`RwTxUnsync`].
+///
+/// [`Environment`]: crate::Environment
+/// [`Tx`]: crate::tx::Tx
+/// [`RwTxUnsync`]: crate::tx::aliases::RwTxUnsync
+pub struct UserData<T>(Arc<T>);
+
+impl<T> UserData<T> {
+    /// Returns the underlying shared pointer.
+    pub fn into_arc(self) -> Arc<T> {
+        self.0
+    }
+}
+
+impl<T> Clone for UserData<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Deref for UserData<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UserData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UserData").field(&*self.0).finish()
+    }
+}
@@ -0,0 +1,167 @@
+#![allow(missing_docs)]
+use signet_libmdbx::*;
+use std::{cell::RefCell, rc::Rc};
+use tempfile::tempdir;
+
+fn setup(flags: DatabaseFlags) -> (tempfile::TempDir, Environment, Database) {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, flags).unwrap();
+    txn.commit().unwrap();
+    (dir, env, db)
+}
+
+fn all_items(env: &Environment, db: Database) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let txn = env.begin_ro_sync().unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+    cursor.iter_start::<Vec<u8>, Vec<u8>>().unwrap().collect::<ReadResult<_>>().unwrap()
+}
+
+#[test]
+fn test_bulk_load() {
+    let (_dir, env, db) = setup(DatabaseFlags::empty());
+    let rows: Vec<_> =
+        (0..10_000u32).map(|i| (i.to_be_bytes().to_vec(), i.to_le_bytes().to_vec())).collect();
+
+    let commits = Rc::new(RefCell::new(Vec::new()));
+    let seen = commits.clone();
+    let stats = env
+        .bulk_loader()
+        .set_max_rows(3_000)
+        .on_progress(move |stats| seen.borrow_mut().push(stats.rows()))
+        .load(db, rows.clone())
+        .unwrap();
+
+    assert_eq!(stats.rows(), 10_000);
+    assert_eq!(stats.bytes(), 80_000);
+    assert_eq!(stats.commits(), 4);
+    assert_eq!(stats.map_grows(), 0);
+    assert_eq!(*commits.borrow(), [3_000, 6_000, 9_000, 10_000]);
+    assert_eq!(all_items(&env, db), rows);
+
+    // Loading continues after the rows already in the table.
+    let more: Vec<_> = (10_000..10_100u32).map(|i| (i.to_be_bytes().to_vec(), vec![])).collect();
+    assert_eq!(env.bulk_loader().load(db, more).unwrap().rows(), 100);
+    assert_eq!(all_items(&env, db).len(), 10_100);
+}
+
+#[test]
+fn test_bulk_load_dup_sort() {
+    let (_dir, env, db) = setup(DatabaseFlags::DUP_SORT);
+    let rows: Vec<_> = (0..1_000u32)
+        .flat_map(|i| (0..5u8).map(move |j| (i.to_be_bytes().to_vec(), vec![j; j as usize + 1])))
+        .collect();
+
+    let stats = env.bulk_loader().set_max_rows(777).load(db, rows.clone()).unwrap();
+    assert_eq!(stats.rows(), 5_000);
+    assert_eq!(all_items(&env, db), rows);
+}
+
+#[test]
+fn test_bulk_load_dup_fixed() {
+    let (_dir, env, db) = setup(DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED);
+    let rows: Vec<_> = (0..100u32)
+        .flat_map(|i| {
+            (0..1_000u64).map(move |j| (i.to_be_bytes().to_vec(), j.to_be_bytes().to_vec()))
+        })
+        .collect();
+
+    let stats = env.bulk_loader().set_max_rows(25_000).load(db, rows.clone()).unwrap();
+    assert_eq!(stats.rows(), 100_000);
+    assert_eq!(stats.commits(), 4);
+    assert_eq!(all_items(&env, db), rows);
+}
+
+#[test]
+fn test_bulk_load_integer_key() {
+    let (_dir, env, db) = setup(DatabaseFlags::INTEGER_KEY);
+    let rows: Vec<_> = (0..5_000u64).map(|i| (i.to_ne_bytes().to_vec(), vec![1])).collect();
+
+    env.bulk_loader().load(db, rows.clone()).unwrap();
+    assert_eq!(all_items(&env, db), rows);
+}
+
+fn small_env() -> (tempfile::TempDir, Environment, Database) {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder()
+        .set_geometry(Geometry { size: Some(0..(1 << 20)), ..Default::default() })
+        .open(dir.path())
+        .unwrap();
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    txn.commit().unwrap();
+    (dir, env, db)
+}
+
+fn large_rows() -> impl Iterator<Item = ([u8; 4], [u8; 100])> {
+    (0..20_000u32).map(|i| (i.to_be_bytes(), [7u8; 100]))
+}
+
+#[test]
+fn test_bulk_load_map_full() {
+    let (_dir, env, db) = small_env();
+
+    // Without growth, the load stops at the last committed chunk.
+    let err =
+        env.bulk_loader().set_max_rows(1_000).set_grow_step(0).load(db, large_rows()).unwrap_err();
+    assert_eq!(err.error(), MdbxError::MapFull);
+    assert_eq!(err.stats().map_grows(), 0);
+    let committed = err.position().rows();
+    assert!(committed > 0 && committed < 20_000);
+    assert_eq!(err.position().last_key(), Some(&(committed as u32 - 1).to_be_bytes()[..]));
+    assert_eq!(all_items(&env, db).len() as u64, committed);
+
+    // The map is never grown beyond the maximum size, though the current
+    // size may first grow to it.
+    let err = env
+        .bulk_loader()
+        .set_max_rows(1_000)
+        .set_max_map_size(1 << 20)
+        .load(db, large_rows().skip(committed as usize))
+        .unwrap_err();
+    assert_eq!(err.error(), MdbxError::MapFull);
+    assert!(err.stats().map_grows() <= 1);
+    assert_eq!(err.position().rows(), 0);
+    let geometry = env.info().unwrap().geometry();
+    assert_eq!(geometry.current(), 1 << 20);
+    assert_eq!(geometry.max(), 1 << 20);
+}
+
+#[test]
+fn test_bulk_load_grows_map() {
+    let (_dir, env, db) = small_env();
+
+    // MDBX can only grow the map if the address space following it is free,
+    // so the load either completes or stops at a resumable position.
+    match env.bulk_loader().set_max_rows(1_000).set_grow_step(1 << 20).load(db, large_rows()) {
+        Ok(stats) => {
+            assert!(stats.map_grows() > 0);
+            assert!(env.info().unwrap().geometry().max() > 1 << 20);
+            assert_eq!(stats.rows(), 20_000);
+        }
+        Err(err) => {
+            assert_eq!(err.error(), MdbxError::UnableExtendMapSize);
+            assert_eq!(all_items(&env, db).len() as u64, err.position().rows());
+        }
+    }
+}
+
+#[test]
+fn test_bulk_load_out_of_order() {
+    let (_dir, env, db) = setup(DatabaseFlags::empty());
+    let rows: Vec<_> = [1u8, 2, 3, 5, 4, 6].iter().map(|&k| (vec![k], vec![k])).collect();
+
+    let err = env.bulk_loader().set_max_rows(2).load(db, rows).unwrap_err();
+    assert_eq!(err.error(), MdbxError::KeyMismatch);
+    assert_eq!(err.position().rows(), 4);
+    assert_eq!(err.position().last_key(), Some(&[5u8][..]));
+    assert_eq!(err.position().last_value(), Some(&[5u8][..]));
+    assert_eq!(all_items(&env, db).len(), 4);
+
+    // Rows must also sort after the rows already in the table.
+    let err = env.bulk_loader().load(db, [([5u8], [0u8])]).unwrap_err();
+    assert_eq!(err.error(), MdbxError::KeyMismatch);
+    assert_eq!(err.position().rows(), 0);
+    assert_eq!(err.position().last_key(), None);
+}
@@ -0,0 +1,210 @@
+#![allow(missing_docs)]
+mod common;
+use common::{TestRoTxn, TestRwTxn, V1Factory, V2Factory};
+use signet_libmdbx::*;
+use std::sync::mpsc::{Receiver, channel};
+use tempfile::tempdir;
+
+/// Records the changes of `txn` into a channel.
+fn record<T: TestRwTxn>(txn: &T, old_values: bool) -> Receiver<ChangeSet> {
+    let (tx, rx) = channel();
+    txn.record_changes(old_values, move |set| tx.send(set).unwrap());
+    rx
+}
+
+/// A change as (op, key, value, old value).
+type Summary<'a> = (ChangeOp, &'a [u8], Option<&'a [u8]>, Option<&'a [u8]>);
+
+fn summary(change: &Change) -> Summary<'_> {
+    (change.op(), change.key(), change.value(), change.old_value())
+}
+
+fn test_record_changes_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    _begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"a", b"0", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let rx = record(&txn, true);
+    txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+    txn.put(db, b"b", b"2", WriteFlags::empty()).unwrap();
+    assert!(!txn.del(db, b"missing", None).unwrap());
+    txn.del(db, b"b", None).unwrap();
+    {
+        let mut cursor = txn.cursor(db).unwrap();
+        cursor.put(b"c", b"3", WriteFlags::empty()).unwrap();
+        cursor.set::<()>(b"a").unwrap();
+        cursor.del().unwrap();
+    }
+    txn.append(db, b"d", b"4").unwrap();
+    txn.clear_db(db).unwrap();
+    assert!(rx.try_recv().is_err());
+    txn.commit().unwrap();
+
+    let set = rx.try_recv().unwrap();
+    assert_eq!(set.txn_id(), env.info().unwrap().last_txnid() as u64);
+    assert!(set.changes().iter().all(|change| change.db() == db));
+    assert_eq!(
+        set.changes().iter().map(summary).collect::<Vec<_>>(),
+        [
+            (ChangeOp::Put, &b"a"[..], Some(&b"1"[..]), Some(&b"0"[..])),
+            (ChangeOp::Put, b"b", Some(b"2"), None),
+            (ChangeOp::Del, b"b", None, Some(b"2")),
+            (ChangeOp::Put, b"c", Some(b"3"), None),
+            (ChangeOp::Del, b"a", None, Some(b"1")),
+            (ChangeOp::Put, b"d", Some(b"4"), None),
+            (ChangeOp::Clear, b"", None, None),
+        ]
+    );
+
+    // Aborted transactions hand nothing to the subscriber.
+    let txn = begin_rw(&env).unwrap();
+    let rx = record(&txn, false);
+    txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+    drop(txn);
+    assert!(rx.try_recv().is_err());
+
+    // Without old values, none are looked up.
+    let txn = begin_rw(&env).unwrap();
+    let rx = record(&txn, false);
+    txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+    txn.put(db, b"a", b"2", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+    let set = rx.try_recv().unwrap();
+    assert_eq!(
+        set.changes().iter().map(summary).collect::<Vec<_>>(),
+        [
+            (ChangeOp::Put, &b"a"[..], Some(&b"1"[..]), None),
+            (ChangeOp::Put, b"a", Some(b"2"), None)
+        ]
+    );
+}
+
+#[test]
+fn test_record_changes_v1() {
+    test_record_changes_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_record_changes_v2() {
+    test_record_changes_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+fn test_record_changes_dup_fixed_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    _begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED).unwrap();
+    let rx = record(&txn, true);
+    {
+        let mut cursor = txn.cursor(db).unwrap();
+        assert_eq!(cursor.put_multiple(b"k", b"aabbcc", 2).unwrap(), 3);
+        cursor.set::<()>(b"k").unwrap();
+        cursor.del().unwrap();
+        cursor.del_all_dups_of(b"k").unwrap();
+    }
+    txn.put(db, b"k", b"dd", WriteFlags::empty()).unwrap();
+    txn.del(db, b"k", Some(b"dd")).unwrap();
+    txn.commit().unwrap();
+
+    let set = rx.try_recv().unwrap();
+    assert_eq!(
+        set.changes().iter().map(summary).collect::<Vec<_>>(),
+        [
+            (ChangeOp::Put, &b"k"[..], Some(&b"aa"[..]), None),
+            (ChangeOp::Put, b"k", Some(b"bb"), None),
+            (ChangeOp::Put, b"k", Some(b"cc"), None),
+            (ChangeOp::Del, b"k", Some(b"aa"), None),
+            (ChangeOp::Del, b"k", None, None),
+            (ChangeOp::Put, b"k", Some(b"dd"), None),
+            (ChangeOp::Del, b"k", Some(b"dd"), None),
+        ]
+    );
+}
+
+#[test]
+fn test_record_changes_dup_fixed_v1() {
+    test_record_changes_dup_fixed_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_record_changes_dup_fixed_v2() {
+    test_record_changes_dup_fixed_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+/// Nested transactions - V1 only
+#[test]
+fn test_record_changes_nested() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    let rx = record(&txn, false);
+    txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+
+    let nested = txn.begin_nested_txn().unwrap();
+    nested.put(db, b"aborted", b"2", WriteFlags::empty()).unwrap();
+    drop(nested);
+
+    let nested = txn.begin_nested_txn().unwrap();
+    nested.put(db, b"committed", b"3", WriteFlags::empty()).unwrap();
+    nested.commit().unwrap();
+
+    txn.put(db, b"b", b"4", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    let keys: Vec<_> = rx.try_recv().unwrap().into_iter().map(|c| c.key().to_vec()).collect();
+    assert_eq!(keys, [&b"a"[..], b"committed", b"b"]);
+
+    // Recording started in a nested transaction is handed to the parent.
+    let txn = env.begin_rw_sync().unwrap();
+    let nested = txn.begin_nested_txn().unwrap();
+    let rx = record(&nested, false);
+    nested.put(db, b"c", b"5", WriteFlags::empty()).unwrap();
+    nested.commit().unwrap();
+    assert!(rx.try_recv().is_err());
+    txn.commit().unwrap();
+    assert_eq!(rx.try_recv().unwrap().len(), 1);
+}
+
+#[test]
+fn test_record_changes_reused_dbi() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let first = txn.create_db(Some("first"), DatabaseFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    // Dropping a table releases its dbi, which the next table reuses.
+    let txn = env.begin_rw_sync().unwrap();
+    let rx = record(&txn, false);
+    txn.put(first, b"a", b"1", WriteFlags::empty()).unwrap();
+    txn.drop_db(first).unwrap();
+    let second = txn.create_db(Some("second"), DatabaseFlags::empty()).unwrap();
+    txn.put(second, b"a", b"2", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    assert_eq!(first.dbi(), second.dbi());
+    assert_ne!(first, second);
+    let dbs = rx.try_recv().unwrap().changes().iter().map(Change::db).collect::<Vec<_>>();
+    assert_eq!(dbs, [first, first, second]);
+}
@@ -5,8 +5,9 @@
 //! functions that work with either variant.
 #![allow(missing_docs, dead_code)]
 use signet_libmdbx::{
-    Cursor, Database, DatabaseFlags, Environment, MdbxResult, ReadResult, Ro, RoSync, Rw, RwSync,
-    Stat, TableObject, TransactionKind, TxSync, TxUnsync, WriteFlags, ffi,
+    CancelHandle, ChangeSet, CommitInfo, Cursor, Database, DatabaseFlags, Environment, MdbxResult,
+    ReadResult, Ro, RoSync, Rw, RwSync, Stat, TableObject, TransactionKind, TxSync, TxUnsync,
+    UserData, WriteFlags, ffi,
     tx::{
         WriteMarker,
         aliases::{RoTxSync, RoTxUnsync, RwTxSync, RwTxUnsync},
@@ -20,11 +21,7 @@ pub trait TestRwTxn: Sized {
 
     fn create_db(&self, name: Option<&str>, flags: DatabaseFlags) -> MdbxResult<Database>;
     fn open_db(&self, name: Option<&str>) -> MdbxResult<Database>;
-    fn get<'a, T: TableObject<'a>>(
-        &'a self,
-        dbi: ffi::MDBX_dbi,
-        key: &[u8],
-    ) -> ReadResult<Option<T>>;
+    fn get<'a, T: TableObject<'a>>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<T>>;
     fn put(&self, db: Database, key: &[u8], data: &[u8], flags: WriteFlags) -> MdbxResult<()>;
     fn append(&self, db: Database, key: &[u8], data: &[u8]) -> MdbxResult<()>;
     fn append_dup(&self, db: Database, key: &[u8], data: &[u8]) -> MdbxResult<()>;
@@ -33,11 +30,14 @@ pub trait TestRwTxn: Sized {
     fn commit(self) -> MdbxResult<()>;
     fn cursor(&self, db: Database) -> MdbxResult<Cursor<'_, Self::Kind>>;
     fn db_stat(&self, dbi: ffi::MDBX_dbi) -> MdbxResult<Stat>;
-
-    /// # Safety_by_dbi
-    /// Caller must close all other Database and Cursor instances pointing to
-    /// this dbi before calling.
-    unsafe fn drop_db(&self, db: Database) -> MdbxResult<()>;
+    fn drop_db(&self, db: Database) -> MdbxResult<()>;
+    fn abort(self) -> MdbxResult<()>;
+    fn cancel_handle(&self) -> CancelHandle;
+    fn set_user_data<T: std::any::Any + Send + Sync>(&self, data: T);
+    fn user_data<T: std::any::Any + Send + Sync>(&self) -> Option<UserData<T>>;
+    fn on_commit(&self, f: impl FnOnce(CommitInfo) + Send + 'static);
+    fn on_abort(&self, f: impl FnOnce() + Send + 'static);
+    fn record_changes(&self, old_values: bool, f: impl FnOnce(ChangeSet) + Send + 'static);
 }
 
 /// Trait for read-only transaction operations used in tests.
@@ -45,11 +45,7 @@ pub trait TestRoTxn: Sized {
     type Kind: TransactionKind;
 
     fn open_db(&self, name: Option<&str>) -> MdbxResult<Database>;
-    fn get<'a, T: TableObject<'a>>(
-        &'a self,
-        dbi: ffi::MDBX_dbi,
-        key: &[u8],
-    ) -> ReadResult<Option<T>>;
+    fn get<'a, T: TableObject<'a>>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<T>>;
     fn commit(self) -> MdbxResult<()>;
     fn cursor(&self, db: Database) -> MdbxResult<Cursor<'_, Self::Kind>>;
     fn db_stat(&self, dbi: ffi::MDBX_dbi) -> MdbxResult<Stat>;
@@ -70,12 +66,8 @@ impl TestRwTxn for RwTxSync {
         TxSync::open_db(self, name)
     }
 
-    fn get<'a, T: TableObject<'a>>(
-        &'a self,
-        dbi: ffi::MDBX_dbi,
-        key: &[u8],
-    ) -> ReadResult<Option<T>> {
-        TxSync::get(self, dbi, key)
+    fn get<'a, T: TableObject<'a>>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<T>> {
+        TxSync::get(self, db, key)
     }
 
     fn put(&self, db: Database, key: &[u8], data: &[u8], flags: WriteFlags) -> MdbxResult<()> {
@@ -110,9 +102,36 @@ impl TestRwTxn for RwTxSync {
         TxSync::db_stat_by_dbi(self, dbi)
     }
 
-    unsafe fn drop_db(&self, db: Database) -> MdbxResult<()> {
-        // SAFETY: Caller ensures no other references to dbi exist.
-        unsafe { TxSync::drop_db(self, db) }
+    fn drop_db(&self, db: Database) -> MdbxResult<()> {
+        TxSync::drop_db(self, db)
+    }
+
+    fn abort(self) -> MdbxResult<()> {
+        TxSync::abort(self)
+    }
+
+    fn cancel_handle(&self) -> CancelHandle {
+        TxSync::cancel_handle(self)
+    }
+
+    fn set_user_data<T: std::any::Any + Send + Sync>(&self, data: T) {
+        TxSync::set_user_data(self, data)
+    }
+
+    fn user_data<T: std::any::Any + Send + Sync>(&self) -> Option<UserData<T>> {
+        TxSync::user_data(self)
+    }
+
+    fn on_commit(&self, f: impl FnOnce(CommitInfo) + Send + 'static) {
+        TxSync::on_commit(self, f)
+    }
+
+    fn on_abort(&self, f: impl FnOnce() + Send + 'static) {
+        TxSync::on_abort(self, f)
+    }
+
+    fn record_changes(&self, old_values: bool, f: impl FnOnce(ChangeSet) + Send + 'static) {
+        TxSync::record_changes(self, old_values, f)
     }
 }
 
@@ -123,12 +142,8 @@ impl TestRoTxn for RoTxSync {
         TxSync::open_db(self, name)
     }
 
-    fn get<'a, T: TableObject<'a>>(
-        &'a self,
-        dbi: ffi::MDBX_dbi,
-        key: &[u8],
-    ) -> ReadResult<Option<T>> {
-        TxSync::get(self, dbi, key)
+    fn get<'a, T: TableObject<'a>>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<T>> {
+        TxSync::get(self, db, key)
     }
 
     fn commit(self) -> MdbxResult<()> {
@@ -159,12 +174,8 @@ impl TestRwTxn for RwTxUnsync {
         TxUnsync::open_db(self, name)
     }
 
-    fn get<'a, T: TableObject<'a>>(
-        &'a self,
-        dbi: ffi::MDBX_dbi,
-        key: &[u8],
-    ) -> ReadResult<Option<T>> {
-        TxUnsync::get(self, dbi, key)
+    fn get<'a, T: TableObject<'a>>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<T>> {
+        TxUnsync::get(self, db, key)
     }
 
     fn put(&self, db: Database, key: &[u8], data: &[u8], flags: WriteFlags) -> MdbxResult<()> {
@@ -199,9 +210,36 @@ impl TestRwTxn for RwTxUnsync {
         TxUnsync::db_stat_by_dbi(self, dbi)
     }
 
-    unsafe fn drop_db(&self, db: Database) -> MdbxResult<()> {
-        // SAFETY: Caller ensures no other references to dbi exist.
-        unsafe { TxUnsync::drop_db(self, db) }
+    fn drop_db(&self, db: Database) -> MdbxResult<()> {
+        TxUnsync::drop_db(self, db)
+    }
+
+    fn abort(self) -> MdbxResult<()> {
+        TxUnsync::abort(self)
+    }
+
+    fn cancel_handle(&self) -> CancelHandle {
+        TxUnsync::cancel_handle(self)
+    }
+
+    fn set_user_data<T: std::any::Any + Send + Sync>(&self, data: T) {
+        RwTxUnsync::set_user_data(self, data)
+    }
+
+    fn user_data<T: std::any::Any + Send + Sync>(&self) -> Option<UserData<T>> {
+        RwTxUnsync::user_data(self)
+    }
+
+    fn on_commit(&self, f: impl FnOnce(CommitInfo) + Send + 'static) {
+        TxUnsync::on_commit(self, f)
+    }
+
+    fn on_abort(&self, f: impl FnOnce() + Send + 'static) {
+        TxUnsync::on_abort(self, f)
+    }
+
+    fn record_changes(&self, old_values: bool, f: impl FnOnce(ChangeSet) + Send + 'static) {
+        TxUnsync::record_changes(self, old_values, f)
     }
 }
 
@@ -212,12 +250,8 @@ impl TestRoTxn for TxUnsync<Ro> {
         TxUnsync::open_db(self, name)
     }
 
-    fn get<'a, T: TableObject<'a>>(
-        &'a self,
-        dbi: ffi::MDBX_dbi,
-        key: &[u8],
-    ) -> ReadResult<Option<T>> {
-        TxUnsync::get(self, dbi, key)
+    fn get<'a, T: TableObject<'a>>(&'a self, db: Database, key: &[u8]) -> ReadResult<Option<T>> {
+        TxUnsync::get(self, db, key)
     }
 
     fn commit(self) -> MdbxResult<()> {
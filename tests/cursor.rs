@@ -2,8 +2,9 @@
 mod common;
 use common::{TestRoTxn, TestRwTxn, V1Factory, V2Factory};
 use signet_libmdbx::{
-    Cursor, DatabaseFlags, DupItem, Environment, MdbxError, MdbxResult, ObjectLength, ReadResult,
-    TransactionKind, WriteFlags,
+    Cursor, DatabaseFlags, DupItem, Environment, FixedValue, MdbxError, MdbxResult, ObjectLength,
+    ReadError, ReadResult, ScanControl, ScanStart, ScanTurn, TransactionKind, UnboundCursor,
+    WriteFlags, ffi, tx::iter::IterRange,
 };
 use std::{borrow::Cow, hint::black_box};
 use tempfile::tempdir;
@@ -1745,3 +1746,1012 @@ fn test_put_multiple_requires_dupfixed_returns_error() {
     let result = cursor.put_multiple(b"key", &values, 4);
     assert!(matches!(result, Err(MdbxError::RequiresDupFixed)));
 }
+
+fn test_scan_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    _begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.open_db(None).unwrap();
+    for i in 0..10u8 {
+        txn.put(db, &[i], &[i * 10], WriteFlags::empty()).unwrap();
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let stop_at = |target: u8| {
+        move |_: &[u8], value: &[u8]| {
+            if value == [target] { ScanControl::Stop } else { ScanControl::Continue }
+        }
+    };
+
+    // The cursor is left on the match.
+    assert!(cursor.scan(ScanStart::First, ScanTurn::Next, stop_at(40)).unwrap());
+    assert_eq!(cursor.get_current().unwrap(), Some((vec![4], vec![40])));
+
+    // Scanning from the current item includes it.
+    let mut seen = Vec::new();
+    let found = cursor
+        .scan(ScanStart::Current, ScanTurn::Prev, |key, _| {
+            seen.push(key[0]);
+            ScanControl::Continue
+        })
+        .unwrap();
+    assert!(!found);
+    assert_eq!(seen, [4, 3, 2, 1, 0]);
+
+    assert!(cursor.scan(ScanStart::Last, ScanTurn::Prev, stop_at(70)).unwrap());
+    assert_eq!(cursor.get_current().unwrap(), Some((vec![7], vec![70])));
+
+    assert!(!cursor.scan(ScanStart::First, ScanTurn::Next, stop_at(55)).unwrap());
+
+    // Forward scans start at the first key at or after the given key, and
+    // backward scans at the last key at or before it.
+    let mut first = None;
+    let found = cursor
+        .scan_from(&[5], ScanTurn::Next, |key, _| {
+            first.get_or_insert(key[0]);
+            ScanControl::Continue
+        })
+        .unwrap();
+    assert!(!found);
+    assert_eq!(first, Some(5));
+
+    assert!(cursor.scan_from(&[5], ScanTurn::Prev, stop_at(20)).unwrap());
+    assert_eq!(cursor.get_current().unwrap(), Some((vec![2], vec![20])));
+    assert!(!cursor.scan_from(&[5], ScanTurn::Next, stop_at(20)).unwrap());
+    assert!(!cursor.scan_from(&[20], ScanTurn::Next, stop_at(20)).unwrap());
+}
+
+#[test]
+fn test_scan_v1() {
+    test_scan_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_scan_v2() {
+    test_scan_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_scan_dup_sort() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"b", b"c"] {
+        for value in [b"1", b"2", b"3"] {
+            txn.put(db, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+
+    // NextNoDup visits the first value of each key.
+    let mut seen = Vec::new();
+    cursor
+        .scan(ScanStart::First, ScanTurn::NextNoDup, |key, value| {
+            seen.push((key.to_vec(), value.to_vec()));
+            ScanControl::Continue
+        })
+        .unwrap();
+    assert_eq!(
+        seen,
+        [(b"a".to_vec(), b"1".to_vec()), (b"b".into(), b"1".into()), (b"c".into(), b"1".into())]
+    );
+
+    // NextDup stays within the current key.
+    cursor.set::<()>(b"b").unwrap();
+    let found = cursor
+        .scan(ScanStart::FirstDup, ScanTurn::NextDup, |key, _| {
+            assert_eq!(key, b"b");
+            ScanControl::Continue
+        })
+        .unwrap();
+    assert!(!found);
+
+    let found = cursor
+        .scan(ScanStart::LastDup, ScanTurn::PrevDup, |_, value| {
+            if value == b"2" { ScanControl::Stop } else { ScanControl::Continue }
+        })
+        .unwrap();
+    assert!(found);
+    assert_eq!(cursor.get_current().unwrap(), Some((b"b".to_vec(), b"2".to_vec())));
+}
+
+#[test]
+fn test_scan_panic() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        cursor.scan(ScanStart::First, ScanTurn::Next, |_, _| panic!("predicate panicked"))
+    }));
+    let payload = result.unwrap_err();
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"predicate panicked"));
+
+    // The cursor remains usable.
+    assert_eq!(cursor.first().unwrap(), Some((b"a".to_vec(), b"1".to_vec())));
+}
+
+fn test_iter_batched_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let items: Vec<_> = (0..5_000u32).map(|i| (i.to_be_bytes(), [i as u8; 50])).collect();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.open_db(None).unwrap();
+    {
+        let mut cursor = txn.cursor(db).unwrap();
+        assert_eq!(cursor.iter_batched::<(), ()>(16).unwrap().count(), 0);
+    }
+    for (key, value) in &items {
+        txn.put(db, key, value, WriteFlags::empty()).unwrap();
+    }
+
+    // Dirty pages are copied when borrowing.
+    {
+        let mut cursor = txn.cursor(db).unwrap();
+        let mut iter = cursor.iter_batched::<Cow<'_, [u8]>, Cow<'_, [u8]>>(100).unwrap();
+        let mut count = 0;
+        while let Some((key, value)) = iter.borrow_next().unwrap() {
+            assert!(matches!(key, Cow::Owned(_)));
+            assert_eq!(value.as_ref(), &items[count].1);
+            count += 1;
+        }
+        assert_eq!(count, items.len());
+    }
+    txn.commit().unwrap();
+
+    let txn = begin_ro(&env).unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+    for batch_size in [0, 2, 7, 1_000, 10_000] {
+        // The iterator starts at the first item regardless of position.
+        cursor.last::<(), ()>().unwrap();
+        let iter = cursor.iter_batched::<[u8; 4], [u8; 50]>(batch_size).unwrap();
+        assert_eq!(iter.batch_size(), batch_size.max(2));
+        assert_eq!(iter.collect::<Result<Vec<_>>>().unwrap(), items);
+    }
+
+    // Decoding errors end the iteration.
+    let mut iter = cursor.iter_batched::<Vec<u8>, [u8; 49]>(10).unwrap();
+    assert!(iter.next().unwrap().is_err());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_iter_batched_v1() {
+    test_iter_batched_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_iter_batched_v2() {
+    test_iter_batched_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_iter_batched_dup_sort() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    txn.put(db, b"a", b"1", WriteFlags::empty()).unwrap();
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let mut iter = cursor.iter_batched::<Vec<u8>, Vec<u8>>(16).unwrap();
+    assert!(matches!(iter.next(), Some(Err(ReadError::Mdbx(MdbxError::Incompatible)))));
+    assert!(iter.next().is_none());
+}
+
+fn test_iter_rev_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.open_db(None).unwrap();
+    {
+        let mut cursor = txn.cursor(db).unwrap();
+        assert_eq!(cursor.iter_rev_end::<(), ()>().unwrap().count(), 0);
+        assert_eq!(cursor.iter_rev_from::<(), ()>(b"key").unwrap().count(), 0);
+    }
+    for key in [b"b", b"d", b"f"] {
+        txn.put(db, key, key, WriteFlags::empty()).unwrap();
+    }
+    txn.commit().unwrap();
+
+    let txn = begin_ro(&env).unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+    let keys = |iter: Vec<(Vec<u8>, Vec<u8>)>| iter.into_iter().map(|(k, _)| k).collect::<Vec<_>>();
+
+    let items = cursor.iter_rev_end::<Vec<u8>, Vec<u8>>().unwrap().collect::<Result<Vec<_>>>();
+    assert_eq!(keys(items.unwrap()), [b"f", b"d", b"b"]);
+
+    let latest = cursor.iter_rev_end::<Vec<u8>, Vec<u8>>().unwrap().take(2);
+    assert_eq!(keys(latest.collect::<Result<Vec<_>>>().unwrap()), [b"f", b"d"]);
+
+    for (from, expected) in [
+        (&b"d"[..], &[b"d", b"b"][..]),
+        (b"e", &[b"d", b"b"]),
+        (b"z", &[b"f", b"d", b"b"]),
+        (b"a", &[]),
+    ] {
+        let iter = cursor.iter_rev_from::<Vec<u8>, Vec<u8>>(from).unwrap();
+        assert_eq!(keys(iter.collect::<Result<Vec<_>>>().unwrap()), expected);
+    }
+}
+
+#[test]
+fn test_iter_rev_v1() {
+    test_iter_rev_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_iter_rev_v2() {
+    test_iter_rev_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_iter_rev_dup_sort() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"c"] {
+        for value in [b"1", b"2", b"3"] {
+            txn.put(db, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let pairs = |pairs: &[(&[u8], &[u8])]| {
+        pairs.iter().map(|(k, v)| (k.to_vec(), v.to_vec())).collect::<Vec<_>>()
+    };
+
+    let items = cursor.iter_rev_end::<Vec<u8>, Vec<u8>>().unwrap().collect::<Result<Vec<_>>>();
+    assert_eq!(
+        items.unwrap(),
+        pairs(&[
+            (b"c", b"3"),
+            (b"c", b"2"),
+            (b"c", b"1"),
+            (b"a", b"3"),
+            (b"a", b"2"),
+            (b"a", b"1")
+        ])
+    );
+
+    // An exact key starts from its last duplicate.
+    let items = cursor.iter_rev_from::<Vec<u8>, Vec<u8>>(b"c").unwrap().take(2);
+    assert_eq!(items.collect::<Result<Vec<_>>>().unwrap(), pairs(&[(b"c", b"3"), (b"c", b"2")]));
+
+    let items = cursor.iter_rev_from::<Vec<u8>, Vec<u8>>(b"b").unwrap().take(2);
+    assert_eq!(items.collect::<Result<Vec<_>>>().unwrap(), pairs(&[(b"a", b"3"), (b"a", b"2")]));
+
+    let values = cursor.iter_dup_rev_of::<Vec<u8>>(b"a").unwrap().collect::<Result<Vec<_>>>();
+    assert_eq!(values.unwrap(), [b"3", b"2", b"1"]);
+    assert_eq!(cursor.iter_dup_rev_of::<Vec<u8>>(b"b").unwrap().count(), 0);
+}
+
+fn test_iter_dupfixed_rev_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED).unwrap();
+    {
+        let mut cursor = txn.cursor(db).unwrap();
+        assert_eq!(cursor.iter_dupfixed_rev::<Vec<u8>, [u8; 4]>().unwrap().count(), 0);
+    }
+    // Keys with one value, a few values, and values spanning several pages.
+    let counts = [1u32, 3, 5_000, 1, 2_500];
+    for (i, count) in counts.iter().enumerate() {
+        for value in 0..*count {
+            txn.put(db, &[i as u8], &value.to_be_bytes(), WriteFlags::empty()).unwrap();
+        }
+    }
+    txn.commit().unwrap();
+
+    let txn = begin_ro(&env).unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+
+    let forward: Vec<_> =
+        cursor.iter_dupfixed_start::<Vec<u8>, [u8; 4]>().unwrap().collect::<Result<_>>().unwrap();
+    let reverse: Vec<_> =
+        cursor.iter_dupfixed_rev::<Vec<u8>, [u8; 4]>().unwrap().collect::<Result<_>>().unwrap();
+
+    // Each key is yielded once, with its first value in iteration order.
+    for items in [&forward, &reverse] {
+        assert_eq!(items.iter().filter(|item| item.is_new_key()).count(), counts.len());
+    }
+    assert_eq!(reverse[0], DupItem::NewKey(vec![4], 2_499u32.to_be_bytes()));
+
+    let mut flat = collect_dup_items(forward.into_iter().map(Ok)).unwrap();
+    flat.reverse();
+    assert_eq!(collect_dup_items(reverse.into_iter().map(Ok)).unwrap(), flat);
+}
+
+#[test]
+fn test_iter_dupfixed_rev_v1() {
+    test_iter_dupfixed_rev_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_iter_dupfixed_rev_v2() {
+    test_iter_dupfixed_rev_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_iter_double_ended() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(Some("table"), DatabaseFlags::empty()).unwrap();
+    let other = txn.create_db(Some("other"), DatabaseFlags::empty()).unwrap();
+    let mut front = txn.cursor(db).unwrap();
+    let mut back = txn.cursor(db).unwrap();
+    assert_eq!(front.iter_double_ended::<(), ()>(&mut back).unwrap().count(), 0);
+
+    for len in [1u8, 2, 5] {
+        txn.clear_db(db).unwrap();
+        for i in 0..len {
+            txn.put(db, [i], [i], WriteFlags::empty()).unwrap();
+        }
+
+        let iter = front.iter_double_ended::<Vec<u8>, Vec<u8>>(&mut back).unwrap();
+        let items = iter.rev().map(|item| item.unwrap().0[0]).collect::<Vec<_>>();
+        assert_eq!(items, (0..len).rev().collect::<Vec<_>>());
+
+        // Alternating ends yields each item once.
+        let mut iter = front.iter_double_ended::<Vec<u8>, Vec<u8>>(&mut back).unwrap();
+        let mut seen = Vec::new();
+        for i in 0.. {
+            let item = if i % 2 == 0 { iter.next() } else { iter.next_back() };
+            let Some(item) = item else { break };
+            seen.push(item.unwrap().0[0]);
+        }
+        assert!(iter.next().is_none() && iter.next_back().is_none());
+        seen.sort();
+        assert_eq!(seen, (0..len).collect::<Vec<_>>());
+    }
+
+    // Both cursors must be on the same table.
+    let mut back = txn.cursor(other).unwrap();
+    let err = front.iter_double_ended::<(), ()>(&mut back).unwrap_err();
+    assert!(matches!(err, ReadError::Mdbx(MdbxError::Incompatible)));
+}
+
+fn test_range_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.open_db(None).unwrap();
+    for key in [b"b", b"c", b"d", b"e"] {
+        txn.put(db, key, key, WriteFlags::empty()).unwrap();
+    }
+    txn.commit().unwrap();
+
+    let txn = begin_ro(&env).unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+
+    let cases: [((_, _), &[u8]); 9] = [
+        ((Unbounded, Unbounded), b"bcde"),
+        ((Included(&b"c"[..]), Unbounded), b"cde"),
+        ((Excluded(&b"c"[..]), Unbounded), b"de"),
+        ((Unbounded, Included(&b"d"[..])), b"bcd"),
+        ((Unbounded, Excluded(&b"d"[..])), b"bc"),
+        ((Excluded(&b"b"[..]), Excluded(&b"e"[..])), b"cd"),
+        ((Included(&b"a"[..]), Included(&b"cc"[..])), b"bc"),
+        ((Included(&b"cc"[..]), Excluded(&b"z"[..])), b"de"),
+        ((Included(&b"d"[..]), Excluded(&b"c"[..])), b""),
+    ];
+    for (range, expected) in cases {
+        let keys = cursor
+            .range::<Vec<u8>, ()>(range)
+            .unwrap()
+            .map(|item| item.unwrap().0[0])
+            .collect::<Vec<_>>();
+        assert_eq!(keys, expected, "{range:?}");
+
+        let mut keys = cursor
+            .range_rev::<Vec<u8>, ()>(range)
+            .unwrap()
+            .map(|item| item.unwrap().0[0])
+            .collect::<Vec<_>>();
+        keys.reverse();
+        assert_eq!(keys, expected, "rev {range:?}");
+    }
+}
+
+#[test]
+fn test_range_v1() {
+    test_range_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_range_v2() {
+    test_range_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_range_comparator() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let integer = txn.create_db(Some("integer"), DatabaseFlags::INTEGER_KEY).unwrap();
+    for i in 0..1_000u64 {
+        txn.put(integer, i.to_ne_bytes(), [], WriteFlags::empty()).unwrap();
+    }
+    let reverse = txn.create_db(Some("reverse"), DatabaseFlags::REVERSE_KEY).unwrap();
+    for key in [&b"ab"[..], b"b", b"cb", b"ba", b"c"] {
+        txn.put(reverse, key, [], WriteFlags::empty()).unwrap();
+    }
+
+    // Integer keys are ordered numerically, not by their bytes.
+    let mut cursor = txn.cursor(integer).unwrap();
+    let (start, end) = (250u64.to_ne_bytes(), 260u64.to_ne_bytes());
+    let keys = cursor
+        .range::<[u8; 8], ()>(&start[..]..&end[..])
+        .unwrap()
+        .map(|item| u64::from_ne_bytes(item.unwrap().0))
+        .collect::<Vec<_>>();
+    assert_eq!(keys, (250..260).collect::<Vec<_>>());
+    let keys = cursor
+        .range_rev::<[u8; 8], ()>(..=&start[..])
+        .unwrap()
+        .take(2)
+        .map(|item| u64::from_ne_bytes(item.unwrap().0))
+        .collect::<Vec<_>>();
+    assert_eq!(keys, [250, 249]);
+
+    // Reversed keys are ordered by their last bytes, so a prefix in table
+    // order is a suffix of the key bytes.
+    let mut cursor = txn.cursor(reverse).unwrap();
+    let keys = |iter: IterRange<'_, '_, _, Vec<u8>, ()>| {
+        iter.map(|item| item.unwrap().0).collect::<Vec<_>>()
+    };
+    assert_eq!(keys(cursor.prefix(b"b").unwrap()), [b"b".to_vec(), b"ab".into(), b"cb".into()]);
+    assert_eq!(keys(cursor.range(&b"b"[..]..=&b"cb"[..]).unwrap()), [&b"b"[..], b"ab", b"cb"]);
+    let rev = cursor.range_rev::<Vec<u8>, ()>(..&b"ab"[..]).unwrap();
+    assert_eq!(rev.map(|item| item.unwrap().0).collect::<Vec<_>>(), [&b"b"[..], b"ba"]);
+}
+
+#[test]
+fn test_range_dup_sort() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"b", b"c"] {
+        for value in [b"1", b"2"] {
+            txn.put(db, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let items = |iter: Vec<(Vec<u8>, Vec<u8>)>| {
+        iter.into_iter().map(|(k, v)| [k[0], v[0]]).collect::<Vec<_>>()
+    };
+
+    // Excluded bounds skip every duplicate of the key.
+    let iter = cursor.range::<Vec<u8>, Vec<u8>>((
+        std::ops::Bound::Excluded(&b"a"[..]),
+        std::ops::Bound::Included(&b"b"[..]),
+    ));
+    assert_eq!(items(iter.unwrap().collect::<Result<_>>().unwrap()), [*b"b1", *b"b2"]);
+
+    // Included upper bounds start from the last duplicate of the key.
+    let iter = cursor.range_rev::<Vec<u8>, Vec<u8>>(&b"b"[..]..=&b"b"[..]);
+    assert_eq!(items(iter.unwrap().collect::<Result<_>>().unwrap()), [*b"b2", *b"b1"]);
+    let iter = cursor.range_rev::<Vec<u8>, Vec<u8>>(..&b"b"[..]);
+    assert_eq!(items(iter.unwrap().collect::<Result<_>>().unwrap()), [*b"a2", *b"a1"]);
+}
+
+#[test]
+fn test_prefix() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    for key in [&b"ap"[..], b"app", b"apple", b"apply", b"apq", b"b"] {
+        txn.put(db, key, [], WriteFlags::empty()).unwrap();
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let mut count = |prefix: &[u8]| cursor.prefix::<(), ()>(prefix).unwrap().count();
+    assert_eq!(count(b"app"), 3);
+    assert_eq!(count(b"apple"), 1);
+    assert_eq!(count(b"ap"), 5);
+    assert_eq!(count(b"aq"), 0);
+    assert_eq!(count(b"c"), 0);
+    assert_eq!(count(b""), 6);
+}
+
+#[test]
+fn test_dup_range() {
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(Some("dup"), DatabaseFlags::DUP_SORT).unwrap();
+    let flags = DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED | DatabaseFlags::INTEGER_DUP;
+    let integer = txn.create_db(Some("integer"), flags).unwrap();
+    for key in [b"a", b"b", b"c"] {
+        for value in [b"2", b"4", b"6"] {
+            txn.put(db, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+    for value in 0..300u32 {
+        txn.put(integer, b"key", value.to_ne_bytes(), WriteFlags::empty()).unwrap();
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let cases: [((_, _), &[u8]); 7] = [
+        ((Unbounded, Unbounded), b"246"),
+        ((Included(&b"4"[..]), Unbounded), b"46"),
+        ((Excluded(&b"4"[..]), Unbounded), b"6"),
+        ((Included(&b"3"[..]), Included(&b"6"[..])), b"46"),
+        ((Unbounded, Excluded(&b"4"[..])), b"2"),
+        ((Excluded(&b"2"[..]), Excluded(&b"6"[..])), b"4"),
+        ((Included(&b"7"[..]), Unbounded), b""),
+    ];
+    for (range, expected) in cases {
+        let values = cursor
+            .dup_range::<Vec<u8>>(b"b", range)
+            .unwrap()
+            .map(|value| value.unwrap()[0])
+            .collect::<Vec<_>>();
+        assert_eq!(values, expected, "{range:?}");
+
+        let mut values = cursor
+            .dup_range_rev::<Vec<u8>>(b"b", range)
+            .unwrap()
+            .map(|value| value.unwrap()[0])
+            .collect::<Vec<_>>();
+        values.reverse();
+        assert_eq!(values, expected, "rev {range:?}");
+    }
+    assert_eq!(cursor.dup_range::<Vec<u8>>(b"bb", ..).unwrap().count(), 0);
+    assert_eq!(cursor.dup_range_rev::<Vec<u8>>(b"bb", ..).unwrap().count(), 0);
+
+    // Integer values are ordered numerically, not by their bytes.
+    let mut cursor = txn.cursor(integer).unwrap();
+    let (start, end) = (250u32.to_ne_bytes(), 260u32.to_ne_bytes());
+    let values = cursor
+        .dup_range::<[u8; 4]>(b"key", &start[..]..&end[..])
+        .unwrap()
+        .map(|value| u32::from_ne_bytes(value.unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(values, (250..260).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_position_predicates() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"b"] {
+        for value in [b"1", b"2"] {
+            txn.put(db, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let state = |cursor: &Cursor<'_, _>| {
+        [
+            cursor.is_on_first(),
+            cursor.is_on_last(),
+            cursor.is_on_first_dup(),
+            cursor.is_on_last_dup(),
+        ]
+    };
+
+    cursor.first::<(), ()>().unwrap();
+    assert_eq!(state(&cursor), [true, false, true, false]);
+    // The first and last predicates only consider the key.
+    cursor.next::<(), ()>().unwrap();
+    assert_eq!(state(&cursor), [true, false, false, true]);
+    cursor.next::<(), ()>().unwrap();
+    assert_eq!(state(&cursor), [false, true, true, false]);
+    cursor.next::<(), ()>().unwrap();
+    assert_eq!(state(&cursor), [false, true, false, true]);
+}
+
+#[test]
+fn test_cursor_compare_and_reset() {
+    use std::cmp::Ordering;
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"b"] {
+        for value in [b"1", b"2"] {
+            txn.put(db, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    let mut left = txn.cursor(db).unwrap();
+    let mut right = txn.cursor(db).unwrap();
+    assert_eq!(left.compare(&right, false), Ok(Ordering::Equal));
+
+    left.first::<(), ()>().unwrap();
+    assert_eq!(left.compare(&right, false), Err(MdbxError::Incompatible));
+    right.first::<(), ()>().unwrap();
+    assert_eq!(left.compare(&right, false), Ok(Ordering::Equal));
+
+    right.next::<(), ()>().unwrap();
+    assert_eq!(left.compare(&right, false), Ok(Ordering::Less));
+    assert_eq!(right.compare(&left, false), Ok(Ordering::Greater));
+    assert_eq!(left.compare(&right, true), Ok(Ordering::Equal));
+
+    right.next::<(), ()>().unwrap();
+    assert_eq!(left.compare(&right, true), Ok(Ordering::Less));
+
+    left.reset().unwrap();
+    assert_eq!(left.get_current::<(), ()>().unwrap(), None);
+    assert_eq!(left.compare(&right, false), Err(MdbxError::Incompatible));
+    assert_eq!(left.first::<Vec<u8>, Vec<u8>>().unwrap(), Some((b"a".to_vec(), b"1".to_vec())));
+}
+
+#[test]
+fn test_estimates() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    for i in 0..100u32 {
+        txn.put(db, i.to_be_bytes(), [], WriteFlags::empty()).unwrap();
+    }
+
+    // Small trees fit in a single page, so estimates are exact.
+    let (ten, fifty) = (10u32.to_be_bytes(), 50u32.to_be_bytes());
+    assert_eq!(txn.estimate_range(db, Some(&ten), Some(&fifty)).unwrap(), 40);
+    assert_eq!(txn.estimate_range(db, Some(&fifty), Some(&ten)).unwrap(), -40);
+    assert_eq!(txn.estimate_range(db, None, None).unwrap(), 100);
+
+    let mut first = txn.cursor(db).unwrap();
+    let mut last = txn.cursor(db).unwrap();
+    first.set::<()>(&ten).unwrap();
+    last.set::<()>(&fifty).unwrap();
+    assert_eq!(first.estimate_distance(&last).unwrap(), 40);
+    assert_eq!(last.estimate_distance(&first).unwrap(), -40);
+
+    assert_eq!(first.estimate_move(signet_libmdbx::ffi::MDBX_NEXT, None).unwrap(), 1);
+    assert_eq!(first.estimate_move(signet_libmdbx::ffi::MDBX_SET_RANGE, Some(&fifty)).unwrap(), 40);
+    assert_eq!(first.estimate_move(signet_libmdbx::ffi::MDBX_LAST, None).unwrap(), 89);
+    // The cursor does not move.
+    assert_eq!(first.get_current::<Vec<u8>, ()>().unwrap(), Some((ten.to_vec(), ())));
+
+    first.reset().unwrap();
+    assert!(first.estimate_move(signet_libmdbx::ffi::MDBX_NEXT, None).is_err());
+}
+
+#[test]
+fn test_unbound_cursor() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(1).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(Some("table"), DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key1", b"val1", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    let unbound = UnboundCursor::new().unwrap();
+    assert!(unbound.db().is_none());
+
+    let txn = env.begin_ro_sync().unwrap();
+    let mut cursor = txn.bind_cursor(unbound, db).unwrap();
+    assert_eq!(cursor.first().unwrap(), Some((*b"key1", *b"val1")));
+    let unbound = cursor.unbind().unwrap();
+    assert_eq!(unbound.db().map(|db| db.dbi()), Some(db.dbi()));
+    drop(txn);
+
+    let txn = env.begin_rw_sync().unwrap();
+    txn.put(db, b"key2", b"val2", WriteFlags::empty()).unwrap();
+    let mut cursor = txn.renew_cursor(unbound).unwrap();
+    assert_eq!(cursor.last().unwrap(), Some((*b"key2", *b"val2")));
+
+    // A bound cursor keeps the database open, an unbound one does not.
+    assert_eq!(txn.close_db(db).unwrap_err(), MdbxError::DbiInUse);
+    let unbound = cursor.unbind().unwrap();
+    txn.commit().unwrap();
+
+    // Unbound cursors can move between threads.
+    let unbound = std::thread::spawn(move || unbound).join().unwrap();
+    let txn = env.begin_ro_sync().unwrap();
+    txn.close_db(db).unwrap();
+    assert_eq!(txn.renew_cursor(unbound).unwrap_err(), MdbxError::BadDbi);
+    let unbound = UnboundCursor::new().unwrap();
+    assert_eq!(txn.renew_cursor(unbound).unwrap_err(), MdbxError::BadDbi);
+}
+
+#[test]
+fn test_for_each() {
+    use std::ops::ControlFlow;
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    for (key, value) in [(b"a", b"1"), (b"b", b"2"), (b"c", b"3")] {
+        txn.put(db, key, value, WriteFlags::empty()).unwrap();
+    }
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let mut seen = Vec::new();
+    let flow = cursor.for_each_raw(ScanStart::First, |key, value| {
+        seen.push([key[0], value[0]]);
+        ControlFlow::<()>::Continue(())
+    });
+    assert_eq!(flow, Ok(ControlFlow::Continue(())));
+    assert_eq!(seen, [*b"a1", *b"b2", *b"c3"]);
+
+    // Breaking leaves the cursor on the item, to resume from.
+    let mut value_ptr = std::ptr::null();
+    let flow = cursor.for_each_raw(ScanStart::First, |key, value| {
+        value_ptr = value.as_ptr();
+        if key == b"b" { ControlFlow::Break(key.len()) } else { ControlFlow::Continue(()) }
+    });
+    assert_eq!(flow, Ok(ControlFlow::Break(1)));
+
+    // Items on dirty pages are passed without copying.
+    let mut key = ffi::MDBX_val { iov_len: 0, iov_base: std::ptr::null_mut() };
+    let mut data = ffi::MDBX_val { iov_len: 0, iov_base: std::ptr::null_mut() };
+    // SAFETY: The cursor is valid and positioned.
+    let rc = unsafe {
+        ffi::mdbx_cursor_get(cursor.cursor(), &mut key, &mut data, ffi::MDBX_GET_CURRENT)
+    };
+    assert_eq!(rc, ffi::MDBX_SUCCESS);
+    assert_eq!(data.iov_base.cast_const().cast::<u8>(), value_ptr);
+
+    let mut rest = Vec::new();
+    let flow = cursor.for_each::<[u8], Vec<u8>, (), _>(ScanStart::Current, |key, value| {
+        rest.push((key.to_vec(), value));
+        ControlFlow::Continue(())
+    });
+    assert_eq!(flow.unwrap(), ControlFlow::Continue(()));
+    assert_eq!(rest, [(b"b".to_vec(), b"2".to_vec()), (b"c".to_vec(), b"3".to_vec())]);
+
+    // Decoding errors end the iteration.
+    let flow =
+        cursor.for_each::<[u8; 2], (), (), _>(ScanStart::First, |_, _| ControlFlow::Continue(()));
+    assert!(matches!(flow, Err(ReadError::Mdbx(MdbxError::DecodeErrorLenDiff))));
+    assert_eq!(cursor.get_current().unwrap(), Some((*b"a", *b"1")));
+}
+
+#[test]
+fn test_dupfixed_pages() {
+    type KeyValues<T> = Vec<(Vec<u8>, Vec<T>)>;
+
+    /// Collects the values of each key, and counts pages borrowed in place.
+    fn collect<K: TransactionKind, T: FixedValue>(
+        cursor: &mut Cursor<'_, K>,
+    ) -> (KeyValues<T>, usize, usize) {
+        let (mut values, mut pages, mut borrowed) = (KeyValues::<T>::new(), 0, 0);
+        for page in cursor.dupfixed_pages::<Vec<u8>, T>().unwrap() {
+            let (key, page) = page.unwrap();
+            assert!(!page.is_empty());
+            pages += 1;
+            borrowed += usize::from(matches!(page, Cow::Borrowed(_)));
+            match values.last_mut() {
+                Some((last, values)) if *last == key => values.extend_from_slice(&page),
+                _ => values.push((key, page.into_owned())),
+            }
+        }
+        (values, pages, borrowed)
+    }
+
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(3).open(dir.path()).unwrap();
+
+    let flags = DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED | DatabaseFlags::INTEGER_DUP;
+    let txn = env.begin_rw_sync().unwrap();
+    let u64s = txn.create_db(Some("u64s"), flags).unwrap();
+    let u32s = txn.create_db(Some("u32s"), flags).unwrap();
+    let hashes =
+        txn.create_db(Some("hashes"), DatabaseFlags::DUP_SORT | DatabaseFlags::DUP_FIXED).unwrap();
+    for key in [b"a", b"b"] {
+        for value in 0..2000u32 {
+            txn.put(u64s, key, u64::from(value).to_ne_bytes(), WriteFlags::empty()).unwrap();
+            txn.put(u32s, key, value.to_ne_bytes(), WriteFlags::empty()).unwrap();
+        }
+    }
+    for value in 0..3u8 {
+        txn.put(hashes, b"key", [value; 32], WriteFlags::empty()).unwrap();
+    }
+    let expected = |n: fn(u32) -> u64| -> Vec<(Vec<u8>, Vec<u64>)> {
+        [b"a", b"b"].map(|key| (key.to_vec(), (0..2000).map(n).collect())).into()
+    };
+
+    // Dirty pages are copied.
+    let mut cursor = txn.cursor(u64s).unwrap();
+    let (values, pages, borrowed) = collect::<_, u64>(&mut cursor);
+    assert_eq!(values, expected(u64::from));
+    assert!(pages > 2, "values should span several pages");
+    assert_eq!(borrowed, 0);
+
+    // Values must match the size of the type.
+    assert!(matches!(
+        cursor.dupfixed_pages::<(), u32>(),
+        Err(ReadError::Mdbx(MdbxError::DecodeErrorLenDiff))
+    ));
+    drop(cursor);
+
+    let mut cursor = txn.cursor(hashes).unwrap();
+    let pages = cursor
+        .dupfixed_pages::<Vec<u8>, [u8; 32]>()
+        .unwrap()
+        .map(|page| page.unwrap().1.into_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(pages, [[[0; 32], [1; 32], [2; 32]]]);
+    drop(cursor);
+    txn.commit().unwrap();
+
+    // Clean pages are borrowed in place when aligned for the type, and
+    // copied otherwise.
+    let txn = env.begin_ro_sync().unwrap();
+    let mut cursor = txn.cursor(txn.open_db(Some("u64s")).unwrap()).unwrap();
+    assert_eq!(collect::<_, u64>(&mut cursor).0, expected(u64::from));
+    let mut cursor = txn.cursor(txn.open_db(Some("u32s")).unwrap()).unwrap();
+    let (values, pages, borrowed) = collect::<_, u32>(&mut cursor);
+    let values: Vec<_> = values
+        .into_iter()
+        .map(|(key, values)| (key, values.into_iter().map(u64::from).collect()))
+        .collect();
+    assert_eq!(values, expected(u64::from));
+    assert_eq!(borrowed, pages);
+}
+
+#[test]
+fn test_delete_range() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let integer = txn.create_db(Some("integer"), DatabaseFlags::INTEGER_KEY).unwrap();
+    for i in 0..1_000u64 {
+        txn.put(integer, i.to_ne_bytes(), [], WriteFlags::empty()).unwrap();
+    }
+    let dup = txn.create_db(Some("dup"), DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"b", b"c", b"d"] {
+        for value in [b"1", b"2", b"3"] {
+            txn.put(dup, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    let keys = |db| {
+        let mut cursor = txn.cursor(db).unwrap();
+        cursor.iter_start::<Vec<u8>, ()>().unwrap().map(|item| item.unwrap().0).collect::<Vec<_>>()
+    };
+    let int = |i: u64| i.to_ne_bytes();
+
+    // Integer keys are compared numerically, not by their bytes.
+    assert_eq!(txn.delete_range(integer, &int(250)[..]..&int(260)[..]).unwrap(), 10);
+    assert_eq!(txn.delete_range(integer, &int(250)[..]..&int(260)[..]).unwrap(), 0);
+    let excluded =
+        (std::ops::Bound::Excluded(&int(900)[..]), std::ops::Bound::Included(&int(950)[..]));
+    assert_eq!(txn.delete_range(integer, excluded).unwrap(), 50);
+    assert_eq!(txn.delete_range(integer, ..&int(100)[..]).unwrap(), 100);
+    assert_eq!(txn.delete_range(integer, &int(990)[..]..).unwrap(), 10);
+    let remaining = keys(integer);
+    assert_eq!(remaining.len(), 830);
+    assert_eq!(remaining.first().unwrap(), &int(100));
+    assert_eq!(remaining.last().unwrap(), &int(989));
+    assert!(remaining.contains(&int(900).to_vec()) && !remaining.contains(&int(950).to_vec()));
+
+    // Whole DUPSORT keys are deleted, counting each of their values.
+    assert_eq!(txn.delete_range(dup, &b"b"[..]..=&b"c"[..]).unwrap(), 6);
+    assert_eq!(keys(dup), [b"a", b"a", b"a", b"d", b"d", b"d"]);
+    assert_eq!(txn.delete_range(dup, ..).unwrap(), 6);
+    assert!(keys(dup).is_empty());
+    assert_eq!(txn.delete_range(dup, ..).unwrap(), 0);
+}
+
+#[test]
+fn test_delete_while() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let plain = txn.create_db(Some("plain"), DatabaseFlags::empty()).unwrap();
+    let dup = txn.create_db(Some("dup"), DatabaseFlags::DUP_SORT).unwrap();
+    for key in [b"a", b"b", b"c", b"d"] {
+        txn.put(plain, key, b"1", WriteFlags::empty()).unwrap();
+        for value in [b"1", b"2"] {
+            txn.put(dup, key, value, WriteFlags::empty()).unwrap();
+        }
+    }
+
+    // Nothing is deleted from an unpositioned cursor.
+    let mut cursor = txn.cursor(plain).unwrap();
+    assert_eq!(cursor.delete_while(|_, _| true).unwrap(), 0);
+
+    // Deletion stops at the first rejected item, leaving the cursor on it.
+    cursor.set::<()>(b"b").unwrap();
+    assert_eq!(cursor.delete_while(|key, _| key < b"d").unwrap(), 2);
+    assert_eq!(cursor.get_current().unwrap(), Some((*b"d", *b"1")));
+    assert_eq!(cursor.first().unwrap(), Some((*b"a", *b"1")));
+    assert_eq!(cursor.delete_while(|_, _| true).unwrap(), 2);
+    assert!(cursor.first::<(), ()>().unwrap().is_none());
+
+    // The predicate sees the first value of each DUPSORT key.
+    let mut cursor = txn.cursor(dup).unwrap();
+    cursor.first::<(), ()>().unwrap();
+    let mut seen = Vec::new();
+    let deleted = cursor
+        .delete_while(|key, value| {
+            seen.push((key.to_vec(), value.to_vec()));
+            key != b"c"
+        })
+        .unwrap();
+    assert_eq!(deleted, 4);
+    assert_eq!(
+        seen,
+        [(b"a".to_vec(), b"1".to_vec()), (b"b".into(), b"1".into()), (b"c".into(), b"1".into())]
+    );
+    assert_eq!(cursor.dup_count().unwrap(), 2);
+
+    // Deleting through the end does not revisit earlier keys.
+    seen.clear();
+    let deleted = cursor
+        .delete_while(|key, _| {
+            seen.push((key.to_vec(), Vec::new()));
+            true
+        })
+        .unwrap();
+    assert_eq!(deleted, 4);
+    assert_eq!(seen, [(b"c".to_vec(), Vec::new()), (b"d".to_vec(), Vec::new())]);
+    assert!(cursor.first::<(), ()>().unwrap().is_none());
+}
@@ -1,6 +1,10 @@
 #![allow(missing_docs)]
 use byteorder::{ByteOrder, LittleEndian};
 use signet_libmdbx::*;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tempfile::tempdir;
 
 #[test]
@@ -38,6 +42,125 @@ fn test_begin_txn() {
     }
 }
 
+#[test]
+fn test_try_begin_rw() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Attempts from another thread fail immediately while the lock is held.
+    let try_begin_elsewhere = |env: &Environment| {
+        let env = env.clone();
+        std::thread::spawn(move || {
+            (env.try_begin_rw_sync().map(drop), env.try_begin_rw_unsync().map(drop))
+        })
+        .join()
+        .unwrap()
+    };
+
+    let txn = env.try_begin_rw_sync().unwrap();
+    assert_eq!(try_begin_elsewhere(&env), (Err(MdbxError::Busy), Err(MdbxError::Busy)));
+    drop(txn);
+
+    let txn = env.try_begin_rw_unsync().unwrap();
+    assert_eq!(try_begin_elsewhere(&env), (Err(MdbxError::Busy), Err(MdbxError::Busy)));
+    txn.commit().unwrap();
+
+    assert_eq!(try_begin_elsewhere(&env), (Ok(()), Ok(())));
+}
+
+#[test]
+fn test_begin_rw_timeout() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder()
+        .set_rw_txn_backoff(Duration::from_millis(5))
+        .set_rw_txn_wait_warning(false)
+        .open(dir.path())
+        .unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let timeout = Duration::from_millis(50);
+    let timed_out = {
+        let env = env.clone();
+        std::thread::spawn(move || {
+            let start = Instant::now();
+            assert_eq!(env.begin_rw_sync_timeout(timeout).unwrap_err(), MdbxError::Busy);
+            assert_eq!(env.begin_rw_unsync_timeout(timeout).unwrap_err(), MdbxError::Busy);
+            start.elapsed()
+        })
+    };
+    assert!(timed_out.join().unwrap() >= timeout * 2);
+
+    // The lock is acquired once the holder finishes within the deadline.
+    let waiter = {
+        let env = env.clone();
+        std::thread::spawn(move || env.begin_rw_sync_timeout(Duration::from_secs(10)).is_ok())
+    };
+    std::thread::sleep(Duration::from_millis(20));
+    txn.commit().unwrap();
+    assert!(waiter.join().unwrap());
+
+    env.begin_rw_unsync_timeout(timeout).unwrap();
+}
+
+#[test]
+fn test_write_txn_reentrancy() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    fn assert_reentrant<T: std::fmt::Debug>(res: MdbxResult<T>, line: u32) {
+        let MdbxError::WriteTxnAlreadyHeldByThread { opened_at } = res.unwrap_err() else {
+            panic!("expected re-entrancy error");
+        };
+        if cfg!(debug_assertions) {
+            let opened_at = opened_at.unwrap();
+            assert!(opened_at.file().ends_with("environment.rs"));
+            assert_eq!(opened_at.line(), line);
+        } else {
+            assert!(opened_at.is_none());
+        }
+    }
+
+    let (mut txn, line) = (env.begin_rw_unsync().unwrap(), line!());
+    assert_reentrant(env.begin_rw_sync(), line);
+    assert_reentrant(env.try_begin_rw_sync(), line);
+    assert_reentrant(env.begin_rw_unsync(), line);
+    assert_reentrant(env.try_begin_rw_unsync(), line);
+    assert_reentrant(env.begin_rw_unsync_timeout(Duration::from_millis(10)), line);
+
+    // Nested writes are still allowed, and other threads simply wait.
+    txn.begin_nested_txn().unwrap().commit().unwrap();
+    let other = env.clone();
+    let res = std::thread::spawn(move || {
+        other.begin_rw_sync_timeout(Duration::from_millis(10)).map(drop)
+    });
+    assert_eq!(res.join().unwrap(), Err(MdbxError::Busy));
+    txn.abort().unwrap();
+
+    // Sync transactions are recorded while they stay on their thread.
+    let (txn, line) = (env.begin_rw_sync().unwrap(), line!());
+    assert_reentrant(env.begin_rw_sync(), line);
+    assert_reentrant(env.try_begin_rw_sync(), line);
+    assert_reentrant(env.begin_rw_sync_timeout(Duration::from_millis(10)), line);
+    assert_reentrant(env.begin_rw_unsync(), line);
+    txn.begin_nested_txn().unwrap().commit().unwrap();
+
+    // Once used from another thread, the thread that opened it waits for it
+    // like any other.
+    let (used_tx, used_rx) = std::sync::mpsc::channel();
+    let holder = std::thread::spawn(move || {
+        txn.id().unwrap();
+        used_tx.send(()).unwrap();
+        std::thread::sleep(Duration::from_millis(50));
+        txn.commit().unwrap();
+    });
+    used_rx.recv().unwrap();
+    assert_eq!(env.try_begin_rw_sync().unwrap_err(), MdbxError::Busy);
+    env.begin_rw_sync_timeout(Duration::from_secs(10)).unwrap().commit().unwrap();
+    holder.join().unwrap();
+
+    env.begin_rw_sync().unwrap();
+}
+
 #[test]
 fn test_open_db() {
     let dir = tempdir().unwrap();
@@ -67,6 +190,31 @@ fn test_close_database() {
     let txn = env.begin_rw_sync().unwrap();
     txn.create_db(Some("db"), DatabaseFlags::empty()).unwrap();
     txn.open_db(Some("db")).unwrap();
+    txn.commit().unwrap();
+
+    let txn = env.begin_ro_sync().unwrap();
+    let db = txn.open_db(Some("db")).unwrap();
+    txn.close_db(db).unwrap();
+    assert_eq!(txn.cursor(db).unwrap_err(), MdbxError::BadDbi);
+    assert_eq!(txn.close_db(db).unwrap_err(), MdbxError::BadDbi);
+}
+
+#[test]
+fn test_failed_close_keeps_handle() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(10).open(dir.path()).unwrap();
+
+    // MDBX refuses to close a handle to a table created by the open write
+    // transaction, and the handle stays valid.
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(Some("db"), DatabaseFlags::empty()).unwrap();
+    assert!(txn.close_db(db).is_err());
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+    assert_eq!(txn.get::<Vec<u8>>(db, b"key").unwrap(), Some(b"val".to_vec()));
+    txn.commit().unwrap();
+
+    let txn = env.begin_ro_sync().unwrap();
+    assert_eq!(txn.get::<Vec<u8>>(db, b"key").unwrap(), Some(b"val".to_vec()));
 }
 
 #[test]
@@ -168,3 +316,342 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_env_user_data() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    assert!(env.user_data::<String>().is_none());
+
+    env.set_user_data(std::sync::Mutex::new(String::from("config")));
+    assert!(env.user_data::<u32>().is_none());
+
+    // Data is mutated through interior mutability, and can be requested
+    // again while a handle is held.
+    let config = env.user_data::<std::sync::Mutex<String>>().unwrap();
+    config.lock().unwrap().push_str("-v2");
+    let again = env.user_data::<std::sync::Mutex<String>>().unwrap();
+    assert_eq!(*again.lock().unwrap(), "config-v2");
+    drop((config, again));
+
+    // Transaction and environment data are independent.
+    let txn = env.begin_ro_sync().unwrap();
+    assert!(txn.user_data::<String>().is_none());
+    drop(txn);
+
+    // The data is dropped along with the last environment handle.
+    let data = Arc::new(());
+    env.set_user_data(data.clone());
+    let clone = env.clone();
+    drop(env);
+    assert_eq!(Arc::strong_count(&data), 2);
+    assert!(clone.user_data::<Arc<()>>().is_some());
+    drop(clone);
+    assert_eq!(Arc::strong_count(&data), 1);
+}
+
+/// Opens an environment with a 1MiB map and the given policy.
+fn small_env(policy: MapFullPolicy) -> (tempfile::TempDir, Environment) {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder()
+        .set_geometry(Geometry { size: Some(0..(1 << 20)), ..Default::default() })
+        .on_map_full(policy)
+        .open(dir.path())
+        .unwrap();
+    (dir, env)
+}
+
+/// Writes enough data to overflow a 1MiB map.
+fn fill(txn: &TxSync<RwSync>) -> ReadResult<()> {
+    let db = txn.open_db(None)?;
+    for i in 0..20_000u32 {
+        txn.put(db, i.to_be_bytes(), [7u8; 100], WriteFlags::empty())?;
+    }
+    Ok(())
+}
+
+fn assert_mdbx(err: ReadError, expected: MdbxError) {
+    assert!(matches!(err, ReadError::Mdbx(err) if err == expected), "{err:?}");
+}
+
+#[test]
+fn test_update() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    assert_eq!(env.map_full_policy(), MapFullPolicy::Fail);
+    assert_eq!(env.retry_policy(), RetryPolicy::default());
+
+    let value = env
+        .update(|txn| -> ReadResult<_> {
+            let db = txn.open_db(None)?;
+            txn.put(db, b"a", b"1", WriteFlags::empty())?;
+            Ok(42)
+        })
+        .unwrap();
+    assert_eq!(value, 42);
+
+    // Failing and panicking closures abort their transactions.
+    let err = env
+        .update(|txn| -> ReadResult<()> {
+            let db = txn.open_db(None)?;
+            txn.put(db, b"b", b"2", WriteFlags::empty())?;
+            Err(MdbxError::NotFound.into())
+        })
+        .unwrap_err();
+    assert_mdbx(err, MdbxError::NotFound);
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        env.update(|txn| -> ReadResult<()> {
+            let db = txn.open_db(None)?;
+            txn.put(db, b"c", b"3", WriteFlags::empty())?;
+            panic!("boom")
+        })
+    }));
+    assert!(panicked.is_err());
+
+    let values = env
+        .view(|txn| -> ReadResult<_> {
+            let db = txn.open_db(None)?;
+            Ok([
+                txn.get::<Vec<u8>>(db, b"a")?,
+                txn.get::<Vec<u8>>(db, b"b")?,
+                txn.get::<Vec<u8>>(db, b"c")?,
+            ])
+        })
+        .unwrap();
+    assert_eq!(values, [Some(b"1".to_vec()), None, None]);
+}
+
+#[test]
+fn test_update_unsync() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    env.update_unsync(|txn| -> ReadResult<()> {
+        let db = txn.open_db(None)?;
+        Ok(txn.put(db, b"a", b"1", WriteFlags::empty())?)
+    })
+    .unwrap();
+    let err = env
+        .update_unsync(|txn| -> ReadResult<()> {
+            let db = txn.open_db(None)?;
+            txn.put(db, b"b", b"2", WriteFlags::empty())?;
+            Err(MdbxError::NotFound.into())
+        })
+        .unwrap_err();
+    assert_mdbx(err, MdbxError::NotFound);
+
+    let count = env
+        .view_unsync(|txn| -> ReadResult<_> {
+            let db = txn.open_db(None)?;
+            Ok(txn.db_stat(&db)?.entries())
+        })
+        .unwrap();
+    assert_eq!(count, 1);
+}
+
+/// An application error, whose MDBX errors the helpers recover from.
+#[derive(Debug)]
+enum AppError {
+    Read(ReadError),
+    Invalid,
+}
+
+impl Retryable for AppError {
+    fn mdbx_error(&self) -> Option<MdbxError> {
+        match self {
+            Self::Read(err) => err.mdbx_error(),
+            Self::Invalid => None,
+        }
+    }
+}
+
+impl From<MdbxError> for AppError {
+    fn from(err: MdbxError) -> Self {
+        Self::Read(err.into())
+    }
+}
+
+impl From<ReadError> for AppError {
+    fn from(err: ReadError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[test]
+fn test_update_retry() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder()
+        .set_retry_policy(RetryPolicy { max_retries: 2, backoff: Duration::from_millis(1) })
+        .open(dir.path())
+        .unwrap();
+
+    // Transient errors are retried, up to the limit.
+    let mut runs = 0;
+    let value = env
+        .update(|_| -> ReadResult<_> {
+            runs += 1;
+            if runs < 3 { Err(MdbxError::Busy.into()) } else { Ok(runs) }
+        })
+        .unwrap();
+    assert_eq!(value, 3);
+
+    let mut runs = 0;
+    let err = env
+        .view(|_| -> ReadResult<()> {
+            runs += 1;
+            Err(MdbxError::BadRslot.into())
+        })
+        .unwrap_err();
+    assert_mdbx(err, MdbxError::BadRslot);
+    assert_eq!(runs, 3);
+
+    // Application errors are retried if caused by transient errors.
+    let mut runs = 0;
+    let value = env
+        .update_unsync(|_| -> Result<_, AppError> {
+            runs += 1;
+            if runs < 3 { Err(MdbxError::Busy.into()) } else { Ok(runs) }
+        })
+        .unwrap();
+    assert_eq!(value, 3);
+
+    // Other errors are not.
+    let mut runs = 0;
+    let err = env
+        .update(|_| -> ReadResult<()> {
+            runs += 1;
+            Err(MdbxError::NotFound.into())
+        })
+        .unwrap_err();
+    assert_mdbx(err, MdbxError::NotFound);
+    assert_eq!(runs, 1);
+
+    let mut runs = 0;
+    let err = env
+        .update(|_| -> Result<(), AppError> {
+            runs += 1;
+            Err(AppError::Invalid)
+        })
+        .unwrap_err();
+    assert!(matches!(err, AppError::Invalid));
+    assert_eq!(runs, 1);
+    let err = env
+        .view_unsync(|txn| -> Result<(), AppError> {
+            txn.open_db(Some("missing"))?;
+            Ok(())
+        })
+        .unwrap_err();
+    assert!(matches!(err, AppError::Read(ReadError::Mdbx(_))));
+}
+
+#[test]
+fn test_update_map_full_fail() {
+    let (_dir, env) = small_env(MapFullPolicy::Fail);
+    let mut runs = 0;
+    let err = env
+        .update(|txn| {
+            runs += 1;
+            fill(txn)
+        })
+        .unwrap_err();
+    assert_mdbx(err, MdbxError::MapFull);
+    assert_eq!(runs, 1);
+}
+
+#[test]
+fn test_update_map_full_at_max() {
+    // The upper bound is already the maximum size, so the map can at most
+    // grow to it.
+    let (_dir, env) = small_env(MapFullPolicy::Grow { step: 1 << 20, max: 1 << 20 });
+    let mut runs = 0;
+    let err = env
+        .update(|txn| {
+            runs += 1;
+            fill(txn)
+        })
+        .unwrap_err();
+    assert_mdbx(err, MdbxError::MapFull);
+    assert!(runs <= 2);
+    let geometry = env.info().unwrap().geometry();
+    assert_eq!(geometry.current(), 1 << 20);
+    assert_eq!(geometry.max(), 1 << 20);
+}
+
+#[test]
+fn test_update_map_full_grow() {
+    // The upper bound reserves address space for 16MiB, and a zero growth
+    // step leaves growing past 1MiB to the policy.
+    let dir = tempdir().unwrap();
+    let env = Environment::builder()
+        .set_geometry(Geometry {
+            size: Some((1 << 20)..(16 << 20)),
+            growth_step: Some(0),
+            ..Default::default()
+        })
+        .on_map_full(MapFullPolicy::Grow { step: 1 << 20, max: 16 << 20 })
+        .open(dir.path())
+        .unwrap();
+
+    let mut runs = 0;
+    env.update(|txn| -> Result<(), AppError> {
+        runs += 1;
+        Ok(fill(txn)?)
+    })
+    .unwrap();
+    assert!(runs > 1);
+    let geometry = env.info().unwrap().geometry();
+    assert!(geometry.current() > 1 << 20);
+    assert_eq!(geometry.max(), 16 << 20);
+
+    let txn = env.begin_ro_sync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    assert_eq!(txn.db_stat(&db).unwrap().entries(), 20_000);
+}
+
+/// Set in the child process of [`test_view_after_external_growth`] to the
+/// path of the environment it grows.
+const GROW_ENV_VAR: &str = "SIGNET_LIBMDBX_GROW_ENV";
+
+#[test]
+fn test_view_after_external_growth() {
+    if let Some(path) = std::env::var_os(GROW_ENV_VAR) {
+        // Child: grow the map past the parent's upper bound from a second
+        // environment. MDBX cannot move the mapping of a `MDBX_NOTLS`
+        // environment, so this fails unless the address space following it
+        // is free.
+        let env = Environment::builder()
+            .on_map_full(MapFullPolicy::Grow { step: 1 << 20, max: 16 << 20 })
+            .open(std::path::Path::new(&path))
+            .unwrap();
+        env.update(fill).unwrap();
+        return;
+    }
+
+    let (dir, env) = small_env(MapFullPolicy::Fail);
+    env.view(|txn| -> ReadResult<_> { Ok(txn.open_db(None).map(drop)?) }).unwrap();
+
+    let grown = std::process::Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "test_view_after_external_growth"])
+        .env(GROW_ENV_VAR, dir.path())
+        .output()
+        .unwrap()
+        .status
+        .success();
+
+    // The reader re-syncs the geometry and retries once, which fails for the
+    // same reason the child may have.
+    let mut runs = 0;
+    let result = env.view(|txn| -> ReadResult<_> {
+        runs += 1;
+        let db = txn.open_db(None)?;
+        Ok(txn.db_stat(&db)?.entries())
+    });
+    match result {
+        Ok(entries) => assert_eq!((entries, runs), (if grown { 20_000 } else { 0 }, 1)),
+        Err(err) => {
+            assert!(grown);
+            assert_mdbx(err, MdbxError::UnableExtendMapSize);
+            assert_eq!(runs, 0);
+        }
+    }
+}
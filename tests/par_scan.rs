@@ -0,0 +1,115 @@
+#![allow(missing_docs)]
+use signet_libmdbx::*;
+use std::ops::{Bound, RangeBounds};
+use tempfile::tempdir;
+
+/// Scans `range` and returns the items of each chunk.
+fn scan<'r>(
+    env: &Environment,
+    db: Database,
+    range: impl RangeBounds<&'r [u8]>,
+    n: usize,
+) -> Vec<Vec<(Vec<u8>, Vec<u8>)>> {
+    env.par_scan(db, range, n, |chunk| {
+        chunk.map(|item| item.map(|(k, v)| (k.into_owned(), v.into_owned()))).collect()
+    })
+    .unwrap()
+}
+
+fn setup(
+    flags: DatabaseFlags,
+    items: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+) -> (tempfile::TempDir, Environment, Database) {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, flags).unwrap();
+    for (k, v) in items {
+        txn.put(db, k, v, WriteFlags::empty()).unwrap();
+    }
+    txn.commit().unwrap();
+    (dir, env, db)
+}
+
+fn all_items(env: &Environment, db: Database) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let txn = env.begin_ro_sync().unwrap();
+    let mut cursor = txn.cursor(db).unwrap();
+    cursor.iter_start::<Vec<u8>, Vec<u8>>().unwrap().collect::<ReadResult<_>>().unwrap()
+}
+
+#[test]
+fn test_par_scan_visits_every_key_once() {
+    let items = (0..10_000u32).map(|i| (i.to_be_bytes().to_vec(), i.to_le_bytes().to_vec()));
+    let (_dir, env, db) = setup(DatabaseFlags::empty(), items);
+    let expected = all_items(&env, db);
+
+    let chunks = scan(&env, db, .., 4);
+    assert!(chunks.len() > 1);
+    assert!(chunks.iter().all(|chunk| !chunk.is_empty()));
+    assert_eq!(chunks.concat(), expected);
+
+    // A single thread scans the whole range.
+    assert_eq!(scan(&env, db, .., 1).concat(), expected);
+    assert_eq!(scan(&env, db, .., 0), [expected]);
+}
+
+#[test]
+fn test_par_scan_bounds() {
+    let items = (0..1_000u32).map(|i| (i.to_be_bytes().to_vec(), vec![]));
+    let (_dir, env, db) = setup(DatabaseFlags::empty(), items);
+    let keys = |range: (Bound<&[u8]>, Bound<&[u8]>)| -> Vec<u32> {
+        scan(&env, db, range, 3)
+            .concat()
+            .into_iter()
+            .map(|(k, _)| u32::from_be_bytes(k.try_into().unwrap()))
+            .collect()
+    };
+
+    let (lo, hi) = (100u32.to_be_bytes(), 900u32.to_be_bytes());
+    assert_eq!(
+        keys((Bound::Included(&lo[..]), Bound::Excluded(&hi[..]))),
+        (100..900).collect::<Vec<_>>()
+    );
+    assert_eq!(scan(&env, db, &lo[..]..&hi[..], 3).concat().len(), 800,);
+    assert_eq!(
+        keys((Bound::Excluded(&lo[..]), Bound::Included(&hi[..]))),
+        (101..=900).collect::<Vec<_>>()
+    );
+    assert_eq!(keys((Bound::Unbounded, Bound::Excluded(&lo[..]))), (0..100).collect::<Vec<_>>());
+    assert_eq!(keys((Bound::Included(&hi[..]), Bound::Unbounded)), (900..1000).collect::<Vec<_>>());
+    assert!(keys((Bound::Included(&hi[..]), Bound::Excluded(&lo[..]))).is_empty());
+}
+
+#[test]
+fn test_par_scan_dup_sort() {
+    let items =
+        (0..2_000u32).flat_map(|i| (0..5u8).map(move |j| (i.to_be_bytes().to_vec(), vec![j])));
+    let (_dir, env, db) = setup(DatabaseFlags::DUP_SORT, items);
+    let expected = all_items(&env, db);
+
+    let chunks = scan(&env, db, .., 4);
+    assert_eq!(chunks.concat(), expected);
+    // The values of a key are never split across chunks.
+    for pair in chunks.windows(2) {
+        if let (Some(last), Some(first)) = (pair[0].last(), pair[1].first()) {
+            assert_ne!(last.0, first.0);
+        }
+    }
+}
+
+#[test]
+fn test_par_scan_integer_key() {
+    let items = (0..5_000u64).map(|i| ((i * 7919).to_ne_bytes().to_vec(), vec![]));
+    let (_dir, env, db) = setup(DatabaseFlags::INTEGER_KEY, items);
+    let expected = all_items(&env, db);
+
+    let chunks = scan(&env, db, .., 4);
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks.concat(), expected);
+}
+
+#[test]
+fn test_par_scan_empty() {
+    let (_dir, env, db) = setup(DatabaseFlags::empty(), []);
+    assert_eq!(scan(&env, db, .., 4), [vec![]]);
+}
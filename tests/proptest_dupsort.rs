@@ -399,7 +399,7 @@ proptest! {
         prop_assert!(deleted);
 
         // After deletion, get should return None
-        let result: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+        let result: Option<Vec<u8>> = txn.get(db, &key).unwrap();
         prop_assert!(result.is_none());
     }
 
@@ -430,7 +430,7 @@ proptest! {
         let deleted = txn.del(db, &key, None).unwrap();
         prop_assert!(deleted);
 
-        let result: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+        let result: Option<Vec<u8>> = txn.get(db, &key).unwrap();
         prop_assert!(result.is_none());
     }
 }
@@ -44,7 +44,7 @@ proptest! {
 
         // If put succeeded, get should not panic
         if put_result.is_ok() {
-            let _: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let _: Option<Vec<u8>> = txn.get(db, &key).unwrap();
         }
     }
 
@@ -71,7 +71,7 @@ proptest! {
         let db = txn.open_db(None).unwrap();
 
         // Get on nonexistent key should return Ok(None), not panic
-        let result: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(db.dbi(), &key);
+        let result: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(db, &key);
         prop_assert!(result.is_ok());
         prop_assert!(result.unwrap().is_none());
     }
@@ -89,7 +89,7 @@ proptest! {
         prop_assert!(put_result.is_ok());
 
         let get_result: signet_libmdbx::ReadResult<Option<Vec<u8>>> =
-            txn.get(db.dbi(), b"");
+            txn.get(db, b"");
         prop_assert!(get_result.is_ok());
 
         let del_result = txn.del(db, b"", None);
@@ -112,7 +112,7 @@ proptest! {
         prop_assert!(put_result.is_ok());
 
         let get_result: signet_libmdbx::ReadResult<Option<Vec<u8>>> =
-            txn.get(db.dbi(), &key);
+            txn.get(db, &key);
         prop_assert!(get_result.is_ok());
         prop_assert!(get_result.unwrap().is_some());
     }
@@ -138,7 +138,7 @@ proptest! {
 
         // If put succeeded, get should not panic
         if put_result.is_ok() {
-            let _: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let _: Option<Vec<u8>> = txn.get(db, &key).unwrap();
         }
     }
 
@@ -165,7 +165,7 @@ proptest! {
         let db = txn.open_db(None).unwrap();
 
         // Get on nonexistent key should return Ok(None), not panic
-        let result: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(db.dbi(), &key);
+        let result: signet_libmdbx::ReadResult<Option<Vec<u8>>> = txn.get(db, &key);
         prop_assert!(result.is_ok());
         prop_assert!(result.unwrap().is_none());
     }
@@ -182,7 +182,7 @@ proptest! {
         prop_assert!(put_result.is_ok());
 
         let get_result: signet_libmdbx::ReadResult<Option<Vec<u8>>> =
-            txn.get(db.dbi(), b"");
+            txn.get(db, b"");
         prop_assert!(get_result.is_ok());
 
         let del_result = txn.del(db, b"", None);
@@ -203,7 +203,7 @@ proptest! {
         prop_assert!(put_result.is_ok());
 
         let get_result: signet_libmdbx::ReadResult<Option<Vec<u8>>> =
-            txn.get(db.dbi(), &key);
+            txn.get(db, &key);
         prop_assert!(get_result.is_ok());
         prop_assert!(get_result.unwrap().is_some());
     }
@@ -226,7 +226,7 @@ proptest! {
 
         let put_result = txn.put(db, &key, &value, WriteFlags::empty());
         if put_result.is_ok() {
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, Some(value));
         }
     }
@@ -249,7 +249,7 @@ proptest! {
 
         let put_result = txn.put(db, &key, &value, WriteFlags::empty());
         if put_result.is_ok() {
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, Some(value));
         }
     }
@@ -278,7 +278,7 @@ proptest! {
         let put2 = txn.put(db, &key, &value2, WriteFlags::empty());
 
         if put1.is_ok() && put2.is_ok() {
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, Some(value2));
         }
     }
@@ -307,7 +307,7 @@ proptest! {
         let put2 = txn.put(db, &key, &value2, WriteFlags::empty());
 
         if put1.is_ok() && put2.is_ok() {
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, Some(value2));
         }
     }
@@ -333,7 +333,7 @@ proptest! {
             let deleted = txn.del(db, &key, None).unwrap();
             prop_assert!(deleted);
 
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, None);
         }
     }
@@ -359,7 +359,7 @@ proptest! {
             let deleted = txn.del(db, &key, None).unwrap();
             prop_assert!(deleted);
 
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, None);
         }
     }
@@ -475,7 +475,7 @@ proptest! {
 
         let put_result = txn.put(db, &key, &value, WriteFlags::empty());
         if put_result.is_ok() {
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, Some(value));
         }
     }
@@ -496,7 +496,7 @@ proptest! {
 
         let put_result = txn.put(db, &key, &value, WriteFlags::empty());
         if put_result.is_ok() {
-            let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+            let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
             prop_assert_eq!(retrieved, Some(value));
         }
     }
@@ -532,8 +532,8 @@ proptest! {
         let put_b = txn.put(db_b, &key, &value_b, WriteFlags::empty());
 
         if put_a.is_ok() && put_b.is_ok() {
-            let retrieved_a: Option<Vec<u8>> = txn.get(db_a.dbi(), &key).unwrap();
-            let retrieved_b: Option<Vec<u8>> = txn.get(db_b.dbi(), &key).unwrap();
+            let retrieved_a: Option<Vec<u8>> = txn.get(db_a, &key).unwrap();
+            let retrieved_b: Option<Vec<u8>> = txn.get(db_b, &key).unwrap();
             // Each db should return its own value, not the other's
             prop_assert_eq!(retrieved_a, Some(value_a));
             prop_assert_eq!(retrieved_b, Some(value_b));
@@ -562,8 +562,8 @@ proptest! {
         let put_b = txn.put(db_b, &key, &value_b, WriteFlags::empty());
 
         if put_a.is_ok() && put_b.is_ok() {
-            let retrieved_a: Option<Vec<u8>> = txn.get(db_a.dbi(), &key).unwrap();
-            let retrieved_b: Option<Vec<u8>> = txn.get(db_b.dbi(), &key).unwrap();
+            let retrieved_a: Option<Vec<u8>> = txn.get(db_a, &key).unwrap();
+            let retrieved_b: Option<Vec<u8>> = txn.get(db_b, &key).unwrap();
             prop_assert_eq!(retrieved_a, Some(value_a));
             prop_assert_eq!(retrieved_b, Some(value_b));
         }
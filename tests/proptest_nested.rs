@@ -39,7 +39,7 @@ proptest! {
                 nested.commit().unwrap();
 
                 // After commit, parent should see the value.
-                let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+                let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
                 prop_assert_eq!(retrieved, Some(value));
             }
         }
@@ -65,7 +65,7 @@ proptest! {
         let db = txn.open_db(None).unwrap();
 
         // Confirm the key is not yet present.
-        let before: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+        let before: Option<Vec<u8>> = txn.get(db, &key).unwrap();
         prop_assume!(before.is_none());
 
         {
@@ -77,7 +77,7 @@ proptest! {
                 drop(nested);
 
                 // Parent should NOT see the value.
-                let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &key).unwrap();
+                let retrieved: Option<Vec<u8>> = txn.get(db, &key).unwrap();
                 prop_assert!(retrieved.is_none());
             }
         }
@@ -120,12 +120,12 @@ proptest! {
         }
 
         // Parent write must still be visible.
-        let retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &parent_key).unwrap();
+        let retrieved: Option<Vec<u8>> = txn.get(db, &parent_key).unwrap();
         prop_assert_eq!(retrieved, Some(parent_value));
 
         // If the keys differ, child write must NOT be visible.
         if parent_key != child_key {
-            let child_retrieved: Option<Vec<u8>> = txn.get(db.dbi(), &child_key).unwrap();
+            let child_retrieved: Option<Vec<u8>> = txn.get(db, &child_key).unwrap();
             prop_assert!(child_retrieved.is_none());
         }
     }
@@ -0,0 +1,198 @@
+#![allow(missing_docs)]
+use signet_libmdbx::*;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use tempfile::tempdir;
+
+fn get(txn: &tx::aliases::RwTxSync, db: Database, key: &[u8]) -> Option<Vec<u8>> {
+    txn.get(db, key).unwrap()
+}
+
+fn savepoint_impl(builder: &mut EnvironmentBuilder, emulated: bool) {
+    let dir = tempdir().unwrap();
+    let env = builder.open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"a", b"0", WriteFlags::empty()).unwrap();
+
+    // Ok releases the writes.
+    let res = txn.savepoint(|sp| {
+        assert_eq!(sp.is_emulated(), emulated);
+        sp.put(db, b"a", b"1", WriteFlags::empty())?;
+        sp.put(db, b"b", b"1", WriteFlags::empty())
+    });
+    assert_eq!(res, Ok(()));
+    assert_eq!(get(&txn, db, b"a").as_deref(), Some(&b"1"[..]));
+    assert_eq!(get(&txn, db, b"b").as_deref(), Some(&b"1"[..]));
+
+    // Err rolls back every write made in the savepoint.
+    let res = txn.savepoint(|sp| {
+        sp.put(db, b"a", b"2", WriteFlags::empty())?;
+        sp.del(db, b"b", None)?;
+        sp.put(db, b"c", b"2", WriteFlags::empty())?;
+        assert_eq!(sp.get::<Vec<u8>>(db, b"a").unwrap().as_deref(), Some(&b"2"[..]));
+        sp.put(db, b"a", b"3", WriteFlags::NO_OVERWRITE)
+    });
+    assert_eq!(res, Err(MdbxError::KeyExist));
+    assert_eq!(get(&txn, db, b"a").as_deref(), Some(&b"1"[..]));
+    assert_eq!(get(&txn, db, b"b").as_deref(), Some(&b"1"[..]));
+    assert_eq!(get(&txn, db, b"c"), None);
+
+    // A panic rolls back.
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        txn.savepoint(|sp| -> MdbxResult<()> {
+            sp.put(db, b"d", b"4", WriteFlags::empty())?;
+            panic!("boom");
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(get(&txn, db, b"d"), None);
+
+    // The guard rolls back explicitly or on drop, and releases explicitly.
+    let sp = txn.begin_savepoint().unwrap();
+    sp.put(db, b"e", b"5", WriteFlags::empty()).unwrap();
+    sp.rollback().unwrap();
+    let sp = txn.begin_savepoint().unwrap();
+    sp.put(db, b"e", b"5", WriteFlags::empty()).unwrap();
+    drop(sp);
+    assert_eq!(get(&txn, db, b"e"), None);
+    let sp = txn.begin_savepoint().unwrap();
+    sp.put(db, b"e", b"5", WriteFlags::empty()).unwrap();
+    sp.release().unwrap();
+    assert_eq!(get(&txn, db, b"e").as_deref(), Some(&b"5"[..]));
+
+    // Only nested savepoints expose the full transaction API.
+    let sp = txn.begin_savepoint().unwrap();
+    if emulated {
+        assert_eq!(sp.txn().unwrap_err(), MdbxError::NestedTransactionsUnsupportedWithWriteMap);
+    } else {
+        sp.txn().unwrap().put(db, b"f", b"6", WriteFlags::empty()).unwrap();
+    }
+    drop(sp);
+    assert_eq!(get(&txn, db, b"f"), None);
+
+    txn.commit().unwrap();
+}
+
+#[test]
+fn test_savepoint_nested() {
+    savepoint_impl(&mut Environment::builder(), false);
+}
+
+#[test]
+fn test_savepoint_emulated() {
+    savepoint_impl(Environment::builder().write_map(), true);
+}
+
+fn savepoint_changes_impl(builder: &mut EnvironmentBuilder) {
+    let dir = tempdir().unwrap();
+    let env = builder.open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    txn.record_changes(false, move |set| tx.send(set).unwrap());
+    txn.put(db, b"a", b"0", WriteFlags::empty()).unwrap();
+
+    txn.savepoint(|sp| sp.put(db, b"b", b"1", WriteFlags::empty())).unwrap();
+    let res = txn.savepoint(|sp| {
+        sp.put(db, b"a", b"2", WriteFlags::empty())?;
+        sp.del(db, b"b", None)?;
+        sp.put(db, b"c", b"2", WriteFlags::empty())?;
+        Err::<(), _>(MdbxError::NotFound)
+    });
+    assert_eq!(res, Err(MdbxError::NotFound));
+    txn.commit().unwrap();
+
+    // Neither the rolled back writes nor the writes undoing them are
+    // recorded.
+    let set = rx.recv().unwrap();
+    let changes: Vec<_> = set
+        .changes()
+        .iter()
+        .map(|c| (c.op(), c.key().to_vec(), c.value().map(<[u8]>::to_vec)))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (ChangeOp::Put, b"a".to_vec(), Some(b"0".to_vec())),
+            (ChangeOp::Put, b"b".to_vec(), Some(b"1".to_vec())),
+        ]
+    );
+}
+
+#[test]
+fn test_savepoint_changes_nested() {
+    savepoint_changes_impl(&mut Environment::builder());
+}
+
+#[test]
+fn test_savepoint_changes_emulated() {
+    savepoint_changes_impl(Environment::builder().write_map());
+}
+
+#[test]
+fn test_savepoint_rollback_failure_keeps_error() {
+    for builder in [Environment::builder(), Environment::builder().write_map().clone()] {
+        let dir = tempdir().unwrap();
+        let env = builder.open(dir.path()).unwrap();
+
+        let txn = env.begin_rw_sync().unwrap();
+        let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+        let handle = txn.cancel_handle();
+
+        // Cancelling the transaction makes an emulated rollback fail, but
+        // the closure's error is still returned.
+        let res = txn.savepoint(|sp| {
+            sp.put(db, b"a", b"1", WriteFlags::empty())?;
+            handle.cancel()?;
+            Err::<(), _>(MdbxError::NotFound)
+        });
+        assert_eq!(res, Err(MdbxError::NotFound));
+    }
+}
+
+#[test]
+fn test_savepoint_emulated_dup_sort() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().write_map().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::DUP_SORT).unwrap();
+    txn.put(db, b"k", b"1", WriteFlags::empty()).unwrap();
+    txn.put(db, b"k", b"2", WriteFlags::empty()).unwrap();
+
+    let res = txn.savepoint(|sp| {
+        sp.del(db, b"k", Some(b"1"))?;
+        sp.put(db, b"k", b"3", WriteFlags::empty())?;
+        sp.del(db, b"k", None)?;
+        sp.put(db, b"k", b"4", WriteFlags::empty())?;
+        Err::<(), _>(MdbxError::NotFound)
+    });
+    assert_eq!(res, Err(MdbxError::NotFound));
+
+    let mut cursor = txn.cursor(db).unwrap();
+    let values: Vec<Vec<u8>> =
+        cursor.iter_dup_of::<Vec<u8>>(b"k").unwrap().collect::<ReadResult<_>>().unwrap();
+    assert_eq!(values, [b"1".to_vec(), b"2".to_vec()]);
+}
+
+#[test]
+fn test_savepoint_unsync() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let mut txn = env.begin_rw_unsync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+
+    txn.savepoint(|sp| sp.put(db, b"kept", b"1", WriteFlags::empty())).unwrap();
+    let res = txn.savepoint(|sp| {
+        sp.put(db, b"discarded", b"1", WriteFlags::empty())?;
+        Err::<(), _>(MdbxError::NotFound)
+    });
+    assert_eq!(res, Err(MdbxError::NotFound));
+
+    assert!(txn.get::<Vec<u8>>(db, b"kept").unwrap().is_some());
+    assert!(txn.get::<Vec<u8>>(db, b"discarded").unwrap().is_none());
+    txn.commit().unwrap();
+}
@@ -5,7 +5,7 @@ use signet_libmdbx::*;
 use std::{
     borrow::Cow,
     io::Write,
-    sync::{Arc, Barrier},
+    sync::{Arc, Barrier, Mutex},
     thread::{self, JoinHandle},
 };
 use tempfile::tempdir;
@@ -33,13 +33,13 @@ fn test_put_get_del_impl<RwTx, RoTx>(
 
     let txn = begin_rw(&env).unwrap();
     let db = txn.open_db(None).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"key1").unwrap(), Some(*b"val1"));
-    assert_eq!(txn.get(db.dbi(), b"key2").unwrap(), Some(*b"val2"));
-    assert_eq!(txn.get(db.dbi(), b"key3").unwrap(), Some(*b"val3"));
-    assert_eq!(txn.get::<()>(db.dbi(), b"key").unwrap(), None);
+    assert_eq!(txn.get(db, b"key1").unwrap(), Some(*b"val1"));
+    assert_eq!(txn.get(db, b"key2").unwrap(), Some(*b"val2"));
+    assert_eq!(txn.get(db, b"key3").unwrap(), Some(*b"val3"));
+    assert_eq!(txn.get::<()>(db, b"key").unwrap(), None);
 
     txn.del(db, b"key1", None).unwrap();
-    assert_eq!(txn.get::<()>(db.dbi(), b"key1").unwrap(), None);
+    assert_eq!(txn.get::<()>(db, b"key1").unwrap(), None);
 }
 
 #[test]
@@ -130,14 +130,14 @@ fn test_put_get_del_empty_key_impl<RwTx, RoTx>(
     let txn = begin_rw(&env).unwrap();
     let db = txn.create_db(None, Default::default()).unwrap();
     txn.put(db, b"", b"hello", WriteFlags::empty()).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"").unwrap(), Some(*b"hello"));
+    assert_eq!(txn.get(db, b"").unwrap(), Some(*b"hello"));
     txn.commit().unwrap();
 
     let txn = begin_rw(&env).unwrap();
     let db = txn.open_db(None).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"").unwrap(), Some(*b"hello"));
+    assert_eq!(txn.get(db, b"").unwrap(), Some(*b"hello"));
     txn.put(db, b"", b"", WriteFlags::empty()).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"").unwrap(), Some(*b""));
+    assert_eq!(txn.get(db, b"").unwrap(), Some(*b""));
 }
 
 #[test]
@@ -176,7 +176,7 @@ fn test_clear_db_impl<RwTx, RoTx>(
 
     let txn = begin_ro(&env).unwrap();
     let db = txn.open_db(None).unwrap();
-    assert_eq!(txn.get::<()>(db.dbi(), b"key").unwrap(), None);
+    assert_eq!(txn.get::<()>(db, b"key").unwrap(), None);
 }
 
 #[test]
@@ -211,9 +211,7 @@ fn test_drop_db_impl<RwTx, RoTx>(
         {
             let txn = begin_rw(&env).unwrap();
             let db = txn.open_db(Some("test")).unwrap();
-            unsafe {
-                txn.drop_db(db).unwrap();
-            }
+            txn.drop_db(db).unwrap();
             assert!(matches!(txn.open_db(Some("test")).unwrap_err(), MdbxError::NotFound));
             txn.commit().unwrap();
         }
@@ -236,6 +234,384 @@ fn test_drop_db_v2() {
     test_drop_db_impl(V2Factory::begin_rw, V2Factory::begin_ro);
 }
 
+fn test_drop_db_invalidates_handles_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    _begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(Some("test"), DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+    let copy = db;
+
+    // A live cursor pins the table.
+    let cursor = txn.cursor(db).unwrap();
+    assert_eq!(txn.drop_db(db).unwrap_err(), MdbxError::DbiInUse);
+    drop(cursor);
+
+    txn.drop_db(db).unwrap();
+
+    // Every copy of the handle is now stale.
+    assert_eq!(txn.put(copy, b"key", b"val", WriteFlags::empty()).unwrap_err(), MdbxError::BadDbi);
+    assert_eq!(txn.cursor(copy).unwrap_err(), MdbxError::BadDbi);
+    assert!(matches!(txn.get::<()>(copy, b"key"), Err(ReadError::Mdbx(MdbxError::BadDbi))));
+    assert_eq!(txn.drop_db(copy).unwrap_err(), MdbxError::BadDbi);
+
+    // Re-creating the table yields a fresh, usable handle.
+    let db = txn.create_db(Some("test"), DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+}
+
+#[test]
+fn test_drop_db_invalidates_handles_v1() {
+    test_drop_db_invalidates_handles_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_drop_db_invalidates_handles_v2() {
+    test_drop_db_invalidates_handles_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+fn test_shared_db_handles_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().set_max_dbs(2).open(dir.path()).unwrap();
+
+    // Handles to tables created by an aborted transaction are invalidated.
+    let txn = begin_rw(&env).unwrap();
+    let aborted = txn.create_db(Some("test"), DatabaseFlags::empty()).unwrap();
+    txn.open_db(Some("test")).unwrap();
+    txn.abort().unwrap();
+
+    let older = begin_ro(&env).unwrap();
+    let txn = begin_rw(&env).unwrap();
+    assert_eq!(txn.cursor(aborted).unwrap_err(), MdbxError::BadDbi);
+    assert_eq!(txn.open_db(Some("test")).unwrap_err(), MdbxError::NotFound);
+    let db = txn.create_db(Some("test"), DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    // Committed handles are not shared with older snapshots, in which the
+    // table does not exist.
+    assert_eq!(older.open_db(Some("test")).unwrap_err(), MdbxError::NotFound);
+    drop(older);
+
+    // Committed handles are reused by later transactions.
+    let txn = begin_ro(&env).unwrap();
+    let opened = txn.open_db(Some("test")).unwrap();
+    assert_eq!(opened.dbi(), db.dbi());
+    assert_eq!(txn.get::<Vec<u8>>(opened, b"key").unwrap().as_deref(), Some(&b"val"[..]));
+    drop(txn);
+
+    // Dropping the table removes it from the shared handles.
+    let txn = begin_rw(&env).unwrap();
+    txn.drop_db(txn.open_db(Some("test")).unwrap()).unwrap();
+    txn.commit().unwrap();
+
+    let txn = begin_ro(&env).unwrap();
+    assert_eq!(txn.cursor(db).unwrap_err(), MdbxError::BadDbi);
+    assert_eq!(txn.open_db(Some("test")).unwrap_err(), MdbxError::NotFound);
+}
+
+#[test]
+fn test_shared_db_handles_v1() {
+    test_shared_db_handles_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_shared_db_handles_v2() {
+    test_shared_db_handles_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+fn test_abort_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+    txn.abort().unwrap();
+
+    let txn = begin_ro(&env).unwrap();
+    let db = txn.open_db(None).unwrap();
+    assert!(txn.get::<()>(db, b"key").unwrap().is_none());
+    drop(txn);
+
+    // The write lock was released by the abort.
+    begin_rw(&env).unwrap().commit().unwrap();
+}
+
+#[test]
+fn test_abort_v1() {
+    test_abort_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_abort_v2() {
+    test_abort_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+fn test_cancel_handle_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+
+    let handle = txn.cancel_handle();
+    assert!(!handle.is_cancelled());
+    let remote = handle.clone();
+    thread::spawn(move || remote.cancel().unwrap()).join().unwrap();
+    assert!(handle.is_cancelled());
+
+    // Every later operation reports the break.
+    assert_eq!(
+        txn.put(db, b"key2", b"val", WriteFlags::empty()).unwrap_err(),
+        MdbxError::TxnBroken
+    );
+    assert!(matches!(txn.get::<()>(db, b"key"), Err(ReadError::Mdbx(MdbxError::TxnBroken))));
+    assert_eq!(txn.cursor(db).unwrap_err(), MdbxError::TxnBroken);
+
+    // Commit is refused, and nothing was written.
+    assert_eq!(txn.commit().unwrap_err(), MdbxError::TxnBroken);
+    let txn = begin_ro(&env).unwrap();
+    let db = txn.open_db(None).unwrap();
+    assert!(txn.get::<()>(db, b"key").unwrap().is_none());
+    drop(txn);
+
+    // Cancelling a finished transaction is a no-op.
+    handle.cancel().unwrap();
+
+    // A broken transaction can still be aborted explicitly.
+    let txn = begin_rw(&env).unwrap();
+    txn.cancel_handle().cancel().unwrap();
+    txn.abort().unwrap();
+
+    begin_rw(&env).unwrap().commit().unwrap();
+}
+
+#[test]
+fn test_cancel_handle_v1() {
+    test_cancel_handle_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_cancel_handle_v2() {
+    test_cancel_handle_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_cancel_during_nested_commits_v1() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    let handle = txn.cancel_handle();
+    let canceller = thread::spawn(move || {
+        thread::yield_now();
+        handle.cancel().unwrap();
+    });
+
+    let err = loop {
+        let res = txn.begin_nested_txn().and_then(|nested| {
+            nested.put(db, b"key", b"val", WriteFlags::empty())?;
+            nested.commit()
+        });
+        if let Err(err) = res {
+            break err;
+        }
+    };
+    canceller.join().unwrap();
+
+    assert_eq!(err, MdbxError::TxnBroken);
+    assert_eq!(txn.commit().unwrap_err(), MdbxError::TxnBroken);
+    env.begin_rw_sync().unwrap().commit().unwrap();
+}
+
+#[test]
+fn test_cancel_during_nested_commits_v2() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let mut txn = env.begin_rw_unsync().unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    let handle = txn.cancel_handle();
+    let canceller = thread::spawn(move || {
+        thread::yield_now();
+        handle.cancel().unwrap();
+    });
+
+    let err = loop {
+        let res = txn.begin_nested_txn().and_then(|nested| {
+            nested.put(db, b"key", b"val", WriteFlags::empty())?;
+            nested.commit()
+        });
+        if let Err(err) = res {
+            break err;
+        }
+    };
+    canceller.join().unwrap();
+
+    assert_eq!(err, MdbxError::TxnBroken);
+    assert_eq!(txn.commit().unwrap_err(), MdbxError::TxnBroken);
+    env.begin_rw_unsync().unwrap().commit().unwrap();
+}
+
+fn test_user_data_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    _begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let txn = begin_rw(&env).unwrap();
+    assert!(txn.user_data::<u32>().is_none());
+
+    txn.set_user_data(1u32);
+    assert!(txn.user_data::<u64>().is_none());
+    assert_eq!(*txn.user_data::<u32>().unwrap(), 1);
+
+    // Handles do not lock the data, so it can be requested and replaced
+    // while one is alive. Existing handles keep the previous value.
+    let held = txn.user_data::<u32>().unwrap();
+    assert_eq!(*txn.user_data::<u32>().unwrap(), 1);
+    txn.set_user_data(2u32);
+    assert_eq!((*held, *txn.user_data::<u32>().unwrap()), (1, 2));
+    drop(held);
+
+    // Replacing the data drops the previous value.
+    let first = Arc::new(());
+    txn.set_user_data(first.clone());
+    assert_eq!(Arc::strong_count(&first), 2);
+    txn.set_user_data("other");
+    assert_eq!(Arc::strong_count(&first), 1);
+    assert!(txn.user_data::<u32>().is_none());
+    assert_eq!(*txn.user_data::<&str>().unwrap(), "other");
+
+    // The data is dropped along with the transaction.
+    let data = Arc::new(());
+    txn.set_user_data(data.clone());
+    txn.commit().unwrap();
+    assert_eq!(Arc::strong_count(&data), 1);
+
+    let txn = begin_rw(&env).unwrap();
+    txn.set_user_data(data.clone());
+    txn.abort().unwrap();
+    assert_eq!(Arc::strong_count(&data), 1);
+}
+
+#[test]
+fn test_user_data_v1() {
+    test_user_data_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_user_data_v2() {
+    test_user_data_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
+#[test]
+fn test_user_data_unsync_refcell() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    // Unsync write transactions never leave their thread, so the data need
+    // not be Sync.
+    let txn = env.begin_rw_unsync().unwrap();
+    txn.set_user_data(std::cell::RefCell::new(Vec::<u32>::new()));
+    txn.user_data::<std::cell::RefCell<Vec<u32>>>().unwrap().borrow_mut().push(1);
+    txn.user_data::<std::cell::RefCell<Vec<u32>>>().unwrap().borrow_mut().push(2);
+    assert_eq!(*txn.user_data::<std::cell::RefCell<Vec<u32>>>().unwrap().borrow(), [1, 2]);
+    assert!(txn.user_data::<u32>().is_none());
+    txn.commit().unwrap();
+}
+
+fn test_commit_hooks_impl<RwTx, RoTx>(
+    begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
+    _begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
+) where
+    RwTx: TestRwTxn,
+    RoTx: TestRoTxn,
+{
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let register = |txn: &RwTx, name: &'static str| {
+        let on_commit = events.clone();
+        txn.on_commit(move |info| on_commit.lock().unwrap().push((name, Some(info.txn_id()))));
+        let on_abort = events.clone();
+        txn.on_abort(move || on_abort.lock().unwrap().push((name, None)));
+    };
+
+    // Commit runs the commit callbacks with the committed txnid.
+    let txn = begin_rw(&env).unwrap();
+    let db = txn.create_db(None, DatabaseFlags::empty()).unwrap();
+    txn.put(db, b"key", b"val", WriteFlags::empty()).unwrap();
+    register(&txn, "commit");
+    assert!(events.lock().unwrap().is_empty());
+    txn.commit().unwrap();
+    let txn_id = env.info().unwrap().last_txnid() as u64;
+    assert_eq!(events.lock().unwrap().drain(..).collect::<Vec<_>>(), [("commit", Some(txn_id))]);
+
+    // Explicit abort and drop run the abort callbacks.
+    let txn = begin_rw(&env).unwrap();
+    register(&txn, "abort");
+    txn.abort().unwrap();
+    let txn = begin_rw(&env).unwrap();
+    register(&txn, "drop");
+    drop(txn);
+    assert_eq!(
+        events.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        [("abort", None), ("drop", None)]
+    );
+
+    // A refused commit runs the abort callbacks.
+    let txn = begin_rw(&env).unwrap();
+    register(&txn, "broken");
+    txn.cancel_handle().cancel().unwrap();
+    assert_eq!(txn.commit().unwrap_err(), MdbxError::TxnBroken);
+    assert_eq!(events.lock().unwrap().drain(..).collect::<Vec<_>>(), [("broken", None)]);
+}
+
+#[test]
+fn test_commit_hooks_v1() {
+    test_commit_hooks_impl(V1Factory::begin_rw, V1Factory::begin_ro);
+}
+
+#[test]
+fn test_commit_hooks_v2() {
+    test_commit_hooks_impl(V2Factory::begin_rw, V2Factory::begin_ro);
+}
+
 fn test_stat_impl<RwTx, RoTx>(
     begin_rw: impl Fn(&Environment) -> MdbxResult<RwTx>,
     begin_ro: impl Fn(&Environment) -> MdbxResult<RoTx>,
@@ -505,11 +881,11 @@ fn test_reserve_v1() {
 
     let txn = env.begin_rw_sync().unwrap();
     let db = txn.open_db(None).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"key1").unwrap(), Some(*b"val1"));
-    assert_eq!(txn.get::<()>(db.dbi(), b"key").unwrap(), None);
+    assert_eq!(txn.get(db, b"key1").unwrap(), Some(*b"val1"));
+    assert_eq!(txn.get::<()>(db, b"key").unwrap(), None);
 
     txn.del(db, b"key1", None).unwrap();
-    assert_eq!(txn.get::<()>(db.dbi(), b"key1").unwrap(), None);
+    assert_eq!(txn.get::<()>(db, b"key1").unwrap(), None);
 }
 
 /// Test reserve - V2 version
@@ -531,11 +907,11 @@ fn test_reserve_v2() {
 
     let txn = env.begin_rw_unsync().unwrap();
     let db = txn.open_db(None).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"key1").unwrap(), Some(*b"val1"));
-    assert_eq!(txn.get::<()>(db.dbi(), b"key").unwrap(), None);
+    assert_eq!(txn.get(db, b"key1").unwrap(), Some(*b"val1"));
+    assert_eq!(txn.get::<()>(db, b"key").unwrap(), None);
 
     txn.del(db, b"key1", None).unwrap();
-    assert_eq!(txn.get::<()>(db.dbi(), b"key1").unwrap(), None);
+    assert_eq!(txn.get::<()>(db, b"key1").unwrap(), None);
 }
 
 /// Test nested transactions - V1 only (V2 doesn't support nested txns)
@@ -552,13 +928,58 @@ fn test_nested_txn() {
         let nested = txn.begin_nested_txn().unwrap();
         let db = nested.open_db(None).unwrap();
         nested.put(db, b"key2", b"val2", WriteFlags::empty()).unwrap();
-        assert_eq!(nested.get(db.dbi(), b"key1").unwrap(), Some(*b"val1"));
-        assert_eq!(nested.get(db.dbi(), b"key2").unwrap(), Some(*b"val2"));
+        assert_eq!(nested.get(db, b"key1").unwrap(), Some(*b"val1"));
+        assert_eq!(nested.get(db, b"key2").unwrap(), Some(*b"val2"));
     }
 
     let db = txn.open_db(None).unwrap();
-    assert_eq!(txn.get(db.dbi(), b"key1").unwrap(), Some(*b"val1"));
-    assert_eq!(txn.get::<()>(db.dbi(), b"key2").unwrap(), None);
+    assert_eq!(txn.get(db, b"key1").unwrap(), Some(*b"val1"));
+    assert_eq!(txn.get::<()>(db, b"key2").unwrap(), None);
+}
+
+/// Test commit and abort callbacks on nested transactions - V1 only
+#[test]
+fn test_nested_txn_hooks() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let register = |txn: &tx::aliases::RwTxSync, name: &'static str| {
+        let on_commit = events.clone();
+        txn.on_commit(move |_| on_commit.lock().unwrap().push(format!("{name} committed")));
+        let on_abort = events.clone();
+        txn.on_abort(move || on_abort.lock().unwrap().push(format!("{name} aborted")));
+    };
+
+    let txn = env.begin_rw_sync().unwrap();
+    register(&txn, "parent");
+
+    // An aborted nested transaction runs its abort callbacks immediately.
+    let nested = txn.begin_nested_txn().unwrap();
+    register(&nested, "aborted child");
+    drop(nested);
+    assert_eq!(*events.lock().unwrap(), ["aborted child aborted"]);
+
+    // A committed nested transaction hands its callbacks to the parent.
+    let nested = txn.begin_nested_txn().unwrap();
+    register(&nested, "committed child");
+    nested.commit().unwrap();
+    assert_eq!(events.lock().unwrap().len(), 1);
+
+    txn.commit().unwrap();
+    assert_eq!(
+        *events.lock().unwrap(),
+        ["aborted child aborted", "parent committed", "committed child committed"]
+    );
+
+    // Merged callbacks follow the parent when it aborts.
+    events.lock().unwrap().clear();
+    let txn = env.begin_rw_sync().unwrap();
+    let nested = txn.begin_nested_txn().unwrap();
+    register(&nested, "child");
+    nested.commit().unwrap();
+    drop(txn);
+    assert_eq!(*events.lock().unwrap(), ["child aborted"]);
 }
 
 /// Test concurrent readers with single writer - V1 only (V2 is !Sync)
@@ -582,14 +1003,14 @@ fn test_concurrent_readers_single_writer() {
             {
                 let txn = reader_env.begin_ro_sync().unwrap();
                 let db = txn.open_db(None).unwrap();
-                assert_eq!(txn.get::<()>(db.dbi(), key).unwrap(), None);
+                assert_eq!(txn.get::<()>(db, key).unwrap(), None);
             }
             reader_barrier.wait();
             reader_barrier.wait();
             {
                 let txn = reader_env.begin_ro_sync().unwrap();
                 let db = txn.open_db(None).unwrap();
-                txn.get::<[u8; 3]>(db.dbi(), key).unwrap().unwrap() == *val
+                txn.get::<[u8; 3]>(db, key).unwrap().unwrap() == *val
             }
         }));
     }
@@ -636,7 +1057,7 @@ fn test_concurrent_writers() {
     for i in 0..n {
         assert_eq!(
             Cow::<Vec<u8>>::Owned(format!("{val}{i}").into_bytes()),
-            txn.get(db.dbi(), format!("{key}{i}").as_bytes()).unwrap().unwrap()
+            txn.get(db, format!("{key}{i}").as_bytes()).unwrap().unwrap()
         );
     }
 }
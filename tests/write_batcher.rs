@@ -0,0 +1,130 @@
+#![allow(missing_docs)]
+use signet_libmdbx::*;
+use std::{sync::mpsc::channel, thread};
+use tempfile::tempdir;
+
+#[test]
+fn test_write_batcher_concurrent_writers() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let db = env.begin_ro_sync().unwrap().open_db(None).unwrap();
+    let batcher = env.write_batcher().unwrap();
+
+    let threads: Vec<_> = (0..8)
+        .map(|t| {
+            let batcher = batcher.clone();
+            thread::spawn(move || {
+                let receivers: Vec<_> = (0..10)
+                    .map(|i| {
+                        let mut batch = WriteBatch::new();
+                        batch.put(
+                            db,
+                            format!("key{t}-{i}"),
+                            format!("val{t}-{i}"),
+                            WriteFlags::empty(),
+                        );
+                        batcher.submit(batch)
+                    })
+                    .collect();
+                receivers.into_iter().all(|rx| rx.recv().unwrap().is_ok())
+            })
+        })
+        .collect();
+    assert!(threads.into_iter().all(|t| t.join().unwrap()));
+
+    let txn = env.begin_ro_sync().unwrap();
+    assert_eq!(txn.db_stat(&db).unwrap().entries(), 80);
+    let val: Vec<u8> = txn.get(db, b"key7-9").unwrap().unwrap();
+    assert_eq!(val, b"val7-9");
+}
+
+#[test]
+fn test_write_batcher_isolates_failures() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let txn = env.begin_rw_sync().unwrap();
+    let db = txn.open_db(None).unwrap();
+    txn.put(db, b"taken", b"val", WriteFlags::empty()).unwrap();
+    txn.commit().unwrap();
+
+    let batcher = env.write_batcher().unwrap();
+
+    // Hold the batcher busy, so the following submissions share a group.
+    let (release_tx, release_rx) = channel::<()>();
+    let blocker = batcher.submit_fn(move |_| {
+        release_rx.recv().unwrap();
+        Ok(())
+    });
+
+    let mut ok = WriteBatch::new();
+    ok.put(db, b"a", b"1", WriteFlags::empty());
+    let ok = batcher.submit(ok);
+
+    let mut failing = WriteBatch::new();
+    failing.put(db, b"b", b"2", WriteFlags::empty()).put(
+        db,
+        b"taken",
+        b"2",
+        WriteFlags::NO_OVERWRITE,
+    );
+    let failing = batcher.submit(failing);
+
+    let closure = batcher.submit_fn(move |txn| txn.put(db, b"c", b"3", WriteFlags::empty()));
+
+    release_tx.send(()).unwrap();
+    assert_eq!(blocker.recv().unwrap(), Ok(()));
+    assert_eq!(ok.recv().unwrap(), Ok(()));
+    assert_eq!(failing.recv().unwrap(), Err(MdbxError::KeyExist));
+    assert_eq!(closure.recv().unwrap(), Ok(()));
+
+    let txn = env.begin_ro_sync().unwrap();
+    assert!(txn.get::<Vec<u8>>(db, b"a").unwrap().is_some());
+    assert!(txn.get::<Vec<u8>>(db, b"b").unwrap().is_none());
+    assert!(txn.get::<Vec<u8>>(db, b"c").unwrap().is_some());
+    assert_eq!(txn.get::<Vec<u8>>(db, b"taken").unwrap().unwrap(), b"val");
+}
+
+#[test]
+fn test_write_batcher_isolates_panics() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    let db = env.begin_ro_sync().unwrap().open_db(None).unwrap();
+
+    let batcher = env.write_batcher().unwrap();
+
+    // Hold the batcher busy, so the following submissions share a group.
+    let (release_tx, release_rx) = channel::<()>();
+    let blocker = batcher.submit_fn(move |_| {
+        release_rx.recv().unwrap();
+        Ok(())
+    });
+    let panicking = batcher.submit_fn(move |txn| {
+        txn.put(db, b"a", b"1", WriteFlags::empty())?;
+        panic!("submission failed");
+    });
+    let closure = batcher.submit_fn(move |txn| txn.put(db, b"b", b"2", WriteFlags::empty()));
+
+    release_tx.send(()).unwrap();
+    assert_eq!(blocker.recv().unwrap(), Ok(()));
+    assert_eq!(panicking.recv().unwrap(), Err(MdbxError::SubmissionPanicked));
+    assert_eq!(closure.recv().unwrap(), Ok(()));
+
+    // The batcher keeps running.
+    let later = batcher.submit_fn(move |txn| txn.put(db, b"c", b"3", WriteFlags::empty()));
+    assert_eq!(later.recv().unwrap(), Ok(()));
+
+    let txn = env.begin_ro_sync().unwrap();
+    assert!(txn.get::<Vec<u8>>(db, b"a").unwrap().is_none());
+    assert!(txn.get::<Vec<u8>>(db, b"b").unwrap().is_some());
+    assert!(txn.get::<Vec<u8>>(db, b"c").unwrap().is_some());
+}
+
+#[test]
+fn test_write_batcher_write_map() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().write_map().open(dir.path()).unwrap();
+    assert_eq!(
+        env.write_batcher().unwrap_err(),
+        MdbxError::NestedTransactionsUnsupportedWithWriteMap
+    );
+}